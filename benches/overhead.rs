@@ -1,5 +1,6 @@
 use criterion::Criterion;
 use criterion::{criterion_group, criterion_main};
+use kameo::error::Infallible;
 use kameo::mailbox::unbounded::UnboundedMailbox;
 use kameo::request::MessageSend;
 use kameo::{
@@ -36,6 +37,60 @@ fn actor(c: &mut Criterion) {
             actor_ref.ask(0).send().await.unwrap();
         });
     });
+
+    // Small primitive replies are sent without boxing, unlike larger replies
+    struct Large(u32);
+
+    impl Message<Large> for BenchActor {
+        type Reply = [u64; 4];
+
+        async fn handle(
+            &mut self,
+            Large(msg): Large,
+            _ctx: Context<'_, Self, Self::Reply>,
+        ) -> Self::Reply {
+            [msg as u64; 4]
+        }
+    }
+
+    c.bench_function("actor_sync_messages_boxed_reply", |b| {
+        b.to_async(&rt).iter(|| async {
+            actor_ref.ask(Large(0)).send().await.unwrap();
+        });
+    });
+
+    // Told messages whose reply can't error skip checking the reply for an error
+    struct Fallible(u32);
+
+    impl Message<Fallible> for BenchActor {
+        type Reply = Result<u32, Infallible>;
+
+        async fn handle(
+            &mut self,
+            Fallible(msg): Fallible,
+            _ctx: Context<'_, Self, Self::Reply>,
+        ) -> Self::Reply {
+            Ok(msg)
+        }
+    }
+
+    c.bench_function("actor_tell_infallible_reply", |b| {
+        b.to_async(&rt).iter(|| async {
+            for _ in 0..100 {
+                actor_ref.tell(0).send().await.unwrap();
+            }
+            actor_ref.flush().await.unwrap();
+        });
+    });
+
+    c.bench_function("actor_tell_fallible_reply", |b| {
+        b.to_async(&rt).iter(|| async {
+            for _ in 0..100 {
+                actor_ref.tell(Fallible(0)).send().await.unwrap();
+            }
+            actor_ref.flush().await.unwrap();
+        });
+    });
 }
 
 fn plain(c: &mut Criterion) {
@@ -21,6 +21,12 @@ impl MyActor {
         self.count
     }
 
+    /// Returns a `&str`, converted into the `String` reply
+    #[message(reply = String)]
+    fn name(&self) -> &'static str {
+        "my actor"
+    }
+
     #[message]
     fn force_err(&self) -> Result<(), i32> {
         Err(3)
@@ -53,6 +59,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let count = my_actor_ref.ask(Inc { amount: 3 }).await?;
     info!("Count is {count}");
 
+    // Reply converted with `Into`
+    let name = my_actor_ref.ask(Name).await?;
+    info!("Name is {name}");
+
     // Increment the count by 50 in the background
     my_actor_ref.tell(Inc { amount: 50 }).send_sync()?;
 
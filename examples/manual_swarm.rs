@@ -76,7 +76,22 @@ fn handle_event(
     event: SwarmEvent<CustomBehaviourEvent>,
 ) {
     match event {
-        SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+        SwarmEvent::ConnectionEstablished {
+            peer_id,
+            connection_id,
+            endpoint,
+            num_established,
+            ..
+        } => {
+            swarm_handler.handle_event(
+                swarm,
+                ActorSwarmEvent::ConnectionEstablished {
+                    peer_id,
+                    connection_id,
+                    endpoint,
+                    num_established,
+                },
+            );
             let name = swarm.local_peer_id().to_string();
             swarm
                 .behaviour_mut()
@@ -172,6 +187,7 @@ impl SwarmBehaviour for CustomBehaviour {
         actor_remote_id: Cow<'static, str>,
         message_remote_id: Cow<'static, str>,
         payload: Vec<u8>,
+        compression: Option<Cow<'static, str>>,
         mailbox_timeout: Option<Duration>,
         reply_timeout: Option<Duration>,
         immediate: bool,
@@ -183,6 +199,7 @@ impl SwarmBehaviour for CustomBehaviour {
                 actor_remote_id,
                 message_remote_id,
                 payload,
+                compression,
                 mailbox_timeout,
                 reply_timeout,
                 immediate,
@@ -197,6 +214,7 @@ impl SwarmBehaviour for CustomBehaviour {
         actor_remote_id: Cow<'static, str>,
         message_remote_id: Cow<'static, str>,
         payload: Vec<u8>,
+        compression: Option<Cow<'static, str>>,
         mailbox_timeout: Option<Duration>,
         immediate: bool,
     ) -> OutboundRequestId {
@@ -207,6 +225,7 @@ impl SwarmBehaviour for CustomBehaviour {
                 actor_remote_id,
                 message_remote_id,
                 payload,
+                compression,
                 mailbox_timeout,
                 immediate,
             },
@@ -265,6 +284,16 @@ impl SwarmBehaviour for CustomBehaviour {
         )
     }
 
+    fn heartbeat(&mut self, peer: &PeerId) -> OutboundRequestId {
+        self.actor_request_response
+            .send_request(peer, SwarmRequest::Heartbeat)
+    }
+
+    fn hello(&mut self, peer: &PeerId, compression: Vec<Cow<'static, str>>) -> OutboundRequestId {
+        self.actor_request_response
+            .send_request(peer, SwarmRequest::Hello { compression })
+    }
+
     fn send_ask_response(
         &mut self,
         channel: ResponseChannel<kameo::remote::SwarmResponse>,
@@ -310,6 +339,23 @@ impl SwarmBehaviour for CustomBehaviour {
             .send_response(channel, SwarmResponse::SignalLinkDied(result))
     }
 
+    fn send_heartbeat_response(
+        &mut self,
+        channel: ResponseChannel<SwarmResponse>,
+    ) -> Result<(), SwarmResponse> {
+        self.actor_request_response
+            .send_response(channel, SwarmResponse::Heartbeat)
+    }
+
+    fn send_hello_response(
+        &mut self,
+        channel: ResponseChannel<SwarmResponse>,
+        compression: Vec<Cow<'static, str>>,
+    ) -> Result<(), SwarmResponse> {
+        self.actor_request_response
+            .send_response(channel, SwarmResponse::Hello { compression })
+    }
+
     fn kademlia_add_address(&mut self, peer: &PeerId, address: Multiaddr) -> kad::RoutingUpdate {
         self.kademlia.add_address(peer, address)
     }
@@ -4,7 +4,7 @@ use syn::{
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
     spanned::Spanned,
-    token, DeriveInput, Generics, Ident, LitInt, LitStr, Token,
+    token, DeriveInput, Generics, Ident, LitInt, LitStr, Token, Type,
 };
 
 pub struct DeriveActor {
@@ -33,6 +33,15 @@ impl ToTokens for DeriveActor {
             MailboxKind::Unbounded => quote! {
                 ::kameo::mailbox::unbounded::UnboundedMailbox<Self>
             },
+            MailboxKind::Lifo => quote! {
+                ::kameo::mailbox::lifo::LifoMailbox<Self>
+            },
+            MailboxKind::Fair => quote! {
+                ::kameo::mailbox::fair::FairMailbox<Self>
+            },
+            MailboxKind::Priority => quote! {
+                ::kameo::mailbox::priority::PriorityMailbox<Self>
+            },
         };
         let new_mailbox_expanded = match attrs.mailbox {
             MailboxKind::Bounded(cap) => {
@@ -44,8 +53,31 @@ impl ToTokens for DeriveActor {
             MailboxKind::Unbounded => quote! {
                 ::kameo::mailbox::unbounded::UnboundedMailbox::new()
             },
+            MailboxKind::Lifo => quote! {
+                ::kameo::mailbox::lifo::LifoMailbox::new()
+            },
+            MailboxKind::Fair => quote! {
+                ::kameo::mailbox::fair::FairMailbox::new()
+            },
+            MailboxKind::Priority => quote! {
+                ::kameo::mailbox::priority::PriorityMailbox::new()
+            },
         };
 
+        let handled_messages_expanded = attrs.messages.as_ref().map(|messages| {
+            let names = messages
+                .iter()
+                .map(|ty| ty.to_token_stream().to_string().replace(' ', ""));
+            let types = messages.iter();
+            quote! {
+                fn handled_messages() -> &'static [&'static str] {
+                    fn assert_handled<A: ::kameo::message::Message<M>, M: Send + 'static>() {}
+                    #( assert_handled::<Self, #types>(); )*
+                    &[ #( #names ),* ]
+                }
+            }
+        });
+
         tokens.extend(quote! {
             #[automatically_derived]
             impl #impl_generics ::kameo::actor::Actor for #ident #ty_generics #where_clause {
@@ -58,6 +90,8 @@ impl ToTokens for DeriveActor {
                 fn new_mailbox() -> (Self::Mailbox, <Self::Mailbox as ::kameo::mailbox::Mailbox<Self>>::Receiver) {
                     #new_mailbox_expanded
                 }
+
+                #handled_messages_expanded
             }
         });
     }
@@ -93,6 +127,7 @@ impl Parse for DeriveActor {
 struct DeriveActorAttrs {
     name: Option<LitStr>,
     mailbox: MailboxKind,
+    messages: Option<Punctuated<Type, Token![,]>>,
 }
 
 impl Parse for DeriveActorAttrs {
@@ -101,6 +136,7 @@ impl Parse for DeriveActorAttrs {
         enum Attr {
             Name(name, LitStr),
             Mailbox(mailbox, MailboxKind),
+            Messages(messages, Punctuated<Type, Token![,]>),
         }
         let attrs: Punctuated<Attr, Token![,]> =
             Punctuated::parse_terminated_with(input, |input| {
@@ -115,6 +151,12 @@ impl Parse for DeriveActorAttrs {
                     let _: Token![=] = input.parse()?;
                     let mailbox: MailboxKind = input.parse()?;
                     Ok(Attr::Mailbox(key, mailbox))
+                } else if lookahead.peek(messages) {
+                    let key: messages = input.parse()?;
+                    let content;
+                    parenthesized!(content in input);
+                    let messages = Punctuated::parse_terminated(&content)?;
+                    Ok(Attr::Messages(key, messages))
                 } else {
                     Err(lookahead.error())
                 }
@@ -122,6 +164,7 @@ impl Parse for DeriveActorAttrs {
 
         let mut name = None;
         let mut mailbox = None;
+        let mut messages = None;
 
         for attr in attrs {
             match attr {
@@ -139,30 +182,53 @@ impl Parse for DeriveActorAttrs {
                         return Err(syn::Error::new(key.span, "mailbox already set"));
                     }
                 }
+                Attr::Messages(key, tys) => {
+                    if messages.is_none() {
+                        messages = Some(tys);
+                    } else {
+                        return Err(syn::Error::new(key.span, "messages already set"));
+                    }
+                }
             }
         }
 
         Ok(DeriveActorAttrs {
             name,
             mailbox: mailbox.unwrap_or_default(),
+            messages,
         })
     }
 }
 
 custom_keyword!(name);
 custom_keyword!(mailbox);
+custom_keyword!(messages);
 custom_keyword!(bounded);
 custom_keyword!(unbounded);
+custom_keyword!(lifo);
+custom_keyword!(fair);
+custom_keyword!(priority);
 
 #[derive(Debug, Default)]
 enum MailboxKind {
     Bounded(Option<usize>),
     #[default]
     Unbounded,
+    Lifo,
+    Fair,
+    Priority,
 }
 
 impl Parse for MailboxKind {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            // "bounded(10)"
+            let lit: LitStr = input.parse()?;
+            return lit
+                .parse_with(MailboxKind::parse)
+                .map_err(|err| syn::Error::new(lit.span(), format!("invalid mailbox: {err}")));
+        }
+
         let lookahead = input.lookahead1();
         if lookahead.peek(bounded) {
             let _: bounded = input.parse()?;
@@ -186,6 +252,15 @@ impl Parse for MailboxKind {
         } else if lookahead.peek(unbounded) {
             let _: unbounded = input.parse()?;
             Ok(MailboxKind::Unbounded)
+        } else if lookahead.peek(lifo) {
+            let _: lifo = input.parse()?;
+            Ok(MailboxKind::Lifo)
+        } else if lookahead.peek(fair) {
+            let _: fair = input.parse()?;
+            Ok(MailboxKind::Fair)
+        } else if lookahead.peek(priority) {
+            let _: priority = input.parse()?;
+            Ok(MailboxKind::Priority)
         } else {
             Err(lookahead.error())
         }
@@ -21,6 +21,8 @@ impl ToTokens for DeriveReply {
                 type Error = ::kameo::error::Infallible;
                 type Value = Self;
 
+                const CAN_ERROR: bool = false;
+
                 #[inline]
                 fn to_result(self) -> ::std::result::Result<Self::Ok, Self::Error> {
                     ::std::result::Result::Ok(self)
@@ -37,12 +37,33 @@ use syn::parse_macro_input;
 ///     pub fn dec(&self, amount: u32) {
 ///         self.count -= amount as i64;
 ///     }
+///
+///     /// Reply type converted with `Into`
+///     #[message(reply = String)]
+///     pub fn name(&self) -> &'static str {
+///         "counter"
+///     }
 /// }
 ///
 /// counter_ref.ask(Inc { amount: 5 }).await?;
 /// counter_ref.ask(Dec { amount: 2 }.clone()).await?;
+/// let name: String = counter_ref.ask(Name).await?;
 /// ```
 ///
+/// # Reply conversions
+///
+/// By default, the message's reply type is the return type of the method. Using `#[message(reply = T)]`
+/// sets the reply type to `T` instead, and the method's return value is converted with [`Into::into`].
+///
+/// Either way, the reply type must implement `Reply`. If it doesn't, the compile error points at the method's
+/// return type, or at `T` when it's overridden. Custom types can implement it with `#[derive(Reply)]`.
+///
+/// The conversion is applied to the whole return value. For example, with `reply = Result<String, MyError>`,
+/// the method must return a type which implements `Into<Result<String, MyError>>`. `Result` has no blanket
+/// conversion between its `Ok` and `Err` types, so a method returning `Result<&str, MyError>` won't compile.
+/// Use `?` or `.map(Into::into)` inside the method in that case. Errors in the converted reply are handled the
+/// same as any other reply: they're returned to the caller with `ask`, or stop the actor when sent with `tell`.
+///
 /// <details>
 /// <summary>See expanded code</summary>
 ///
@@ -89,6 +110,15 @@ pub fn messages(_attr: TokenStream, item: TokenStream) -> TokenStream {
 ///  - `bounded` (default capacity of 1000)
 ///  - `bounded(64)` (custom capacity of 64)
 ///  - `unbounded`
+///  - `lifo` (unbounded, processing the most recently sent message first)
+///  - `fair` (unbounded, taking turns processing messages from each sender)
+///  - `priority` (unbounded, processing the message with the highest priority first)
+///
+/// The value may also be written as a string literal, such as `mailbox = "bounded(64)"`.
+///
+/// The `#[actor(messages(Foo, Bar))]` attribute can be specified to populate
+/// [Actor::handled_messages](https://docs.rs/kameo/latest/kameo/actor/trait.Actor.html#method.handled_messages)
+/// with the names of the listed message types. Each type must be handled by the actor, or a compile error is emitted.
 ///
 ///
 /// # Example
@@ -25,6 +25,28 @@ struct Message {
     fields: Punctuated<Field, Token![,]>,
     attrs: Vec<TokenStream>,
     generics: Generics,
+    reply: Option<Type>,
+}
+
+enum MessageArg {
+    Reply(Type),
+    Attr(Meta),
+}
+
+impl Parse for MessageArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) && input.peek2(Token![=]) {
+            let fork = input.fork();
+            let ident: Ident = fork.parse()?;
+            if ident == "reply" {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                return Ok(MessageArg::Reply(input.parse()?));
+            }
+        }
+
+        Ok(MessageArg::Attr(input.parse()?))
+    }
 }
 
 impl
@@ -77,6 +99,7 @@ impl
             fields,
             attrs,
             generics,
+            reply: None,
         })
     }
 }
@@ -101,6 +124,7 @@ impl Messages {
                             .collect();
 
                         let mut is_message = false;
+                        let mut reply = None;
                         impl_item_fn.attrs.retain(|attr| {
                             if is_message {
                                 return true;
@@ -125,10 +149,20 @@ impl Messages {
                                         return true;
                                     }
 
-                                    let args_res = Punctuated::<Meta, Token![,]>::parse_separated_nonempty.parse2(list.tokens.clone());
+                                    let args_res = Punctuated::<MessageArg, Token![,]>::parse_separated_nonempty.parse2(list.tokens.clone());
                                     match args_res {
                                         Ok(items) => {
-                                            attrs.extend(items.into_iter().map(|attr| quote! { #[ #attr ] }));
+                                            for item in items {
+                                                match item {
+                                                    MessageArg::Reply(ty) => {
+                                                        if reply.is_some() {
+                                                            errors.push(syn::Error::new(ty.span(), "reply specified multiple times"));
+                                                        }
+                                                        reply = Some(ty);
+                                                    }
+                                                    MessageArg::Attr(attr) => attrs.push(quote! { #[ #attr ] }),
+                                                }
+                                            }
                                         },
                                         Err(err) => {
                                             errors.push(err);
@@ -231,7 +265,7 @@ impl Messages {
                                 field_doc_attrs,
                                 generics,
                             )) {
-                                Ok(message) => Some(message),
+                                Ok(message) => Some(Message { reply, ..message }),
                                 Err(err) => {
                                     errors.push(err);
                                     None
@@ -314,6 +348,7 @@ impl Messages {
                  ident: msg_ident,
                  fields,
                  generics,
+                 reply: reply_override,
                  ..
              }| {
                 let mut all_generics = item_impl.generics.clone();
@@ -335,11 +370,12 @@ impl Messages {
                 let self_ref = quote! { &mut self };
                 let msg = quote_spanned! {self_span=> msg: #msg_ident #msg_ty_generics };
                 let fn_ident = &sig.ident;
-                let reply = match sig.output.clone() {
-                    ReturnType::Default => parse_quote_spanned! {sig.output.span()=>
+                let reply = match (reply_override, sig.output.clone()) {
+                    (Some(ty), _) => Box::new(ty.clone()),
+                    (None, ReturnType::Default) => parse_quote_spanned! {sig.output.span()=>
                         ()
                     },
-                    ReturnType::Type(_, ty) => ty,
+                    (None, ReturnType::Type(_, ty)) => ty,
                 };
                 let await_tokens = sig.asyncness.map(|_| quote_spanned! {sig.asyncness.span()=>
                     .await
@@ -352,13 +388,23 @@ impl Messages {
                     }
                 });
 
+                let handle_body = if reply_override.is_some() {
+                    quote_spanned! {sig.span()=>
+                        ::std::convert::Into::into(self.#fn_ident(#( #params ),*) #await_tokens)
+                    }
+                } else {
+                    quote_spanned! {sig.span()=>
+                        self.#fn_ident(#( #params ),*) #await_tokens
+                    }
+                };
+
                 quote_spanned! {sig.span()=>
                     #[automatically_derived]
                     impl #impl_generics ::kameo::message::#trait_name<#msg_ident #msg_ty_generics> for #actor_ident #actor_ty_generics #where_clause {
                         type Reply = #reply;
 
                         async fn handle(#self_ref, #[allow(unused_variables)] #msg, _ctx: ::kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply {
-                            self.#fn_ident(#( #params ),*) #await_tokens
+                            #handle_body
                         }
                     }
                 }
@@ -22,24 +22,52 @@
 //! [`on_panic`]: Actor::on_panic
 
 mod actor_ref;
+pub mod bulkhead;
+pub mod circuit_breaker;
+mod graph;
+pub mod group;
 mod id;
 mod kind;
+mod mapped;
+mod migrate;
+#[cfg(feature = "tracing")]
+mod panic_hook;
 pub mod pool;
 pub mod pubsub;
+mod recipient;
+mod runtime;
+pub mod saga;
+mod sequence;
+mod set;
 mod spawn;
+mod stats;
+mod watch;
+pub mod watchdog;
 
-use std::any;
+use std::{any, time::Duration};
 
 use futures::Future;
 
 use crate::{
-    error::{ActorStopReason, BoxError, PanicError},
+    error::{ActorStopReason, BoxError, PanicError, Restartability},
     mailbox::Mailbox,
+    reply::StrandedReply,
 };
 
 pub use actor_ref::*;
+pub use graph::*;
 pub use id::*;
+pub use mapped::*;
+#[cfg(feature = "tracing")]
+pub use panic_hook::*;
+pub use recipient::*;
+pub(crate) use runtime::default_mailbox_capacity;
+pub use runtime::{init, RuntimeConfig};
+pub use sequence::{Sequence, SequenceCheck, SequenceTracker, SequencedSender};
+pub use set::*;
 pub use spawn::*;
+pub use stats::ActorStats;
+pub use watch::Watchable;
 
 /// Core behavior of an actor, including its lifecycle events and how it processes messages.
 ///
@@ -92,7 +120,8 @@ pub use spawn::*;
 ///
 /// # Lifecycle Hooks
 /// - `on_start`: Called when the actor starts. This is where initialization happens.
-/// - `on_panic`: Called when the actor encounters a panic or an error while processing a "tell" message.
+/// - `on_panic`: Called when the actor encounters a panic while processing a message.
+/// - `on_async_error`: Called when a "tell" message returns an error, routing it to `on_panic` by default.
 /// - `on_stop`: Called before the actor is stopped. This allows for cleanup tasks.
 /// - `on_link_died`: Hook that is invoked when a linked actor dies.
 ///
@@ -119,12 +148,219 @@ pub trait Actor: Sized + Send + 'static {
 
     /// The name of the actor, which can be useful for logging or debugging.
     ///
+    /// To give individual actors of the same type their own names, spawn them with
+    /// [`spawn_named`].
+    ///
     /// # Default Implementation
     /// By default, this returns the type name of the actor.
     fn name() -> &'static str {
         any::type_name::<Self>()
     }
 
+    /// The type names of messages handled by the actor, useful for tooling such as generating a schema
+    /// or a debug endpoint describing the actor's interface.
+    ///
+    /// When using the derive macro, this can be populated with `#[actor(messages(Foo, Bar))]`,
+    /// which also checks at compile time that the actor implements [`Message`] for each type.
+    ///
+    /// # Default Implementation
+    /// By default, this returns an empty slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::{messages, Actor};
+    ///
+    /// #[derive(Actor)]
+    /// #[actor(messages(Inc))]
+    /// struct Counter {
+    ///     count: i64,
+    /// }
+    ///
+    /// #[messages]
+    /// impl Counter {
+    ///     #[message]
+    ///     fn inc(&mut self, amount: i64) {
+    ///         self.count += amount;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(Counter::handled_messages(), &["Inc"]);
+    /// ```
+    ///
+    /// [`Message`]: crate::message::Message
+    fn handled_messages() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// The maximum size in bytes of messages accepted by the actor, or `None` for no limit.
+    ///
+    /// Messages larger than this, as reported by [`Message::message_size`], are rejected with
+    /// [`SendError::TooLarge`](crate::error::SendError::TooLarge) before they're enqueued. See
+    /// [`MessageSize`](crate::message::MessageSize) for more information.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `None`.
+    ///
+    /// [`Message::message_size`]: crate::message::Message::message_size
+    fn max_message_bytes() -> Option<usize> {
+        None
+    }
+
+    /// The number of recently handled messages to record, for debugging panics.
+    ///
+    /// When non-zero, the actor keeps a ring buffer of the last messages it handled, with their names and timing.
+    /// If a message handler panics, or returns an error with no caller to receive it, the history is logged and
+    /// attached to the [`PanicError`] passed to [`on_panic`](Actor::on_panic), where it can be read with
+    /// [`PanicError::message_history`]. The message which failed is the last entry.
+    ///
+    /// Messages aren't required to implement `Debug`, so each entry records the message's type name rather than
+    /// its contents.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `0`, and no history is recorded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::{Actor, PreparedActor};
+    /// use kameo::error::ActorStopReason;
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    /// # use kameo::message::{Context, Message};
+    ///
+    /// struct Parser;
+    ///
+    /// impl Actor for Parser {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     fn record_history() -> usize {
+    ///         50
+    ///     }
+    /// }
+    ///
+    /// struct Parse(&'static str);
+    /// #
+    /// # impl Message<Parse> for Parser {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, Parse(s): Parse, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    /// #         s.parse::<u32>().unwrap();
+    /// #     }
+    /// # }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let prepared_actor = PreparedActor::new();
+    /// prepared_actor.actor_ref().tell(Parse("1")).await?;
+    /// prepared_actor.actor_ref().tell(Parse("one")).await?;
+    ///
+    /// let (_, reason) = prepared_actor.spawn(Parser).await?;
+    /// let ActorStopReason::Panicked(err) = reason else { panic!() };
+    /// assert_eq!(err.message_history().len(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    fn record_history() -> usize {
+        0
+    }
+
+    /// Whether to abort a message handler when the caller of an [`ask`](ActorRef::ask) request stops waiting for
+    /// its reply.
+    ///
+    /// A caller stops waiting when it drops the reply future, such as when a client disconnects or a surrounding
+    /// timeout elapses. By default, the handler still runs to completion and its reply is discarded. Handlers can
+    /// check for this themselves with [`Context::is_reply_abandoned`](crate::message::Context::is_reply_abandoned).
+    ///
+    /// When this returns `true`, the handler is dropped at its next `.await` once the caller stops waiting, saving
+    /// the work of abandoned requests. The actor carries on with its next message, so handlers of actors which opt in
+    /// should leave the actor's state consistent at every `.await`. Tell requests have no caller, and are always
+    /// handled to completion.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `false`.
+    fn abort_abandoned_requests() -> bool {
+        false
+    }
+
+    /// How long the actor can go without receiving a message before it stops itself.
+    ///
+    /// This is useful for ephemeral actors, such as one per session or connection, which would otherwise be leaked
+    /// if their owner forgets to stop them. The timeout restarts whenever the actor receives a message or other
+    /// signal, and only runs while its mailbox is empty, so an actor with messages queued is never idle. Once it
+    /// elapses, the actor stops with [`ActorStopReason::Normal`].
+    ///
+    /// # Default Implementation
+    /// By default, this returns `None`, and the actor never stops for being idle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::Actor;
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    ///
+    /// struct Session;
+    ///
+    /// impl Actor for Session {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     fn idle_timeout() -> Option<Duration> {
+    ///         Some(Duration::from_millis(10))
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let session = kameo::spawn(Session);
+    /// session.wait_for_stop().await;
+    /// # });
+    /// ```
+    fn idle_timeout() -> Option<Duration> {
+        None
+    }
+
+    /// The maximum time the actor runs for, however busy it is, before it stops itself.
+    ///
+    /// The lifetime starts once the actor is spawned. When it's reached, the actor stops with
+    /// [`ActorStopReason::Normal`] once it has finished the message it's handling, without handling any messages
+    /// still queued in its mailbox. Callers of those messages receive
+    /// [`SendError::ActorNotRunning`](crate::error::SendError::ActorNotRunning), as if the actor was stopped.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `None`, and the actor runs until it's stopped.
+    fn max_lifetime() -> Option<Duration> {
+        None
+    }
+
+    /// The level of the `handle_message` spans created for messages handled by the actor.
+    ///
+    /// This allows a noisy actor, such as one handling frequent health checks, to be logged at a lower level than
+    /// others. The spans are still subject to the tracing subscriber's filters, so a span at
+    /// [`Level::TRACE`](tracing::Level::TRACE) is only recorded if the subscriber enables `TRACE` for the
+    /// `kameo::message` target. If the span is disabled, the handler runs in the sender's span instead.
+    ///
+    /// # Default Implementation
+    /// By default, this returns [`Level::DEBUG`](tracing::Level::DEBUG).
+    #[cfg(feature = "tracing")]
+    fn log_level() -> tracing::Level {
+        tracing::Level::DEBUG
+    }
+
+    /// Whether to log the messages left in the mailbox when the actor stops.
+    ///
+    /// Messages still queued when the actor stops, such as when it's [killed](ActorRef::kill) or when they were sent
+    /// after [`stop_gracefully`](ActorRef::stop_gracefully), are never handled. Callers of pending ask requests receive
+    /// [`SendError::ActorNotRunning`](crate::error::SendError::ActorNotRunning), but tell requests are dropped
+    /// silently. When enabled, the actor logs a warning with the number of each type of message dropped, so work
+    /// lost during an abrupt stop doesn't go unnoticed.
+    ///
+    /// Messages aren't required to implement `Debug`, so each message is reported by its type name.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `false`, and nothing is logged.
+    #[cfg(feature = "tracing")]
+    fn log_unprocessed_messages() -> bool {
+        false
+    }
+
     /// Creates a new mailbox for the actor. This sets up the message queue and receiver for the actor.
     ///
     /// # Returns
@@ -135,6 +371,66 @@ pub trait Actor: Sized + Send + 'static {
         Self::Mailbox::default_mailbox()
     }
 
+    /// Classifies an error returned by one of the actor's lifecycle hooks, such as [`on_start`](Actor::on_start)
+    /// or [`InitActor::init`], as transient or permanent.
+    ///
+    /// The hint is carried by the [`PanicError`] in [`ActorStopReason::Panicked`], so a supervisor can read it with
+    /// [`PanicError::restartability`] in [`on_link_died`](Actor::on_link_died), restarting the actor with a backoff
+    /// for transient failures, and escalating permanent ones.
+    ///
+    /// # Default Implementation
+    /// By default, all errors are [`Restartability::Permanent`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::{Actor, ActorRef, PreparedActor};
+    /// use kameo::error::{ActorStopReason, BoxError, Restartability};
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    ///
+    /// #[derive(Debug)]
+    /// enum ConnectError {
+    ///     Unreachable,
+    ///     InvalidConfig,
+    /// }
+    /// #
+    /// # impl std::fmt::Display for ConnectError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         std::fmt::Debug::fmt(self, f)
+    /// #     }
+    /// # }
+    /// #
+    /// # impl std::error::Error for ConnectError {}
+    ///
+    /// struct Connection;
+    ///
+    /// impl Actor for Connection {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     fn restartability(err: &BoxError) -> Restartability {
+    ///         match err.downcast_ref::<ConnectError>() {
+    ///             Some(ConnectError::Unreachable) => Restartability::Transient,
+    ///             Some(ConnectError::InvalidConfig) | None => Restartability::Permanent,
+    ///         }
+    ///     }
+    ///
+    ///     async fn on_start(&mut self, _actor_ref: ActorRef<Self>) -> Result<(), BoxError> {
+    ///         Err(Box::new(ConnectError::Unreachable))
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let (_, reason) = PreparedActor::new().spawn(Connection).await?;
+    /// let ActorStopReason::Panicked(err) = reason else { panic!() };
+    /// assert_eq!(err.restartability(), Restartability::Transient);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[allow(unused_variables)]
+    fn restartability(err: &BoxError) -> Restartability {
+        Restartability::Permanent
+    }
+
     /// Called when the actor starts, before it processes any messages.
     ///
     /// Messages sent internally by the actor during `on_start` are prioritized and processed
@@ -149,7 +445,8 @@ pub trait Actor: Sized + Send + 'static {
         async { Ok(()) }
     }
 
-    /// Called when the actor encounters a panic or an error during "tell" message handling.
+    /// Called when the actor encounters a panic during message handling, or when a lifecycle hook
+    /// such as [`on_async_error`](Actor::on_async_error) requests the actor stop with [`ActorStopReason::Panicked`].
     ///
     /// This method gives the actor an opportunity to clean up or reset its state and determine
     /// whether it should be stopped or continue processing messages.
@@ -169,11 +466,128 @@ pub trait Actor: Sized + Send + 'static {
         async move { Ok(Some(ActorStopReason::Panicked(err))) }
     }
 
+    /// Called after [`on_panic`](Actor::on_panic) lets the actor continue, if the caller of the message which
+    /// panicked is still waiting for a reply.
+    ///
+    /// The hook can send a fallback reply, such as a default or degraded value, with [`StrandedReply::send`].
+    /// By default, the reply is dropped, and the caller receives
+    /// [`SendError::ActorStopped`](crate::error::SendError::ActorStopped). If `on_panic` stops the actor instead,
+    /// this hook isn't called, and the caller receives [`SendError::ActorDied`](crate::error::SendError::ActorDied)
+    /// with the reason the actor stopped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::WeakActorRef;
+    /// use kameo::error::{ActorStopReason, BoxError, PanicError};
+    /// use kameo::message::{Context, Message};
+    /// use kameo::reply::StrandedReply;
+    /// use kameo::Actor;
+    ///
+    /// struct Prices;
+    ///
+    /// impl Actor for Prices {
+    ///     type Mailbox = kameo::mailbox::unbounded::UnboundedMailbox<Self>;
+    ///
+    ///     async fn on_panic(
+    ///         &mut self,
+    ///         _: WeakActorRef<Self>,
+    ///         _: PanicError,
+    ///     ) -> Result<Option<ActorStopReason>, BoxError> {
+    ///         Ok(None) // Keep running
+    ///     }
+    ///
+    ///     async fn on_panic_reply(
+    ///         &mut self,
+    ///         _: WeakActorRef<Self>,
+    ///         _: PanicError,
+    ///         reply: StrandedReply<Self>,
+    ///     ) -> Result<(), BoxError> {
+    ///         // Reply with a stale price rather than an error
+    ///         let _ = reply.send::<GetPrice>(Some(100));
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// struct GetPrice;
+    ///
+    /// impl Message<GetPrice> for Prices {
+    ///     type Reply = Option<u64>;
+    ///
+    ///     async fn handle(&mut self, _: GetPrice, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         panic!("price feed unavailable")
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let prices = kameo::spawn(Prices);
+    /// assert_eq!(prices.ask(GetPrice).await?, Some(100));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[allow(unused_variables)]
+    fn on_panic_reply(
+        &mut self,
+        actor_ref: WeakActorRef<Self>,
+        err: PanicError,
+        reply: StrandedReply<Self>,
+    ) -> impl Future<Output = Result<(), BoxError>> + Send {
+        async { Ok(()) }
+    }
+
+    /// Called when a message handler returns an error which has no caller waiting for a reply,
+    /// such as a message sent with [`tell`](ActorRef::tell). This includes messages rejected by
+    /// [`Message::validate`](crate::message::Message::validate).
+    ///
+    /// By default, the error is treated as a panic and routed to [`on_panic`](Actor::on_panic).
+    /// This can be overridden to log and ignore errors for fire-and-forget messages which can legitimately fail.
+    ///
+    /// # Parameters
+    /// - `err`: The error returned by the message handler.
+    ///
+    /// # Returns
+    /// - `Some(ActorStopReason)`: Stops the actor, calling `on_panic` first if the reason is [`ActorStopReason::Panicked`].
+    /// - `None`: Allows the actor to continue processing messages.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::{Actor, WeakActorRef};
+    /// use kameo::error::{ActorStopReason, BoxError, PanicError};
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    ///
+    /// struct MyActor;
+    ///
+    /// impl Actor for MyActor {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     async fn on_async_error(
+    ///         &mut self,
+    ///         _actor_ref: WeakActorRef<Self>,
+    ///         err: PanicError,
+    ///     ) -> Result<Option<ActorStopReason>, BoxError> {
+    ///         println!("message failed: {err}");
+    ///         Ok(None) // Keep running
+    ///     }
+    /// }
+    /// ```
+    #[allow(unused_variables)]
+    fn on_async_error(
+        &mut self,
+        actor_ref: WeakActorRef<Self>,
+        err: PanicError,
+    ) -> impl Future<Output = Result<Option<ActorStopReason>, BoxError>> + Send {
+        async move { Ok(Some(ActorStopReason::Panicked(err))) }
+    }
+
     /// Called when a linked actor dies.
     ///
     /// By default, the actor will stop if the reason for the linked actor's death is anything other
     /// than `Normal`. You can customize this behavior in the implementation.
     ///
+    /// The dead actor can be restarted from within this hook by upgrading `actor_ref` and calling
+    /// [`ActorRef::spawn_link`].
+    ///
     /// # Returns
     /// Whether the actor should stop or continue processing messages.
     #[allow(unused_variables)]
@@ -213,3 +627,69 @@ pub trait Actor: Sized + Send + 'static {
         async { Ok(()) }
     }
 }
+
+/// An actor which is constructed asynchronously by the runtime, from a set of arguments.
+///
+/// Resources such as database pools or connections often can only be created asynchronously.
+/// Rather than storing them as an `Option` which is filled in by [`Actor::on_start`] and unwrapped in every handler,
+/// the actor can be built by [`init`](InitActor::init) once the resources are ready, and spawned with [`spawn_init`].
+/// To wait until the actor has been constructed and handle a failed `init`, spawn it with [`spawn_with_init`] instead.
+///
+/// The actor's [`ActorRef`] is available immediately, and any messages sent before `init` completes are queued in the
+/// mailbox, so handlers only ever see a fully initialized actor. Once `init` succeeds, the actor runs as usual,
+/// starting with [`Actor::on_start`].
+///
+/// If `init` returns an error or panics, the actor never starts. Any queued messages are rejected,
+/// and linked actors are not notified, just as if [`Actor::on_start`] had failed.
+///
+/// # Restarting
+///
+/// `init` is called exactly once for each spawned actor. When [`Actor::on_panic`] returns `Ok(None)`,
+/// the actor continues with its existing state, and `init` is not called again.
+/// To restart an actor with fresh state, spawn a new actor with [`spawn_init`] and new arguments,
+/// for example from [`Actor::on_link_died`] in a supervising actor.
+///
+/// # Example
+///
+/// ```
+/// use kameo::actor::{spawn_init, ActorRef, InitActor};
+/// use kameo::error::BoxError;
+/// use kameo::mailbox::unbounded::UnboundedMailbox;
+/// use kameo::Actor;
+///
+/// # struct Pool;
+/// # async fn connect(url: &str) -> Pool { Pool }
+/// struct Database {
+///     pool: Pool,
+/// }
+///
+/// impl Actor for Database {
+///     type Mailbox = UnboundedMailbox<Self>;
+/// }
+///
+/// impl InitActor for Database {
+///     type Args = String;
+///
+///     async fn init(url: String, _actor_ref: ActorRef<Self>) -> Result<Self, BoxError> {
+///         Ok(Database {
+///             pool: connect(&url).await,
+///         })
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = spawn_init::<Database>("postgres://localhost".to_string());
+/// # })
+/// ```
+pub trait InitActor: Actor {
+    /// The arguments used to construct the actor.
+    type Args: Send + 'static;
+
+    /// Constructs the actor from its arguments.
+    ///
+    /// This is called by the runtime in the actor's task, before [`Actor::on_start`].
+    fn init(
+        args: Self::Args,
+        actor_ref: ActorRef<Self>,
+    ) -> impl Future<Output = Result<Self, BoxError>> + Send;
+}
@@ -1,8 +1,21 @@
-use std::{cell::Cell, collections::HashMap, fmt, ops, sync::Arc};
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    cmp,
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    iter, ops,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock, PoisonError,
+    },
+    time::Instant,
+};
 
-use futures::{stream::AbortHandle, Stream, StreamExt};
+use futures::{future::BoxFuture, stream::AbortHandle, FutureExt, Stream, StreamExt};
 use tokio::{
-    sync::{Mutex, Semaphore},
+    sync::{oneshot, Mutex, Semaphore},
     task::JoinHandle,
     task_local,
 };
@@ -17,8 +30,8 @@ use crate::remote;
 
 use crate::{
     error::{self, SendError},
-    mailbox::{bounded::BoundedMailbox, Mailbox, SignalMailbox, WeakMailbox},
-    message::{Message, StreamMessage},
+    mailbox::{bounded::BoundedMailbox, Mailbox, Signal, SignalMailbox, WeakMailbox},
+    message::{Extensions, Message, MessageMeta, StreamMessage},
     reply::Reply,
     request::{
         self, AskRequest, LocalAskRequest, LocalTellRequest, MessageSend, TellRequest,
@@ -27,7 +40,14 @@ use crate::{
     Actor,
 };
 
-use super::id::ActorID;
+use super::{
+    bulkhead::Bulkhead,
+    id::ActorID,
+    migrate::{Migratable, MigrationRequest, MigrationSlot},
+    stats::StatsCounters,
+    watch::StateWatch,
+    ActorStats, MappedRef, PreparedActor, Recipient, Spawner, Watchable, WeakRecipient,
+};
 
 task_local! {
     pub(crate) static CURRENT_ACTOR_ID: ActorID;
@@ -36,17 +56,81 @@ thread_local! {
     pub(crate) static CURRENT_THREAD_ACTOR_ID: Cell<Option<ActorID>> = const { Cell::new(None) };
 }
 
+/// State shared between every reference to an actor.
+pub(crate) struct ActorInfo {
+    pub(crate) name: Cow<'static, str>,
+    pub(crate) stats: StatsCounters,
+    pub(crate) migration: MigrationSlot,
+    /// Set once the actor has begun notifying its links that it stopped, after which no links can be added.
+    pub(crate) links_closed: AtomicBool,
+    /// Aborts the actor's message loop, killing it.
+    pub(crate) abort_handle: AbortHandle,
+    /// Set once the actor has been closed with [`ActorRef::close`], after which it rejects new messages.
+    pub(crate) closed: AtomicBool,
+    /// Set once the actor has been asked to stop with [`ActorRef::stop_gracefully`] or [`ActorRef::close`].
+    pub(crate) stop_requested: AtomicBool,
+    pub(crate) state_watch: StateWatch,
+    /// The bulkhead the actor was spawned in, limiting how many of its actors handle messages at the same time.
+    pub(crate) bulkhead: OnceLock<Bulkhead>,
+    /// The spawner the actor runs on, if it was spawned or migrated with a custom [`Spawner`].
+    pub(crate) spawner: std::sync::Mutex<Option<Arc<dyn Spawner + Send + Sync>>>,
+}
+
+impl ActorInfo {
+    /// Spawns a task for the actor on the spawner it runs on, falling back to [`tokio::spawn`].
+    pub(crate) fn spawn(&self, future: BoxFuture<'static, ()>) {
+        let spawner = self
+            .spawner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        match spawner {
+            Some(spawner) => spawner.spawn(future),
+            None => {
+                tokio::spawn(future);
+            }
+        }
+    }
+
+    /// Sets the spawner used for the actor's tasks.
+    pub(crate) fn set_spawner<S>(&self, spawner: &S)
+    where
+        S: Spawner + Clone + Send + Sync + 'static,
+    {
+        *self.spawner.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some(Arc::new(spawner.clone()));
+    }
+}
+
 /// A reference to an actor, used for sending messages and managing its lifecycle.
 ///
 /// An `ActorRef` allows interaction with an actor through message passing, both for asking (waiting for a reply)
 /// and telling (without waiting for a reply). It also provides utilities for managing the actor's state,
 /// such as checking if the actor is alive, registering the actor under a name, and stopping the actor gracefully.
+///
+/// Actor refs are compared, hashed and ordered by their [`ActorID`], so they can be used as keys in both a
+/// `HashMap` and a `BTreeMap`. For local actors, the ordering reflects the order they were spawned in.
+///
+/// ```
+/// use std::collections::BTreeSet;
+///
+/// # #[derive(kameo::Actor)]
+/// # struct MyActor;
+/// #
+/// # tokio_test::block_on(async {
+/// let first = kameo::spawn(MyActor);
+/// let second = kameo::spawn(MyActor);
+///
+/// let actors = BTreeSet::from([second.clone(), first.clone(), second.clone()]);
+/// assert_eq!(actors.into_iter().collect::<Vec<_>>(), [first, second]);
+/// # })
+/// ```
 pub struct ActorRef<A: Actor> {
     id: ActorID,
     mailbox: A::Mailbox,
-    abort_handle: AbortHandle,
     pub(crate) links: Links,
     pub(crate) startup_semaphore: Arc<Semaphore>,
+    pub(crate) info: Arc<ActorInfo>,
 }
 
 impl<A> ActorRef<A>
@@ -59,13 +143,25 @@ where
         abort_handle: AbortHandle,
         links: Links,
         startup_semaphore: Arc<Semaphore>,
+        name: Cow<'static, str>,
     ) -> Self {
         ActorRef {
             id: ActorID::generate(),
             mailbox,
-            abort_handle,
             links,
             startup_semaphore,
+            info: Arc::new(ActorInfo {
+                name,
+                stats: StatsCounters::new(),
+                migration: MigrationSlot::default(),
+                links_closed: AtomicBool::new(false),
+                abort_handle,
+                closed: AtomicBool::new(false),
+                stop_requested: AtomicBool::new(false),
+                state_watch: StateWatch::default(),
+                bulkhead: OnceLock::new(),
+                spawner: std::sync::Mutex::new(None),
+            }),
         }
     }
 
@@ -75,6 +171,15 @@ where
         self.id
     }
 
+    /// Returns the name of the actor.
+    ///
+    /// This is the name the actor was spawned with using [`spawn_named`](crate::actor::spawn_named),
+    /// or [`Actor::name`] otherwise.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.info.name
+    }
+
     /// Returns whether the actor is currently alive.
     #[inline]
     pub fn is_alive(&self) -> bool {
@@ -85,10 +190,7 @@ where
     ///
     /// This makes the actor discoverable by parts of the app by name.
     #[cfg(not(feature = "remote"))]
-    pub fn register(
-        &self,
-        name: impl Into<std::borrow::Cow<'static, str>>,
-    ) -> Result<(), error::RegistryError> {
+    pub fn register(&self, name: impl Into<Cow<'static, str>>) -> Result<(), error::RegistryError> {
         crate::registry::ACTOR_REGISTRY
             .lock()
             .unwrap()
@@ -146,12 +248,146 @@ where
         WeakActorRef {
             id: self.id,
             mailbox: self.mailbox.downgrade(),
-            abort_handle: self.abort_handle.clone(),
             links: self.links.clone(),
             startup_notify: self.startup_semaphore.clone(),
+            info: self.info.clone(),
+        }
+    }
+
+    /// Creates a [`Sender`] which can only be used to send messages to the actor.
+    ///
+    /// Senders are cheaper to clone than an `ActorRef`, and don't expose linking or lifecycle management,
+    /// making them well suited to being passed to many short lived tasks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # struct Msg;
+    /// #
+    /// # impl kameo::message::Message<Msg> for MyActor {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, msg: Msg, ctx: kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply { }
+    /// # }
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// let sender = actor_ref.sender();
+    ///
+    /// for _ in 0..10 {
+    ///     let sender = sender.clone();
+    ///     tokio::spawn(async move {
+    ///         sender.tell(Msg).await.unwrap();
+    ///     });
+    /// }
+    /// # })
+    /// ```
+    #[inline]
+    pub fn sender(&self) -> Sender<A> {
+        Sender {
+            actor_ref: Arc::new(self.clone()),
         }
     }
 
+    /// Creates a type erased [`Recipient`] which can be used to send messages of type `M` to the actor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::Recipient;
+    /// # use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # #[derive(kameo::Actor)]
+    /// # struct OtherActor;
+    /// #
+    /// # struct Msg;
+    /// #
+    /// # impl Message<Msg> for MyActor {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, msg: Msg, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+    /// # }
+    /// #
+    /// # impl Message<Msg> for OtherActor {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, msg: Msg, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+    /// # }
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let recipients: Vec<Recipient<Msg>> = vec![
+    ///     kameo::spawn(MyActor).recipient(),
+    ///     kameo::spawn(OtherActor).recipient(),
+    /// ];
+    /// for recipient in &recipients {
+    ///     recipient.tell(Msg).await?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[inline]
+    pub fn recipient<M>(&self) -> Recipient<M>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+            MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+    {
+        Recipient::new(self.clone())
+    }
+
+    /// Creates a type erased [`WeakRecipient`], which does not keep the actor alive.
+    ///
+    /// This is equivalent to `actor_ref.recipient().downgrade()`, without creating a strong reference.
+    #[must_use = "Downgrade creates a WeakRecipient without destroying the original non-weak actor ref."]
+    #[inline]
+    pub fn downgrade_to_recipient<M>(&self) -> WeakRecipient<M>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+            MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+    {
+        WeakRecipient::new(self.downgrade())
+    }
+
+    /// Creates a [`MappedRef`], which applies `f` to the reply of every ask request sent through it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kameo::message::{Context, Message};
+    /// #
+    /// # #[derive(kameo::Actor)]
+    /// # struct Storage;
+    /// #
+    /// struct Get(&'static str);
+    /// #
+    /// # impl Message<Get> for Storage {
+    /// #     type Reply = Vec<u8>;
+    /// #     async fn handle(&mut self, msg: Get, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { b"hello".to_vec() }
+    /// # }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let storage = kameo::spawn(Storage)
+    ///     .map_reply(|bytes: Vec<u8>| String::from_utf8_lossy(&bytes).into_owned());
+    ///
+    /// let value = storage.ask(Get("greeting")).await?;
+    /// assert_eq!(value, "hello");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[inline]
+    pub fn map_reply<T, U>(
+        &self,
+        f: impl Fn(T) -> U + Send + Sync + 'static,
+    ) -> MappedRef<A, T, U> {
+        MappedRef::new(self.clone(), f)
+    }
+
     /// Returns the number of [`ActorRef`] handles.
     #[inline]
     pub fn strong_count(&self) -> usize {
@@ -164,6 +400,25 @@ where
         self.mailbox.weak_count()
     }
 
+    /// Returns a snapshot of the actor's runtime statistics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// let stats = actor_ref.stats();
+    /// println!("handled {} messages in {:?}", stats.messages_handled, stats.uptime);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn stats(&self) -> ActorStats {
+        self.info.stats.snapshot()
+    }
+
     /// Returns `true` if the current task is the actor itself.
     ///
     /// This is useful when checking if certain code is being executed from within the actor's own context.
@@ -181,9 +436,59 @@ where
     /// before it shuts down. Any new messages sent after the stop signal will be ignored.
     #[inline]
     pub async fn stop_gracefully(&self) -> Result<(), error::SendError> {
+        self.info.stop_requested.store(true, Ordering::Release);
         self.mailbox.signal_stop().await
     }
 
+    /// Closes the actor, rejecting new messages while it finishes processing the messages already in its mailbox.
+    ///
+    /// Unlike [`stop_gracefully`](ActorRef::stop_gracefully), which keeps accepting messages until the actor reaches
+    /// the stop signal, any message sent after `close` is called fails immediately with [`SendError::Closed`],
+    /// returning the message to the sender. The actor then stops normally once its mailbox has been drained. This
+    /// prevents new work from entering the actor while it drains during shutdown.
+    ///
+    /// Messages which were being sent concurrently with `close` may either be handled before the actor stops, or be
+    /// rejected with [`SendError::ActorNotRunning`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::error::SendError;
+    /// # use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # struct Msg;
+    /// #
+    /// # impl Message<Msg> for MyActor {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, msg: Msg, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+    /// # }
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// actor_ref.tell(Msg).await?; // Still handled
+    ///
+    /// actor_ref.close().await?;
+    /// assert!(matches!(actor_ref.tell(Msg).await, Err(SendError::Closed(Msg))));
+    ///
+    /// actor_ref.wait_for_stop().await;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn close(&self) -> Result<(), error::SendError> {
+        self.info.closed.store(true, Ordering::Release);
+        self.info.stop_requested.store(true, Ordering::Release);
+        self.mailbox.signal_stop().await
+    }
+
+    /// Returns `true` if the actor has been closed with [`close`](ActorRef::close).
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.info.closed.load(Ordering::Acquire)
+    }
+
     /// Kills the actor immediately.
     ///
     /// This method aborts the actor immediately. Messages in the mailbox will be ignored and dropped.
@@ -193,7 +498,150 @@ where
     /// Note: If the actor is in the middle of processing a message, it will abort processing of that message.
     #[inline]
     pub fn kill(&self) {
-        self.abort_handle.abort()
+        self.info.abort_handle.abort()
+    }
+
+    /// Moves the actor to run on another executor, without stopping it.
+    ///
+    /// The actor is moved once it has finished handling its current message, and keeps its [`ActorID`],
+    /// state, links and mailbox, so existing references continue to work. Messages sent while the actor is
+    /// being moved wait in its mailbox, so none are lost. Since the actor is moved within the same process,
+    /// its state does not need to be serialized.
+    ///
+    /// The original task or thread, along with its `JoinHandle`, keeps waiting until the actor stops on its
+    /// new executor.
+    ///
+    /// Actors running with [`PreparedActor::run`](crate::actor::PreparedActor::run) can't be migrated, since
+    /// they're driven by the caller, and return [`MigrateError::NotMigratable`](error::MigrateError::NotMigratable).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kameo::Actor;
+    /// #
+    /// # #[derive(Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    ///
+    /// let runtime = tokio::runtime::Runtime::new()?;
+    /// actor_ref.migrate(runtime.handle()).await?;
+    /// assert!(actor_ref.is_alive());
+    /// # actor_ref.kill();
+    /// # actor_ref.wait_for_stop().await;
+    /// # runtime.shutdown_background();
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn migrate<S>(&self, spawner: &S) -> Result<(), error::MigrateError>
+    where
+        S: Spawner + Clone + Send + Sync + 'static,
+    {
+        if !self.info.migration.is_supported() {
+            return Err(if self.is_alive() {
+                error::MigrateError::NotMigratable
+            } else {
+                error::MigrateError::ActorNotRunning
+            });
+        }
+
+        let _guard = self.info.migration.lock.lock().await;
+        let mut request = MigrationRequest::new(self.info.clone());
+        let task = tokio::select! {
+            biased;
+            task = &mut request => task,
+            _ = self.wait_for_stop() => None,
+        };
+        let task = task.ok_or(error::MigrateError::ActorNotRunning)?;
+        self.info.set_spawner(spawner);
+        spawner.spawn(Migratable::new(task, self.info.clone()).boxed());
+
+        Ok(())
+    }
+
+    /// Spawns a new actor with a copy of this actor's current state.
+    ///
+    /// The state is cloned by the actor itself between messages, so the copy never captures a message which
+    /// was only partly handled. Messages sent before `fork` are handled before the state is cloned, and messages
+    /// sent after are only handled by the original actor.
+    ///
+    /// The new actor runs independently with its own [`ActorID`] and an empty mailbox, and isn't linked to the
+    /// original actor. It has the same name, and runs on the same [`Spawner`] and in the same
+    /// [`Bulkhead`](crate::actor::bulkhead::Bulkhead) as the original actor. Its [`on_start`](Actor::on_start) hook
+    /// runs as it would for any newly spawned actor.
+    ///
+    /// Only actors implementing [`Clone`] can be forked. Calling `fork` from within the actor's own handler
+    /// will deadlock.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kameo::message::{Context, Message};
+    /// #
+    /// #[derive(kameo::Actor, Clone)]
+    /// struct Counter {
+    ///     count: i64,
+    /// }
+    ///
+    /// struct Inc(i64);
+    /// #
+    /// # impl Message<Inc> for Counter {
+    /// #     type Reply = i64;
+    /// #     async fn handle(&mut self, Inc(n): Inc, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    /// #         self.count += n;
+    /// #         self.count
+    /// #     }
+    /// # }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(Counter { count: 0 });
+    /// actor_ref.tell(Inc(5)).await?;
+    ///
+    /// let forked_ref = actor_ref.fork().await?;
+    /// assert_eq!(forked_ref.ask(Inc(1)).await?, 6);
+    /// assert_eq!(actor_ref.ask(Inc(10)).await?, 15);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn fork(&self) -> Result<ActorRef<A>, error::SendError>
+    where
+        A: Clone,
+    {
+        if self.is_closed() {
+            return Err(SendError::Closed(()));
+        }
+        let (tx, rx) = oneshot::channel();
+        self.mailbox
+            .send(Signal::Fork(Box::new(move |actor: &A| {
+                let _ = tx.send(actor.clone());
+            })))
+            .await
+            .map_err(|_| SendError::ActorNotRunning(()))?;
+        let state = rx.await.map_err(|_| SendError::ActorStopped)?;
+
+        let prepared_actor = PreparedActor::new_with_name(self.info.name.clone());
+        let actor_ref = prepared_actor.actor_ref().clone();
+        if let Some(bulkhead) = self.info.bulkhead.get() {
+            let _ = actor_ref.info.bulkhead.set(bulkhead.clone());
+        }
+        let spawner = self
+            .info
+            .spawner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        match spawner {
+            Some(spawner) => {
+                let spawner = move |future: BoxFuture<'static, ()>| spawner.spawn(future);
+                prepared_actor.spawn_with(&spawner, state);
+            }
+            None => {
+                prepared_actor.spawn(state);
+            }
+        }
+
+        Ok(actor_ref)
     }
 
     /// Waits for the actor to finish startup and become ready to process messages.
@@ -234,6 +682,26 @@ where
         let _ = self.startup_semaphore.acquire().await;
     }
 
+    /// Subscribes to changes in the actor's state.
+    ///
+    /// The stream yields a snapshot of the state each time it changes after a message is handled, and ends once
+    /// the actor stops. Only the latest state is kept for each subscriber, so rapid changes are coalesced rather
+    /// than buffered. The stream doesn't yield the current state when subscribing, only later changes.
+    ///
+    /// See [`Watchable`] for more information.
+    pub fn subscribe(&self) -> impl Stream<Item = A::State> + Send + Unpin + 'static
+    where
+        A: Watchable,
+    {
+        let rx = self.info.state_watch.subscribe::<A>();
+        futures::stream::unfold(rx, |mut rx| async move {
+            rx.changed().await.ok()?;
+            let state = rx.borrow_and_update().clone()?;
+            Some((state, rx))
+        })
+        .boxed()
+    }
+
     /// Waits for the actor to finish processing and stop.
     ///
     /// This method suspends execution until the actor has stopped, ensuring that any ongoing
@@ -249,6 +717,52 @@ where
         self.mailbox.closed().await
     }
 
+    /// Waits until every message sent to the actor before this call has been handled.
+    ///
+    /// This enqueues a barrier in the actor's mailbox, and resolves once the actor reaches it. Since an actor handles
+    /// one message at a time, every message enqueued before the barrier has finished being handled by then, which is
+    /// useful as a drain point after a series of [`tell`](ActorRef::tell) requests. Messages sent concurrently from
+    /// other tasks may or may not be enqueued before the barrier.
+    ///
+    /// With a [`LifoMailbox`](crate::mailbox::lifo::LifoMailbox), the barrier also waits for any newer messages
+    /// received before the older ones have been handled.
+    ///
+    /// Like an [`ask`](ActorRef::ask) request, this waits for mailbox capacity, and calling it from within the actor's
+    /// own handler will deadlock.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kameo::Actor;
+    /// # use kameo::message::{Context, Message};
+    /// #
+    /// # #[derive(Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # struct Msg;
+    /// #
+    /// # impl Message<Msg> for MyActor {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, msg: Msg, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+    /// # }
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// actor_ref.tell(Msg).await?;
+    /// actor_ref.tell(Msg).await?;
+    /// actor_ref.flush().await?; // Both messages have been handled
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn flush(&self) -> Result<(), error::SendError> {
+        let (tx, rx) = oneshot::channel();
+        self.mailbox
+            .send(Signal::Flush(tx))
+            .await
+            .map_err(|_| SendError::ActorNotRunning(()))?;
+        rx.await.map_err(|_| SendError::ActorNotRunning(()))
+    }
+
     /// Sends a message to the actor and waits for a reply.
     ///
     /// The `ask` pattern is used when you expect a response from the actor. This method returns
@@ -345,6 +859,142 @@ where
         )
     }
 
+    /// Sends multiple messages to the actor as `tell` requests, in the order they're given.
+    ///
+    /// Each message is enqueued before the next one is sent, waiting for capacity if the mailbox is bounded, so the
+    /// actor receives the messages in the same order even while other tasks are sending to it. Messages from other
+    /// senders may be interleaved between them, but never reorder them. This holds for every mailbox which handles
+    /// messages in the order they were sent, but not for the [priority](crate::mailbox::priority) and
+    /// [LIFO](crate::mailbox::lifo) mailboxes, which reorder messages by design.
+    ///
+    /// If a message can't be sent, the remaining messages are returned in the error in their original order,
+    /// starting with the message which failed. Messages sent before it are still handled.
+    ///
+    /// To verify the order from within the actor, use a [`SequencedSender`](super::SequencedSender).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # impl kameo::message::Message<u32> for MyActor {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, msg: u32, ctx: kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply { }
+    /// # }
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// actor_ref.send_all_ordered(1..=5).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn send_all_ordered<M>(
+        &self,
+        msgs: impl IntoIterator<Item = M>,
+    ) -> Result<(), SendError<Vec<M>>>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+    {
+        let sent_within_actor = self.is_current();
+        let mut msgs = msgs.into_iter();
+        while let Some(msg) = msgs.next() {
+            if self.is_closed() {
+                return Err(SendError::Closed(iter::once(msg).chain(msgs).collect()));
+            }
+            if crate::message::is_too_large::<A, M>(&msg) {
+                return Err(SendError::TooLarge(iter::once(msg).chain(msgs).collect()));
+            }
+            let res = self
+                .mailbox
+                .send(Signal::Message {
+                    message: Box::new(msg),
+                    actor_ref: self.clone(),
+                    reply: None,
+                    sent_within_actor,
+                    extensions: Extensions::new(),
+                    meta: MessageMeta::for_send(),
+                    sent_at: Instant::now(),
+                })
+                .await;
+            if let Err(err) = res {
+                let msg = err.0.downcast_message::<M>().unwrap();
+                return Err(SendError::ActorNotRunning(
+                    iter::once(msg).chain(msgs).collect(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Spawns an actor linked to this actor, returning the new actor's ref.
+    ///
+    /// This is equivalent to [`spawn_link`](crate::actor::spawn_link), and is useful for restarting
+    /// a child actor from within [`Actor::on_link_died`] after upgrading the [`WeakActorRef`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::{ActorID, ActorRef, WeakActorRef};
+    /// use kameo::error::{ActorStopReason, BoxError};
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    /// use kameo::Actor;
+    ///
+    /// #[derive(Actor)]
+    /// struct Child;
+    ///
+    /// struct Parent {
+    ///     child: Option<ActorRef<Child>>,
+    /// }
+    ///
+    /// impl Actor for Parent {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     async fn on_start(&mut self, actor_ref: ActorRef<Self>) -> Result<(), BoxError> {
+    ///         self.child = Some(actor_ref.spawn_link(Child).await);
+    ///         Ok(())
+    ///     }
+    ///
+    ///     async fn on_link_died(
+    ///         &mut self,
+    ///         actor_ref: WeakActorRef<Self>,
+    ///         id: ActorID,
+    ///         _reason: ActorStopReason,
+    ///     ) -> Result<Option<ActorStopReason>, BoxError> {
+    ///         if self.child.as_ref().map(|child| child.id()) == Some(id) {
+    ///             if let Some(actor_ref) = actor_ref.upgrade() {
+    ///                 // Restart the child instead of stopping
+    ///                 self.child = Some(actor_ref.spawn_link(Child).await);
+    ///             }
+    ///         }
+    ///
+    ///         Ok(None)
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub async fn spawn_link<B>(&self, actor: B) -> ActorRef<B>
+    where
+        B: Actor,
+    {
+        crate::actor::spawn_link(self, actor).await
+    }
+
+    /// Spawns and links an actor to this actor, unless this actor has already stopped.
+    ///
+    /// If this actor is stopping, the new actor is returned in [`SendError::ActorNotRunning`] without being spawned.
+    ///
+    /// See [`try_spawn_link`](crate::actor::try_spawn_link) for the ordering guarantees.
+    #[inline]
+    pub async fn try_spawn_link<B>(&self, actor: B) -> Result<ActorRef<B>, SendError<B>>
+    where
+        B: Actor,
+    {
+        crate::actor::try_spawn_link(self, actor).await
+    }
+
     /// Links two actors as siblings, ensuring they notify each other if either one dies.
     ///
     /// # Example
@@ -376,6 +1026,23 @@ where
         sibbling_links.insert(self.id, Link::Local(self.weak_signal_mailbox()));
     }
 
+    /// Links two actors as siblings, unless `sibbling_ref` has begun notifying its links that it stopped.
+    ///
+    /// Returns `false` without linking if the sibling is stopping.
+    pub(crate) async fn link_if_running<B: Actor>(&self, sibbling_ref: &ActorRef<B>) -> bool {
+        let (mut this_links, mut sibbling_links) =
+            tokio::join!(self.links.lock(), sibbling_ref.links.lock());
+        if sibbling_ref.info.links_closed.load(Ordering::Acquire) {
+            return false;
+        }
+        this_links.insert(
+            sibbling_ref.id,
+            Link::Local(sibbling_ref.weak_signal_mailbox()),
+        );
+        sibbling_links.insert(self.id, Link::Local(self.weak_signal_mailbox()));
+        true
+    }
+
     /// Blockingly links two actors as siblings, ensuring they notify each other if either one dies.
     ///
     /// This method is intended for use cases where you need to link actors in synchronous code.
@@ -709,6 +1376,87 @@ where
     pub fn max_capacity(&self) -> usize {
         self.mailbox.0.max_capacity()
     }
+
+    /// Attempts to send a batch of messages to the actor without waiting for mailbox capacity.
+    ///
+    /// Messages are sent in order as `tell` requests until the mailbox is full. Sending is partial rather than
+    /// all-or-nothing: messages which fit are enqueued, and the remaining messages are returned in the error
+    /// in their original order.
+    ///
+    /// - [`SendError::MailboxFull`] is returned with the messages which did not fit in the mailbox.
+    /// - [`SendError::ActorNotRunning`] is returned with the remaining messages if the actor stopped.
+    /// - [`SendError::TooLarge`] is returned with the remaining messages, starting with the message which exceeded
+    ///   [`Actor::max_message_bytes`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::error::SendError;
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # #[actor(mailbox = bounded(2))]
+    /// # struct MyActor;
+    /// #
+    /// # impl kameo::message::Message<u32> for MyActor {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, msg: u32, ctx: kameo::message::Context<'_, Self, Self::Reply>) -> Self::Reply {
+    /// #         std::future::pending::<()>().await;
+    /// #     }
+    /// # }
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// match actor_ref.try_send_many(vec![1, 2, 3, 4, 5]) {
+    ///     Ok(()) => println!("all messages sent"),
+    ///     Err(SendError::MailboxFull(remaining)) => println!("{} messages did not fit", remaining.len()),
+    ///     Err(err) => println!("failed to send messages: {err}"),
+    /// }
+    /// # });
+    /// ```
+    pub fn try_send_many<M>(
+        &self,
+        msgs: impl IntoIterator<Item = M>,
+    ) -> Result<(), SendError<Vec<M>>>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+    {
+        if self.is_closed() {
+            return Err(SendError::Closed(msgs.into_iter().collect()));
+        }
+        let sent_within_actor = self.is_current();
+        let mut msgs = msgs.into_iter();
+        while let Some(msg) = msgs.next() {
+            if crate::message::is_too_large::<A, M>(&msg) {
+                return Err(SendError::TooLarge(iter::once(msg).chain(msgs).collect()));
+            }
+            let res = self.mailbox.0.try_send(Signal::Message {
+                message: Box::new(msg),
+                actor_ref: self.clone(),
+                reply: None,
+                sent_within_actor,
+                extensions: Extensions::new(),
+                meta: MessageMeta::for_send(),
+                sent_at: Instant::now(),
+            });
+            match res.map_err(SendError::<M>::from) {
+                Ok(()) => {}
+                Err(SendError::MailboxFull(msg)) => {
+                    return Err(SendError::MailboxFull(
+                        iter::once(msg).chain(msgs).collect(),
+                    ));
+                }
+                Err(SendError::ActorNotRunning(msg)) => {
+                    return Err(SendError::ActorNotRunning(
+                        iter::once(msg).chain(msgs).collect(),
+                    ));
+                }
+                Err(_) => unreachable!("try_send can only fail if the mailbox is full or closed"),
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl<A: Actor> Clone for ActorRef<A> {
@@ -716,9 +1464,9 @@ impl<A: Actor> Clone for ActorRef<A> {
         ActorRef {
             id: self.id,
             mailbox: self.mailbox.clone(),
-            abort_handle: self.abort_handle.clone(),
             links: self.links.clone(),
             startup_semaphore: self.startup_semaphore.clone(),
+            info: self.info.clone(),
         }
     }
 }
@@ -727,6 +1475,7 @@ impl<A: Actor> fmt::Debug for ActorRef<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut d = f.debug_struct("ActorRef");
         d.field("id", &self.id);
+        d.field("name", &self.name());
         match self.links.try_lock() {
             Ok(guard) => {
                 d.field("links", &guard.keys());
@@ -739,6 +1488,32 @@ impl<A: Actor> fmt::Debug for ActorRef<A> {
     }
 }
 
+impl<A: Actor> PartialEq for ActorRef<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<A: Actor> Eq for ActorRef<A> {}
+
+impl<A: Actor> PartialOrd for ActorRef<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: Actor> Ord for ActorRef<A> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl<A: Actor> Hash for ActorRef<A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 impl<A: Actor> AsRef<Links> for ActorRef<A> {
     fn as_ref(&self) -> &Links {
         &self.links
@@ -1002,6 +1777,48 @@ impl<A: Actor> fmt::Debug for RemoteActorRef<A> {
     }
 }
 
+/// Serializes the remote actor ref as its [`ActorID`] and [`RemoteActor::REMOTE_ID`](remote::RemoteActor::REMOTE_ID),
+/// allowing it to be embedded in messages sent to other nodes.
+#[cfg(feature = "remote")]
+impl<A> serde::Serialize for RemoteActorRef<A>
+where
+    A: Actor + remote::RemoteActor,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (self.id, A::REMOTE_ID).serialize(serializer)
+    }
+}
+
+/// Deserializes a remote actor ref, resolving it with the local [`ActorSwarm`](remote::ActorSwarm).
+///
+/// Deserialization fails if the actor swarm has not been bootstrapped, or if the serialized
+/// remote ID does not match [`RemoteActor::REMOTE_ID`](remote::RemoteActor::REMOTE_ID).
+#[cfg(feature = "remote")]
+impl<'de, A> serde::Deserialize<'de> for RemoteActorRef<A>
+where
+    A: Actor + remote::RemoteActor,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let (id, remote_id): (ActorID, std::borrow::Cow<'de, str>) =
+            serde::Deserialize::deserialize(deserializer)?;
+        if remote_id != A::REMOTE_ID {
+            return Err(D::Error::custom(error::RegistryError::BadActorType));
+        }
+        let swarm = remote::ActorSwarm::get()
+            .ok_or_else(|| D::Error::custom(error::RegistryError::SwarmNotBootstrapped))?;
+
+        Ok(RemoteActorRef::new(id, swarm.sender().clone()))
+    }
+}
+
 /// A actor ref that does not prevent the actor from being stopped.
 ///
 /// If all [`ActorRef`] instances of an actor were dropped and only
@@ -1010,12 +1827,14 @@ impl<A: Actor> fmt::Debug for RemoteActorRef<A> {
 /// In order to send messages to an actor, the `WeakActorRef` needs to be upgraded using
 /// [`WeakActorRef::upgrade`], which returns `Option<ActorRef>`. It returns `None`
 /// if all `ActorRef`s have been dropped, and otherwise it returns an `ActorRef`.
+///
+/// Like [`ActorRef`], weak actor refs are compared, hashed and ordered by their [`ActorID`].
 pub struct WeakActorRef<A: Actor> {
     id: ActorID,
     mailbox: <A::Mailbox as Mailbox<A>>::WeakMailbox,
-    abort_handle: AbortHandle,
     pub(crate) links: Links,
     startup_notify: Arc<Semaphore>,
+    pub(crate) info: Arc<ActorInfo>,
 }
 
 impl<A: Actor> WeakActorRef<A> {
@@ -1024,15 +1843,22 @@ impl<A: Actor> WeakActorRef<A> {
         self.id
     }
 
+    /// Returns the name of the actor.
+    ///
+    /// See [`ActorRef::name`].
+    pub fn name(&self) -> &str {
+        &self.info.name
+    }
+
     /// Tries to convert a `WeakActorRef` into a [`ActorRef`]. This will return `Some`
     /// if there are other `ActorRef` instances alive, otherwise `None` is returned.
     pub fn upgrade(&self) -> Option<ActorRef<A>> {
         self.mailbox.upgrade().map(|mailbox| ActorRef {
             id: self.id,
             mailbox,
-            abort_handle: self.abort_handle.clone(),
             links: self.links.clone(),
             startup_semaphore: self.startup_notify.clone(),
+            info: self.info.clone(),
         })
     }
 
@@ -1052,17 +1878,44 @@ impl<A: Actor> Clone for WeakActorRef<A> {
         WeakActorRef {
             id: self.id,
             mailbox: self.mailbox.clone(),
-            abort_handle: self.abort_handle.clone(),
             links: self.links.clone(),
             startup_notify: self.startup_notify.clone(),
+            info: self.info.clone(),
         }
     }
 }
 
+impl<A: Actor> PartialEq for WeakActorRef<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<A: Actor> Eq for WeakActorRef<A> {}
+
+impl<A: Actor> PartialOrd for WeakActorRef<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: Actor> Ord for WeakActorRef<A> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl<A: Actor> Hash for WeakActorRef<A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 impl<A: Actor> fmt::Debug for WeakActorRef<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut d = f.debug_struct("WeakActorRef");
         d.field("id", &self.id);
+        d.field("name", &self.name());
         match self.links.try_lock() {
             Ok(guard) => {
                 d.field("links", &guard.keys());
@@ -1075,6 +1928,84 @@ impl<A: Actor> fmt::Debug for WeakActorRef<A> {
     }
 }
 
+/// A send-only handle to an actor, created with [`ActorRef::sender`].
+///
+/// A `Sender` can be used to ask and tell messages to an actor, but cannot be used to link, stop, or downgrade it.
+/// Cloning a `Sender` only increments a single reference count.
+///
+/// Like an [`ActorRef`], the actor will remain alive for as long as any `Sender` exists.
+pub struct Sender<A: Actor> {
+    actor_ref: Arc<ActorRef<A>>,
+}
+
+impl<A: Actor> Sender<A> {
+    /// Returns the actor identifier.
+    #[inline]
+    pub fn id(&self) -> ActorID {
+        self.actor_ref.id()
+    }
+
+    /// Returns whether the actor is currently alive.
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        self.actor_ref.is_alive()
+    }
+
+    /// Sends a message to the actor, waiting for a reply.
+    ///
+    /// See [`ActorRef::ask`].
+    #[inline]
+    #[track_caller]
+    pub fn ask<M>(
+        &self,
+        msg: M,
+    ) -> AskRequest<
+        LocalAskRequest<'_, A, A::Mailbox>,
+        A::Mailbox,
+        M,
+        WithoutRequestTimeout,
+        WithoutRequestTimeout,
+    >
+    where
+        A: Message<M>,
+        M: Send + 'static,
+    {
+        self.actor_ref.ask(msg)
+    }
+
+    /// Sends a message to the actor without waiting for a reply.
+    ///
+    /// See [`ActorRef::tell`].
+    #[inline]
+    #[track_caller]
+    pub fn tell<M>(
+        &self,
+        msg: M,
+    ) -> TellRequest<LocalTellRequest<'_, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+    {
+        self.actor_ref.tell(msg)
+    }
+}
+
+impl<A: Actor> Clone for Sender<A> {
+    fn clone(&self) -> Self {
+        Sender {
+            actor_ref: self.actor_ref.clone(),
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for Sender<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("id", &self.actor_ref.id())
+            .finish()
+    }
+}
+
 /// A collection of links to other actors that are notified when the actor dies.
 ///
 /// Links are used for parent-child or sibling relationships, allowing actors to observe each other's lifecycle.
@@ -1096,3 +2027,166 @@ pub(crate) enum Link {
     #[cfg(feature = "remote")]
     Remote(std::borrow::Cow<'static, str>),
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, HashSet},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use futures::future::BoxFuture;
+
+    use crate::{
+        actor::{Actor, PreparedActor},
+        error::SendError,
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+    };
+
+    struct MyActor;
+
+    impl Actor for MyActor {
+        type Mailbox = UnboundedMailbox<Self>;
+    }
+
+    #[tokio::test]
+    async fn actor_refs_compare_by_id() {
+        let a = crate::spawn(MyActor);
+        let b = crate::spawn(MyActor);
+
+        assert_eq!(a, a.clone());
+        assert_ne!(a, b);
+        assert_eq!(a.cmp(&b), a.id().cmp(&b.id()));
+        // Actors spawned later have larger ids
+        assert!(a < b);
+
+        // Stopping an actor doesn't change its identity
+        a.kill();
+        a.wait_for_stop().await;
+        assert_eq!(a, a.clone());
+        assert!(a < b);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::mutable_key_type)]
+    async fn actor_refs_as_map_keys() {
+        let actor_refs: Vec<_> = (0..3).map(|_| crate::spawn(MyActor)).collect();
+
+        let mut map = BTreeMap::new();
+        for actor_ref in actor_refs.iter().rev() {
+            map.insert(actor_ref.clone(), actor_ref.id());
+        }
+        map.insert(actor_refs[1].clone(), actor_refs[1].id());
+        assert_eq!(map.len(), 3);
+        assert!(map.keys().eq(actor_refs.iter()));
+
+        let set: HashSet<_> = actor_refs.iter().chain(&actor_refs).cloned().collect();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&actor_refs[0]));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::mutable_key_type)]
+    async fn weak_actor_refs_compare_by_id() {
+        let a = crate::spawn(MyActor);
+        let b = crate::spawn(MyActor);
+
+        assert_eq!(a.downgrade(), a.downgrade());
+        assert_ne!(a.downgrade(), b.downgrade());
+        assert_eq!(a.downgrade().cmp(&b.downgrade()), a.cmp(&b));
+        assert_eq!(a.downgrade().upgrade().unwrap(), a);
+
+        let set: HashSet<_> = [a.downgrade(), a.downgrade(), b.downgrade()].into();
+        assert_eq!(set.len(), 2);
+    }
+    #[tokio::test]
+    async fn ask_requests_to_forked_actor() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Clone)]
+        struct MyActor {
+            items: Vec<u32>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Push(u32);
+
+        impl Message<Push> for MyActor {
+            type Reply = Vec<u32>;
+
+            async fn handle(
+                &mut self,
+                Push(n): Push,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.items.push(n);
+                // The fork must not observe a half-applied message
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                self.items.push(n);
+                self.items.clone()
+            }
+        }
+
+        let actor_ref = crate::spawn(MyActor { items: vec![] });
+        actor_ref.tell(Push(1)).await?;
+
+        let forked_ref = actor_ref.fork().await?;
+        assert_ne!(forked_ref.id(), actor_ref.id());
+        assert_eq!(forked_ref.ask(Push(2)).await?, vec![1, 1, 2, 2]);
+        assert_eq!(actor_ref.ask(Push(3)).await?, vec![1, 1, 3, 3]);
+
+        actor_ref.kill();
+        actor_ref.wait_for_stop().await;
+        assert!(forked_ref.is_alive());
+        assert_eq!(
+            actor_ref.fork().await.unwrap_err(),
+            SendError::ActorNotRunning(())
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn forked_actor_keeps_name_and_spawner() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Clone)]
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        // Forking doesn't reserve any message types, so actors can handle messages generically
+        impl<M: Send + 'static> Message<M> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: M, _ctx: Context<'_, Self, Self::Reply>) {}
+        }
+
+        let spawned = Arc::new(AtomicUsize::new(0));
+        let spawner = {
+            let spawned = spawned.clone();
+            move |future: BoxFuture<'static, ()>| {
+                spawned.fetch_add(1, Ordering::Relaxed);
+                tokio::spawn(future);
+            }
+        };
+
+        let prepared_actor = PreparedActor::new_named("original");
+        let actor_ref = prepared_actor.actor_ref().clone();
+        prepared_actor.spawn_with(&spawner, MyActor);
+        assert_eq!(spawned.load(Ordering::Relaxed), 1);
+
+        let forked_ref = actor_ref.fork().await?;
+        assert_eq!(forked_ref.name(), "original");
+        assert_eq!(spawned.load(Ordering::Relaxed), 2);
+        forked_ref.ask(()).await?;
+
+        Ok(())
+    }
+}
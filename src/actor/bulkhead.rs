@@ -0,0 +1,262 @@
+//! Provides bulkheads for isolating groups of actors from each other.
+//!
+//! A bulkhead is a named group of actors sharing a concurrency budget, which limits how many of its actors can be
+//! handling a message at the same time. Actors spawned in a bulkhead wait for a permit from the budget before handling
+//! each message, so an overloaded or misbehaving group can only ever occupy its own budget, and can't starve actors in
+//! other bulkheads or outside of any bulkhead. A bulkhead can also run its actors on a runtime of its own, isolating
+//! their worker threads as well.
+//!
+//! # Budgets and per-actor limits
+//!
+//! Each actor still handles one message at a time, so a bulkhead's budget caps how many of its actors are busy at
+//! once, rather than how many messages a single actor handles. An actor waiting for a permit keeps its messages queued
+//! in its mailbox as usual, so the actor's own mailbox capacity and backpressure still apply, and a full bulkhead
+//! slows down its senders rather than dropping messages. Lifecycle hooks such as [`Actor::on_start`] don't use the
+//! budget.
+//!
+//! Permits are held for the whole of a message handler, including any time spent waiting on other actors. An actor
+//! which asks another actor in the same bulkhead while the budget is exhausted will wait forever, so actors which
+//! depend on each other should be placed in different bulkheads, or be given enough budget for the whole chain.
+//!
+//! # Example
+//!
+//! ```
+//! use kameo::Actor;
+//! use kameo::actor::bulkhead::{spawn_in_bulkhead, Bulkhead};
+//!
+//! #[derive(Actor)]
+//! struct FileWriter;
+//!
+//! # tokio_test::block_on(async {
+//! // At most 4 actors in the bulkhead handle messages at the same time
+//! Bulkhead::new("io-group", 4).register();
+//!
+//! let writer = spawn_in_bulkhead(FileWriter, "io-group");
+//! # writer.wait_startup().await;
+//! # })
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
+
+use tokio::{
+    runtime::Handle,
+    sync::{OwnedSemaphorePermit, Semaphore},
+};
+
+use crate::Actor;
+
+use super::{ActorRef, PreparedActor};
+
+static BULKHEADS: OnceLock<Mutex<HashMap<Arc<str>, Bulkhead>>> = OnceLock::new();
+
+fn bulkheads() -> &'static Mutex<HashMap<Arc<str>, Bulkhead>> {
+    BULKHEADS.get_or_init(Default::default)
+}
+
+/// A named group of actors sharing a concurrency budget.
+///
+/// See the [module level documentation](self) for more information.
+#[derive(Clone)]
+pub struct Bulkhead {
+    name: Arc<str>,
+    max_concurrent: usize,
+    permits: Arc<Semaphore>,
+    runtime: Option<Handle>,
+}
+
+impl Bulkhead {
+    /// Creates a new bulkhead, allowing at most `max_concurrent` of its actors to handle a message at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_concurrent` is zero, or larger than [`Semaphore::MAX_PERMITS`].
+    pub fn new(name: impl Into<Arc<str>>, max_concurrent: usize) -> Self {
+        assert!(
+            max_concurrent > 0,
+            "bulkhead budget must be greater than zero"
+        );
+        Bulkhead {
+            name: name.into(),
+            max_concurrent,
+            permits: Arc::new(Semaphore::new(max_concurrent)),
+            runtime: None,
+        }
+    }
+
+    /// Runs the bulkhead's actors on the given runtime, rather than the runtime they're spawned from.
+    pub fn runtime(mut self, handle: Handle) -> Self {
+        self.runtime = Some(handle);
+        self
+    }
+
+    /// Registers the bulkhead under its name, so actors can be spawned in it with [`spawn_in_bulkhead`].
+    ///
+    /// Returns `false` if a bulkhead with the same name is already registered, in which case the existing bulkhead
+    /// is kept.
+    pub fn register(&self) -> bool {
+        let mut bulkheads = bulkheads().lock().unwrap_or_else(PoisonError::into_inner);
+        if bulkheads.contains_key(&self.name) {
+            return false;
+        }
+        bulkheads.insert(self.name.clone(), self.clone());
+        true
+    }
+
+    /// Returns the registered bulkhead with the given name.
+    pub fn get(name: &str) -> Option<Bulkhead> {
+        bulkheads()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned()
+    }
+
+    /// Returns the name of the bulkhead.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the maximum number of actors in the bulkhead which can handle a message at the same time.
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    /// Returns the number of actors in the bulkhead currently handling a message.
+    pub fn in_use(&self) -> usize {
+        self.max_concurrent - self.permits.available_permits()
+    }
+
+    /// Spawns an actor in the bulkhead.
+    ///
+    /// Unlike [`spawn_in_bulkhead`], the bulkhead doesn't need to be registered.
+    pub fn spawn<A: Actor>(&self, actor: A) -> ActorRef<A> {
+        let prepared_actor = PreparedActor::new();
+        let actor_ref = prepared_actor.actor_ref().clone();
+        let _ = actor_ref.info.bulkhead.set(self.clone());
+        match &self.runtime {
+            Some(handle) => prepared_actor.spawn_with(handle, actor),
+            None => {
+                prepared_actor.spawn(actor);
+            }
+        }
+        actor_ref
+    }
+
+    /// Waits for a permit to handle a message.
+    pub(crate) async fn acquire(&self) -> OwnedSemaphorePermit {
+        self.permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("bulkhead semaphore is never closed")
+    }
+}
+
+impl fmt::Debug for Bulkhead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bulkhead")
+            .field("name", &self.name)
+            .field("max_concurrent", &self.max_concurrent)
+            .field("in_use", &self.in_use())
+            .finish()
+    }
+}
+
+/// Spawns an actor in the registered bulkhead with the given name.
+///
+/// See the [module level documentation](self) for more information.
+///
+/// # Panics
+///
+/// Panics if no bulkhead is registered with the given name.
+pub fn spawn_in_bulkhead<A: Actor>(actor: A, name: &str) -> ActorRef<A> {
+    let bulkhead = Bulkhead::get(name).unwrap_or_else(|| panic!("no bulkhead named {name:?}"));
+    bulkhead.spawn(actor)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use crate::{
+        actor::Actor,
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+        spawn,
+    };
+
+    use super::{spawn_in_bulkhead, Bulkhead};
+
+    #[tokio::test]
+    async fn bulkhead_limits_concurrent_handlers() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Clone, Default)]
+        struct Busy {
+            current: Arc<AtomicUsize>,
+            max: Arc<AtomicUsize>,
+        }
+
+        impl Actor for Busy {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Work;
+
+        impl Message<Work> for Busy {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                _: Work,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                self.current.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        let bulkhead = Bulkhead::new("tell_requests_in_bulkhead", 2);
+        assert!(bulkhead.register());
+        assert!(!Bulkhead::new("tell_requests_in_bulkhead", 8).register());
+
+        let busy = Busy::default();
+        let actor_refs: Vec<_> = (0..4)
+            .map(|_| spawn_in_bulkhead(busy.clone(), "tell_requests_in_bulkhead"))
+            .collect();
+        for actor_ref in &actor_refs {
+            actor_ref.tell(Work).await?;
+            actor_ref.tell(Work).await?;
+        }
+        for actor_ref in &actor_refs {
+            actor_ref.flush().await?;
+        }
+
+        // Only two of the four actors handle a message at the same time
+        assert_eq!(busy.max.load(Ordering::SeqCst), 2);
+        assert_eq!(bulkhead.in_use(), 0);
+
+        // Actors outside the bulkhead aren't limited by it
+        let unlimited = Busy::default();
+        let actor_refs: Vec<_> = (0..4).map(|_| spawn(unlimited.clone())).collect();
+        for actor_ref in &actor_refs {
+            actor_ref.tell(Work).await?;
+        }
+        for actor_ref in &actor_refs {
+            actor_ref.flush().await?;
+        }
+        assert_eq!(unlimited.max.load(Ordering::SeqCst), 4);
+
+        Ok(())
+    }
+}
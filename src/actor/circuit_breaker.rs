@@ -0,0 +1,493 @@
+//! Provides circuit breakers for failing fast on requests to an actor which keeps failing.
+//!
+//! A [`CircuitBreaker`] wraps an [`ActorRef`] and watches the results of the requests sent through it. Once a number
+//! of requests in a row have failed, the circuit opens, and further requests fail immediately with
+//! [`SendError::CircuitOpen`] instead of being sent. This stops callers from piling work onto an actor which is
+//! struggling, such as one wrapping a database or remote service which is down, and gives it time to recover.
+//!
+//! # States
+//!
+//! - **Closed**: Requests are sent as usual. After [`failure_threshold`] failures in a row within the [`window`], the
+//!   circuit opens.
+//! - **Open**: Requests fail immediately without being sent. After the [`cooldown`], the circuit becomes half-open.
+//! - **Half-open**: A single trial request is sent, while others fail immediately. If it succeeds the circuit
+//!   closes, otherwise it opens again for another cooldown. Results of requests sent before the circuit opened are
+//!   ignored, so only the trial decides.
+//!
+//! Every error returned by a request counts as a failure, including handler errors and timeouts, and any reply
+//! counts as a success. The current state can be read with [`CircuitBreaker::state`], such as for reporting metrics.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use kameo::actor::circuit_breaker::{CircuitBreaker, CircuitState};
+//! # use kameo::message::{Context, Message};
+//!
+//! #[derive(kameo::Actor)]
+//! struct Database;
+//!
+//! struct Query(String);
+//! #
+//! # impl Message<Query> for Database {
+//! #     type Reply = Result<u64, String>;
+//! #     async fn handle(&mut self, msg: Query, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { Ok(0) }
+//! # }
+//!
+//! # tokio_test::block_on(async {
+//! let database = kameo::spawn(Database);
+//! let breaker = CircuitBreaker::new(database)
+//!     .failure_threshold(3)
+//!     .cooldown(Duration::from_secs(10))
+//!     .timeout(Duration::from_secs(1));
+//!
+//! let rows = breaker.ask(Query("SELECT count(*) FROM users".to_string())).await?;
+//! assert_eq!(breaker.state(), CircuitState::Closed);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+//!
+//! [`failure_threshold`]: CircuitBreaker::failure_threshold
+//! [`window`]: CircuitBreaker::window
+//! [`cooldown`]: CircuitBreaker::cooldown
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::SendError,
+    message::Message,
+    request::{AskRequest, LocalAskRequest, MessageSend, WithoutRequestTimeout},
+    Actor, Reply,
+};
+
+use super::ActorRef;
+
+/// The state of a [`CircuitBreaker`].
+///
+/// See the [module level documentation](self) for more information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+    /// Requests are sent to the actor.
+    Closed,
+    /// Requests fail immediately with [`SendError::CircuitOpen`].
+    Open,
+    /// The cooldown has passed, and the next request is sent to test whether the actor has recovered.
+    HalfOpen,
+}
+
+/// A wrapper around an [`ActorRef`] which stops sending requests to the actor while they keep failing.
+///
+/// Clones share the same circuit, so a breaker can be cloned into each task calling the actor.
+///
+/// See the [module level documentation](self) for more information.
+pub struct CircuitBreaker<A: Actor> {
+    actor_ref: ActorRef<A>,
+    failure_threshold: u32,
+    window: Duration,
+    cooldown: Duration,
+    timeout: Option<Duration>,
+    circuit: Arc<Mutex<Circuit>>,
+}
+
+#[derive(Debug)]
+enum Circuit {
+    Closed {
+        /// The number of requests in a row which have failed.
+        failures: u32,
+        /// When the first of the failures in a row happened.
+        since: Option<Instant>,
+    },
+    Open {
+        until: Instant,
+    },
+    HalfOpen {
+        /// Whether the trial request is still waiting for a reply.
+        trial: bool,
+    },
+}
+
+impl<A: Actor> CircuitBreaker<A> {
+    /// Creates a new closed circuit breaker for the actor.
+    ///
+    /// By default, the circuit opens after 5 failures in a row within 60 seconds, and stays open for 30 seconds.
+    pub fn new(actor_ref: ActorRef<A>) -> Self {
+        CircuitBreaker {
+            actor_ref,
+            failure_threshold: 5,
+            window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+            timeout: None,
+            circuit: Arc::new(Mutex::new(Circuit::Closed {
+                failures: 0,
+                since: None,
+            })),
+        }
+    }
+
+    /// Sets how many requests in a row must fail for the circuit to open.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the threshold is zero.
+    pub fn failure_threshold(mut self, threshold: u32) -> Self {
+        assert!(threshold > 0, "failure threshold must be greater than zero");
+        self.failure_threshold = threshold;
+        self
+    }
+
+    /// Sets how long failures in a row are counted for.
+    ///
+    /// Once this long has passed since the first of the failures, the count starts again from the next failure, so
+    /// occasional failures spread out over time never open the circuit.
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Sets how long the circuit stays open before a trial request is sent.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Sets the maximum duration to wait for each request, including waiting for mailbox capacity.
+    ///
+    /// A request which doesn't reply in time fails with [`SendError::Timeout`], and counts as a failure.
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = Some(duration);
+        self
+    }
+
+    /// Returns the actor requests are sent to.
+    pub fn actor_ref(&self) -> &ActorRef<A> {
+        &self.actor_ref
+    }
+
+    /// Returns the current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        match *self.lock() {
+            Circuit::Closed { .. } => CircuitState::Closed,
+            Circuit::Open { until } if Instant::now() < until => CircuitState::Open,
+            Circuit::Open { .. } | Circuit::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Sends a message to the actor and waits for a reply, unless the circuit is open.
+    ///
+    /// Returns [`SendError::CircuitOpen`] with the message if the circuit is open, or if it's half-open and a trial
+    /// request is already waiting for a reply.
+    ///
+    /// See [`ActorRef::ask`].
+    pub async fn ask<M>(
+        &self,
+        msg: M,
+    ) -> Result<<A::Reply as Reply>::Ok, SendError<M, <A::Reply as Reply>::Error>>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        for<'a> AskRequest<
+            LocalAskRequest<'a, A, A::Mailbox>,
+            A::Mailbox,
+            M,
+            WithoutRequestTimeout,
+            WithoutRequestTimeout,
+        >: MessageSend<
+            Ok = <A::Reply as Reply>::Ok,
+            Error = SendError<M, <A::Reply as Reply>::Error>,
+        >,
+    {
+        let Some(mut trial) = self.acquire() else {
+            return Err(SendError::CircuitOpen(msg));
+        };
+
+        let fut = self.actor_ref.ask(msg).send();
+        let res = match self.timeout {
+            Some(duration) => tokio::time::timeout(duration, fut)
+                .await
+                .unwrap_or_else(|elapsed| Err(elapsed.into())),
+            None => fut.await,
+        };
+        trial.record(res.is_ok());
+        res
+    }
+
+    /// Checks whether a request can be sent, returning `None` if the circuit is open.
+    fn acquire(&self) -> Option<Trial<'_, A>> {
+        let mut circuit = self.lock();
+        let is_trial = match *circuit {
+            Circuit::Closed { .. } => false,
+            Circuit::Open { until } if Instant::now() < until => return None,
+            Circuit::Open { .. } | Circuit::HalfOpen { trial: false } => {
+                *circuit = Circuit::HalfOpen { trial: true };
+                true
+            }
+            Circuit::HalfOpen { trial: true } => return None,
+        };
+        Some(Trial {
+            breaker: self,
+            is_trial,
+            recorded: false,
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Circuit> {
+        self.circuit.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<A: Actor> Clone for CircuitBreaker<A> {
+    fn clone(&self) -> Self {
+        CircuitBreaker {
+            actor_ref: self.actor_ref.clone(),
+            failure_threshold: self.failure_threshold,
+            window: self.window,
+            cooldown: self.cooldown,
+            timeout: self.timeout,
+            circuit: self.circuit.clone(),
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for CircuitBreaker<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("actor_id", &self.actor_ref.id())
+            .field("state", &self.state())
+            .field("failure_threshold", &self.failure_threshold)
+            .field("window", &self.window)
+            .field("cooldown", &self.cooldown)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// A request allowed through the circuit, which records its result once it completes.
+struct Trial<'a, A: Actor> {
+    breaker: &'a CircuitBreaker<A>,
+    is_trial: bool,
+    recorded: bool,
+}
+
+impl<A: Actor> Trial<'_, A> {
+    fn record(&mut self, success: bool) {
+        self.recorded = true;
+        let breaker = self.breaker;
+        let mut circuit = breaker.lock();
+        let now = Instant::now();
+        match (&mut *circuit, success) {
+            // A request sent before the circuit opened doesn't affect the cooldown
+            (Circuit::Open { .. }, _) => {}
+            // Only the trial request decides whether a half-open circuit closes or opens again
+            (Circuit::HalfOpen { .. }, _) if !self.is_trial => {}
+            (Circuit::Closed { .. } | Circuit::HalfOpen { .. }, true) => {
+                *circuit = Circuit::Closed {
+                    failures: 0,
+                    since: None,
+                };
+            }
+            (Circuit::Closed { failures, since }, false) => {
+                match since {
+                    Some(since) if now.duration_since(*since) <= breaker.window => {
+                        *failures += 1;
+                    }
+                    _ => {
+                        *failures = 1;
+                        *since = Some(now);
+                    }
+                }
+                if *failures >= breaker.failure_threshold {
+                    *circuit = Circuit::Open {
+                        until: now + breaker.cooldown,
+                    };
+                }
+            }
+            (Circuit::HalfOpen { .. }, false) => {
+                *circuit = Circuit::Open {
+                    until: now + breaker.cooldown,
+                };
+            }
+        }
+    }
+}
+
+impl<A: Actor> Drop for Trial<'_, A> {
+    fn drop(&mut self) {
+        // A trial request which was cancelled lets the next request be the trial instead
+        if self.is_trial && !self.recorded {
+            let mut circuit = self.breaker.lock();
+            if let Circuit::HalfOpen { trial } = &mut *circuit {
+                *trial = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::{
+        actor::Actor,
+        error::SendError,
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+    };
+
+    use super::{Circuit, CircuitBreaker, CircuitState};
+
+    struct MyActor;
+
+    impl Actor for MyActor {
+        type Mailbox = UnboundedMailbox<Self>;
+    }
+
+    fn end_cooldown(breaker: &CircuitBreaker<MyActor>) {
+        *breaker.lock() = Circuit::Open {
+            until: Instant::now(),
+        };
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_opens_after_failures_in_a_row() {
+        let breaker = CircuitBreaker::new(crate::spawn(MyActor)).failure_threshold(2);
+
+        breaker.acquire().unwrap().record(false);
+        breaker.acquire().unwrap().record(true);
+        breaker.acquire().unwrap().record(false);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.acquire().unwrap().record(false);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.acquire().is_none());
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_forgets_failures_outside_window() {
+        let breaker = CircuitBreaker::new(crate::spawn(MyActor))
+            .failure_threshold(2)
+            .window(Duration::from_millis(20));
+
+        breaker.acquire().unwrap().record(false);
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        breaker.acquire().unwrap().record(false);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_ignores_stale_results_while_half_open() {
+        let breaker = CircuitBreaker::new(crate::spawn(MyActor)).failure_threshold(1);
+
+        // Requests sent while the circuit was closed, which complete after it opens
+        let mut stale_success = breaker.acquire().unwrap();
+        let mut stale_failure = breaker.acquire().unwrap();
+        breaker.acquire().unwrap().record(false);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        end_cooldown(&breaker);
+        let mut trial = breaker.acquire().unwrap();
+        assert!(trial.is_trial);
+        assert!(breaker.acquire().is_none());
+
+        stale_success.record(true);
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        stale_failure.record(false);
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.acquire().is_none());
+
+        trial.record(true);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_reopens_after_failed_trial() {
+        let breaker = CircuitBreaker::new(crate::spawn(MyActor)).failure_threshold(1);
+
+        breaker.acquire().unwrap().record(false);
+        end_cooldown(&breaker);
+        breaker.acquire().unwrap().record(false);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // A cancelled trial lets the next request be the trial instead
+        end_cooldown(&breaker);
+        drop(breaker.acquire().unwrap());
+        let trial = breaker.acquire().unwrap();
+        assert!(trial.is_trial);
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_ask_opens_and_closes_circuit() -> Result<(), Box<dyn std::error::Error>>
+    {
+        struct Downstream {
+            healthy: bool,
+        }
+
+        impl Actor for Downstream {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Call;
+
+        impl Message<Call> for Downstream {
+            type Reply = Result<(), String>;
+
+            async fn handle(
+                &mut self,
+                _msg: Call,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                if self.healthy {
+                    Ok(())
+                } else {
+                    Err("unavailable".to_string())
+                }
+            }
+        }
+
+        struct SetHealthy(bool);
+
+        impl Message<SetHealthy> for Downstream {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                msg: SetHealthy,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.healthy = msg.0;
+            }
+        }
+
+        let actor_ref = crate::spawn(Downstream { healthy: false });
+        let breaker = CircuitBreaker::new(actor_ref.clone())
+            .failure_threshold(2)
+            .cooldown(Duration::from_millis(50));
+
+        assert!(breaker.ask(Call).await.unwrap_err().is_handler_error());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.ask(Call).await.unwrap_err().is_handler_error());
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(
+            breaker.ask(Call).await,
+            Err(SendError::CircuitOpen(Call))
+        ));
+
+        // A failed trial opens the circuit again
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.ask(Call).await.unwrap_err().is_handler_error());
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // A successful trial closes it
+        actor_ref.ask(SetHealthy(true)).await?;
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        breaker.ask(Call).await?;
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,316 @@
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use serde::Serialize;
+use tokio::sync::Semaphore;
+
+use super::{
+    actor_ref::{ActorInfo, Link, Links},
+    ActorID,
+};
+
+static ACTORS: OnceLock<Mutex<HashMap<ActorID, RegisteredActor>>> = OnceLock::new();
+
+struct RegisteredActor {
+    links: Links,
+    startup_semaphore: Arc<Semaphore>,
+    info: Arc<ActorInfo>,
+}
+
+/// A snapshot of all running actors and the links between them, returned by [`export_graph`].
+///
+/// The graph can be rendered with graphviz using [`SupervisionGraph::to_dot`], or serialized with serde,
+/// for example to JSON.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SupervisionGraph {
+    /// The actors running in the current process.
+    pub nodes: Vec<ActorNode>,
+    /// The links between actors.
+    ///
+    /// Links are bidirectional, so each link appears only once.
+    pub edges: Vec<LinkEdge>,
+}
+
+impl SupervisionGraph {
+    /// Renders the graph in the graphviz DOT format.
+    ///
+    /// Links to remote actors are drawn as dashed edges to a node which only shows the actor's id.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph kameo {\n");
+        for node in &self.nodes {
+            let _ = writeln!(
+                dot,
+                "    \"{}\" [label=\"{}\\n{}\"];",
+                node.id,
+                node.name.replace('"', "\\\""),
+                node.id,
+            );
+        }
+        for edge in &self.edges {
+            let style = if edge.remote { " [style=dashed]" } else { "" };
+            let _ = writeln!(dot, "    \"{}\" -- \"{}\"{style};", edge.from, edge.to);
+        }
+        dot.push('}');
+        dot
+    }
+}
+
+/// An actor in a [`SupervisionGraph`].
+#[derive(Clone, Debug, Serialize)]
+pub struct ActorNode {
+    /// The id of the actor.
+    pub id: ActorID,
+    /// The name of the actor, as returned by [`ActorRef::name`](crate::actor::ActorRef::name).
+    pub name: Cow<'static, str>,
+    /// Whether the actor is still starting up, or is running.
+    pub state: ActorNodeState,
+    /// The number of messages the actor has finished handling.
+    pub messages_handled: u64,
+    /// The number of signals which were waiting in the mailbox when the last message was received.
+    pub mailbox_len: usize,
+}
+
+/// The lifecycle state of an [`ActorNode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorNodeState {
+    /// The actor's [`on_start`](crate::Actor::on_start) hook has not yet finished.
+    Starting,
+    /// The actor is processing messages.
+    Running,
+}
+
+/// A link between two actors in a [`SupervisionGraph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct LinkEdge {
+    /// One of the linked actors.
+    pub from: ActorID,
+    /// The other linked actor.
+    pub to: ActorID,
+    /// Whether `to` is an actor running on a remote peer.
+    pub remote: bool,
+}
+
+/// Exports the current supervision graph of all actors running in this process.
+///
+/// Nodes are added when an actor is spawned and removed once it has stopped, and edges are created with
+/// [`ActorRef::link`](crate::actor::ActorRef::link) and its variants.
+///
+/// Since actors keep running while the graph is being built, the result is a best effort snapshot.
+///
+/// # Example
+///
+/// ```
+/// # use kameo::Actor;
+/// #
+/// # #[derive(Actor)]
+/// # struct MyActor;
+/// #
+/// # tokio_test::block_on(async {
+/// let a = kameo::spawn(MyActor);
+/// let b = kameo::spawn(MyActor);
+/// a.link(&b).await;
+///
+/// let graph = kameo::export_graph().await;
+/// assert_eq!(graph.nodes.len(), 2);
+/// assert_eq!(graph.edges.len(), 1);
+/// println!("{}", graph.to_dot());
+/// # });
+/// ```
+pub async fn export_graph() -> SupervisionGraph {
+    let actors: Vec<_> = actors()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .iter()
+        .map(|(id, actor)| {
+            (
+                *id,
+                actor.links.clone(),
+                actor.startup_semaphore.clone(),
+                actor.info.clone(),
+            )
+        })
+        .collect();
+
+    let mut graph = SupervisionGraph::default();
+    let mut seen = HashSet::new();
+    for (id, links, startup_semaphore, info) in actors {
+        let state = if startup_semaphore.available_permits() > 0 {
+            ActorNodeState::Running
+        } else {
+            ActorNodeState::Starting
+        };
+        let stats = info.stats.snapshot();
+        graph.nodes.push(ActorNode {
+            id,
+            name: info.name.clone(),
+            state,
+            messages_handled: stats.messages_handled,
+            mailbox_len: stats.mailbox_len,
+        });
+
+        for (link_id, link) in links.lock().await.iter() {
+            seen.insert((id, *link_id));
+            if seen.contains(&(*link_id, id)) {
+                // Already added from the other side of the link
+                continue;
+            }
+            graph.edges.push(LinkEdge {
+                from: id,
+                to: *link_id,
+                remote: !matches!(link, Link::Local(_)),
+            });
+        }
+    }
+
+    graph
+}
+
+/// Registers a spawned actor in the supervision graph until the returned guard is dropped.
+pub(crate) fn register(
+    id: ActorID,
+    links: Links,
+    startup_semaphore: Arc<Semaphore>,
+    info: Arc<ActorInfo>,
+) -> RegisteredGuard {
+    actors()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(
+            id,
+            RegisteredActor {
+                links,
+                startup_semaphore,
+                info,
+            },
+        );
+
+    RegisteredGuard { id }
+}
+
+fn actors() -> &'static Mutex<HashMap<ActorID, RegisteredActor>> {
+    ACTORS.get_or_init(Default::default)
+}
+
+/// Removes an actor from the supervision graph when dropped.
+pub(crate) struct RegisteredGuard {
+    id: ActorID,
+}
+
+impl Drop for RegisteredGuard {
+    fn drop(&mut self) {
+        actors()
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::Notify;
+
+    use crate::{
+        actor::{spawn_named, Actor, ActorRef},
+        error::BoxError,
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+        request::MessageSend,
+    };
+
+    use super::{export_graph, ActorNode, ActorNodeState, SupervisionGraph};
+
+    #[derive(Default)]
+    struct MyActor {
+        started: Option<Arc<Notify>>,
+    }
+
+    impl Actor for MyActor {
+        type Mailbox = UnboundedMailbox<Self>;
+
+        async fn on_start(&mut self, _actor_ref: ActorRef<Self>) -> Result<(), BoxError> {
+            if let Some(started) = &self.started {
+                started.notified().await;
+            }
+            Ok(())
+        }
+    }
+
+    struct Ping;
+
+    impl Message<Ping> for MyActor {
+        type Reply = ();
+
+        async fn handle(&mut self, _msg: Ping, _ctx: Context<'_, Self, Self::Reply>) {}
+    }
+
+    fn node<A: Actor>(graph: &SupervisionGraph, actor_ref: &ActorRef<A>) -> Option<ActorNode> {
+        graph
+            .nodes
+            .iter()
+            .find(|node| node.id == actor_ref.id())
+            .cloned()
+    }
+
+    #[tokio::test]
+    async fn export_graph_snapshots_actors_and_links() -> Result<(), Box<dyn std::error::Error>> {
+        let started = Arc::new(Notify::new());
+        let starting = crate::spawn(MyActor {
+            started: Some(started.clone()),
+        });
+        let running = spawn_named(MyActor::default(), "my \"actor\"");
+        running.wait_startup().await;
+        running.link(&starting).await;
+        running.ask(Ping).send().await?;
+        running.flush().await?;
+
+        let graph = export_graph().await;
+        let starting_node = node(&graph, &starting).unwrap();
+        let running_node = node(&graph, &running).unwrap();
+        assert_eq!(starting_node.state, ActorNodeState::Starting);
+        assert_eq!(running_node.state, ActorNodeState::Running);
+        assert_eq!(running_node.name, "my \"actor\"");
+        assert_eq!(running_node.messages_handled, 1);
+
+        // Links are only exported once
+        let ids = [starting.id(), running.id()];
+        let edges: Vec<_> = graph
+            .edges
+            .iter()
+            .filter(|edge| ids.contains(&edge.from) || ids.contains(&edge.to))
+            .collect();
+        assert_eq!(edges.len(), 1);
+        assert!(ids.contains(&edges[0].from) && ids.contains(&edges[0].to));
+        assert!(!edges[0].remote);
+
+        let dot = graph.to_dot();
+        assert!(dot.contains(&format!(
+            "\"{}\" [label=\"my \\\"actor\\\"\\n{}\"];",
+            running.id(),
+            running.id()
+        )));
+        assert!(dot.contains(&format!("\"{}\" -- \"{}\";", edges[0].from, edges[0].to)));
+
+        // Stopped actors are removed
+        started.notify_one();
+        starting.wait_startup().await;
+        assert_eq!(
+            node(&export_graph().await, &starting).unwrap().state,
+            ActorNodeState::Running
+        );
+        running.kill();
+        running.wait_for_stop().await;
+        starting.wait_for_stop().await;
+        let graph = export_graph().await;
+        assert!(node(&graph, &running).is_none());
+        assert!(node(&graph, &starting).is_none());
+
+        Ok(())
+    }
+}
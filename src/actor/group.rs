@@ -0,0 +1,333 @@
+//! Provides supervision groups of linked actors.
+//!
+//! The `group` module offers a way to link a set of interdependent actors together, so that if any one of them dies,
+//! every other actor in the group is notified through [`Actor::on_link_died`]. With the default `on_link_died`
+//! behaviour, this results in a "one for all" group, where the failure of a single actor stops the whole group.
+//!
+//! Links are fully meshed, meaning every actor is linked to every other actor in the group. For a group of `n` actors,
+//! this creates `n * (n - 1) / 2` links. For large groups, a hub-and-spoke layout can be used instead by linking each
+//! actor to a single supervisor with [`ActorRef::link`], which only needs `n` links but notifies the supervisor alone.
+//!
+//! # Features
+//! - **Bulk Linking**: Link a set of actors together with a single call to [`link_group`].
+//! - **Group Handle**: Use a [`GroupRef`] to broadcast messages to, or stop, the entire group.
+//!
+//! # Example
+//!
+//! ```
+//! use kameo::Actor;
+//! use kameo::actor::group::GroupRef;
+//! # use kameo::message::{Context, Message};
+//!
+//! #[derive(Actor)]
+//! struct MyActor;
+//! #
+//! # impl Message<&'static str> for MyActor {
+//! #     type Reply = ();
+//! #     async fn handle(&mut self, msg: &'static str, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+//! # }
+//!
+//! # tokio_test::block_on(async {
+//! let group = GroupRef::new((0..3).map(|_| kameo::spawn(MyActor))).await;
+//!
+//! group.broadcast("Hello, group!").await;
+//! group.stop_gracefully().await;
+//! group.wait_for_stop().await;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+
+use std::{fmt, iter::repeat_n, sync::Arc};
+
+use futures::future::join_all;
+
+use crate::{
+    error::SendError,
+    message::Message,
+    request::{LocalTellRequest, MessageSend, TellRequest, WithoutRequestTimeout},
+    Actor, Reply,
+};
+
+use super::ActorRef;
+
+/// Links every actor in the slice to every other actor, forming a fully meshed supervision group.
+///
+/// If any actor in the group dies, all other actors in the group will be notified through [`Actor::on_link_died`].
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct MyActor;
+///
+/// # tokio_test::block_on(async {
+/// let a = kameo::spawn(MyActor);
+/// let b = kameo::spawn(MyActor);
+/// let c = kameo::spawn(MyActor);
+///
+/// kameo::actor::group::link_group(&[&a, &b, &c]).await;
+/// # })
+/// ```
+pub async fn link_group<A: Actor>(actors: &[&ActorRef<A>]) {
+    for (i, actor_ref) in actors.iter().enumerate() {
+        for sibbling_ref in &actors[i + 1..] {
+            actor_ref.link(sibbling_ref).await;
+        }
+    }
+}
+
+/// A handle to a group of linked actors.
+///
+/// Creating a `GroupRef` links all actors together using [`link_group`],
+/// and allows messages to be broadcasted to, and the lifecycle managed for, the entire group.
+pub struct GroupRef<A: Actor> {
+    actors: Vec<ActorRef<A>>,
+}
+
+impl<A: Actor> GroupRef<A> {
+    /// Creates a new group from a set of actors, linking them all together.
+    pub async fn new(actors: impl IntoIterator<Item = ActorRef<A>>) -> Self {
+        let actors: Vec<_> = actors.into_iter().collect();
+        link_group(&actors.iter().collect::<Vec<_>>()).await;
+
+        GroupRef { actors }
+    }
+
+    /// Returns the actors in the group.
+    pub fn actors(&self) -> &[ActorRef<A>] {
+        &self.actors
+    }
+
+    /// Sends a message to every actor in the group, returning the result of each send.
+    ///
+    /// The message is cloned for each actor. For large messages, use [`broadcast_shared`](GroupRef::broadcast_shared)
+    /// to share a single copy instead.
+    pub async fn broadcast<M>(
+        &self,
+        msg: M,
+    ) -> Vec<Result<(), SendError<M, <A::Reply as Reply>::Error>>>
+    where
+        A: Message<M>,
+        M: Clone + Send + 'static,
+        for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+            MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+    {
+        join_all(
+            self.actors
+                .iter()
+                .zip(repeat_n(msg, self.actors.len()))
+                .map(|(actor_ref, msg)| actor_ref.tell(msg).send()),
+        )
+        .await
+    }
+
+    /// Sends a shared message to every actor in the group, returning the result of each send.
+    ///
+    /// Each actor receives a clone of the `Arc` rather than of the message, so broadcasting a large payload, such as
+    /// a configuration blob, allocates it once regardless of the size of the group. The actors handle the message
+    /// by implementing `Message<Arc<M>>`.
+    pub async fn broadcast_shared<M>(
+        &self,
+        msg: Arc<M>,
+    ) -> Vec<Result<(), SendError<Arc<M>, <A::Reply as Reply>::Error>>>
+    where
+        A: Message<Arc<M>>,
+        M: Send + Sync + 'static,
+        for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, Arc<M>, WithoutRequestTimeout>:
+            MessageSend<Ok = (), Error = SendError<Arc<M>, <A::Reply as Reply>::Error>>,
+    {
+        self.broadcast(msg).await
+    }
+
+    /// Signals every actor in the group to stop after processing all messages currently in their mailboxes.
+    pub async fn stop_gracefully(&self) {
+        join_all(
+            self.actors
+                .iter()
+                .map(|actor_ref| actor_ref.stop_gracefully()),
+        )
+        .await;
+    }
+
+    /// Kills every actor in the group immediately.
+    pub fn kill(&self) {
+        for actor_ref in &self.actors {
+            actor_ref.kill();
+        }
+    }
+
+    /// Waits for every actor in the group to stop.
+    pub async fn wait_for_stop(&self) {
+        join_all(
+            self.actors
+                .iter()
+                .map(|actor_ref| actor_ref.wait_for_stop()),
+        )
+        .await;
+    }
+}
+
+impl<A: Actor> Clone for GroupRef<A> {
+    fn clone(&self) -> Self {
+        GroupRef {
+            actors: self.actors.clone(),
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for GroupRef<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GroupRef")
+            .field("actors", &self.actors)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use crate::{
+        actor::{Actor, PreparedActor},
+        error::ActorStopReason,
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+    };
+
+    use super::{link_group, GroupRef};
+
+    struct MyActor {
+        received: Arc<AtomicUsize>,
+    }
+
+    impl Actor for MyActor {
+        type Mailbox = UnboundedMailbox<Self>;
+    }
+
+    impl Message<u32> for MyActor {
+        type Reply = ();
+
+        async fn handle(&mut self, msg: u32, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+            self.received.fetch_add(msg as usize, Ordering::Relaxed);
+        }
+    }
+
+    fn my_actor() -> MyActor {
+        MyActor {
+            received: Arc::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn link_group_stops_every_actor_when_one_dies() {
+        let prepared: Vec<_> = (0..3).map(|_| PreparedActor::new()).collect();
+        let actor_refs: Vec<_> = prepared.iter().map(|p| p.actor_ref().clone()).collect();
+        link_group(&actor_refs.iter().collect::<Vec<_>>()).await;
+        let outsider = crate::spawn(my_actor());
+        let mut handles: Vec<_> = prepared.into_iter().map(|p| p.spawn(my_actor())).collect();
+
+        actor_refs[0].kill();
+        let (_, reason) = handles.remove(0).await.unwrap();
+        assert_eq!(reason, ActorStopReason::Killed);
+        for handle in handles {
+            let (_, reason) = handle.await.unwrap();
+            assert!(matches!(reason, ActorStopReason::LinkDied { .. }));
+        }
+        assert!(outsider.is_alive());
+    }
+
+    #[tokio::test]
+    async fn link_group_ignores_normal_stops() {
+        let a = crate::spawn(my_actor());
+        let b = crate::spawn(my_actor());
+        let c = crate::spawn(my_actor());
+        link_group(&[&a, &b, &c]).await;
+
+        a.stop_gracefully().await.unwrap();
+        a.wait_for_stop().await;
+        b.tell(1).await.unwrap();
+        c.tell(1).await.unwrap();
+        assert!(b.is_alive());
+        assert!(c.is_alive());
+    }
+
+    #[tokio::test]
+    async fn group_ref_broadcasts_to_and_stops_every_actor() {
+        let received = Arc::new(AtomicUsize::new(0));
+        let group = GroupRef::new((0..3).map(|_| {
+            crate::spawn(MyActor {
+                received: received.clone(),
+            })
+        }))
+        .await;
+        assert_eq!(group.actors().len(), 3);
+
+        assert!(group.broadcast(2).await.iter().all(Result::is_ok));
+        group.stop_gracefully().await;
+        group.wait_for_stop().await;
+        assert_eq!(received.load(Ordering::Relaxed), 6);
+        assert!(group.actors().iter().all(|actor_ref| !actor_ref.is_alive()));
+        assert!(group.broadcast(1).await.iter().all(Result::is_err));
+    }
+
+    #[tokio::test]
+    async fn group_ref_broadcasts_shared_messages() -> Result<(), Box<dyn std::error::Error>> {
+        struct Config(Vec<u8>);
+
+        #[derive(Default)]
+        struct MyActor {
+            config: Option<Arc<Config>>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        impl Message<Arc<Config>> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                config: Arc<Config>,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.config = Some(config);
+            }
+        }
+
+        struct GetConfig;
+
+        impl Message<GetConfig> for MyActor {
+            type Reply = Option<Arc<Config>>;
+
+            async fn handle(
+                &mut self,
+                _: GetConfig,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.config.clone()
+            }
+        }
+
+        let group = GroupRef::new((0..3).map(|_| crate::spawn(MyActor::default()))).await;
+        let config = Arc::new(Config(vec![0; 1024]));
+        let results = group.broadcast_shared(config.clone()).await;
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(Result::is_ok));
+        for actor_ref in group.actors() {
+            let received = actor_ref
+                .ask(GetConfig)
+                .await?
+                .expect("config was broadcast");
+            assert!(Arc::ptr_eq(&received, &config));
+            assert_eq!(received.0.len(), 1024);
+        }
+
+        Ok(())
+    }
+}
@@ -9,11 +9,24 @@ use crate::remote::ActorSwarm;
 
 static ACTOR_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// The bit set in the sequence ids of seeded actor ids, which the process-wide counter never reaches.
+#[cfg(feature = "test-util")]
+const SEEDED_ID_BIT: u64 = 1 << 63;
+
+#[cfg(feature = "test-util")]
+thread_local! {
+    /// The next sequence id to assign on this thread, if ids have been seeded with [`ActorID::seed`].
+    static SEEDED_COUNTER: std::cell::Cell<Option<u64>> = const { std::cell::Cell::new(None) };
+}
+
 /// A globally unique identifier for an actor within a distributed system.
 ///
 /// `ActorID` combines a locally sequential `sequence_id` with an optional `peer_id`
-/// to uniquely identify actors across a distributed network.#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// to uniquely identify actors across a distributed network.
+///
+/// IDs are ordered by their `sequence_id`, and then by their `peer_id`. Since sequence ids are assigned in
+/// increasing order, local actors are ordered by when they were spawned.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ActorID {
     sequence_id: u64,
     #[cfg(feature = "remote")]
@@ -67,9 +80,60 @@ impl ActorID {
     ///
     /// A new `ActorID` instance with the next available `sequence_id`.
     pub fn generate() -> Self {
+        #[cfg(feature = "test-util")]
+        if let Some(sequence_id) = SEEDED_COUNTER.with(|counter| {
+            let sequence_id = counter.get()?;
+            counter.set(Some(sequence_id.wrapping_add(1) & !SEEDED_ID_BIT));
+            Some(sequence_id)
+        }) {
+            return ActorID::new(SEEDED_ID_BIT | sequence_id);
+        }
+
         ActorID::new(ACTOR_COUNTER.fetch_add(1, Ordering::Relaxed))
     }
 
+    /// Makes actor ids generated on the current thread deterministic, starting from `next`.
+    ///
+    /// **This is for tests only, and should never be used in production.** Seeded ids have the highest bit of their
+    /// sequence id set, which the process-wide counter never reaches, so they can't clash with the ids of actors
+    /// spawned without a seed. They are only unique within the thread which seeded them though, so two threads
+    /// seeded with the same value would share ids, confusing anything keyed by actor id, such as links and
+    /// [`export_graph`](crate::actor::export_graph).
+    ///
+    /// Seeding makes logs and exported supervision graphs reproducible across test runs, so they can be compared
+    /// against golden files. Ids are assigned when an actor is spawned, on the spawning thread, so every actor must
+    /// be spawned from the seeded thread. This is the case for tests using a current thread runtime, which is the
+    /// default for `#[tokio::test]`.
+    ///
+    /// Ids are generated from the process-wide counter again once the returned guard is dropped.
+    ///
+    /// Requires the `test-util` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::ActorID;
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let _seed = ActorID::seed(100);
+    /// let a = kameo::spawn(MyActor);
+    /// let b = kameo::spawn(MyActor);
+    /// assert_eq!(a.id().sequence_id(), 1 << 63 | 100);
+    /// assert_eq!(b.id().sequence_id(), 1 << 63 | 101);
+    /// # })
+    /// ```
+    #[cfg(feature = "test-util")]
+    pub fn seed(next: u64) -> ActorIDSeed {
+        let previous = SEEDED_COUNTER.with(|counter| counter.replace(Some(next & !SEEDED_ID_BIT)));
+        ActorIDSeed {
+            previous,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
     /// Returns the sequential identifier of the actor.
     ///
     /// This `sequence_id` is a unique, locally-generated `u64` assigned to each actor
@@ -150,6 +214,25 @@ impl ActorID {
     }
 }
 
+/// A guard which keeps actor ids on the current thread deterministic, returned by [`ActorID::seed`].
+///
+/// Dropping the guard restores the ids generated before it was created.
+#[cfg(feature = "test-util")]
+#[must_use = "actor ids are only seeded until the guard is dropped"]
+#[derive(Debug)]
+pub struct ActorIDSeed {
+    previous: Option<u64>,
+    /// The guard restores a thread local, so it must be dropped on the thread it was created on.
+    phantom: std::marker::PhantomData<*const ()>,
+}
+
+#[cfg(feature = "test-util")]
+impl Drop for ActorIDSeed {
+    fn drop(&mut self) {
+        SEEDED_COUNTER.with(|counter| counter.set(self.previous));
+    }
+}
+
 impl fmt::Display for ActorID {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         #[cfg(not(feature = "remote"))]
@@ -205,7 +288,7 @@ impl<'de> Deserialize<'de> for ActorID {
 }
 
 #[cfg(feature = "remote")]
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 enum PeerIdKind {
     Local,
     PeerId(libp2p::PeerId),
@@ -220,3 +303,58 @@ impl PeerIdKind {
         }
     }
 }
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use crate::{
+        actor::Actor,
+        error::SendError,
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+        spawn,
+    };
+
+    use super::ActorID;
+
+    #[tokio::test]
+    async fn seeded_actor_ids() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        impl Message<()> for MyActor {
+            type Reply = u64;
+
+            async fn handle(&mut self, _msg: (), ctx: Context<'_, Self, Self::Reply>) -> u64 {
+                ctx.actor_ref().spawn_link(MyActor).await.id().sequence_id()
+            }
+        }
+
+        // Seeded ids never clash with ids from the process-wide counter, even with the same sequence
+        let unseeded = ActorID::generate();
+        let seeded = {
+            let _seed = ActorID::seed(unseeded.sequence_id());
+            ActorID::generate()
+        };
+        assert_ne!(seeded, unseeded);
+
+        let ids = || async {
+            let _seed = ActorID::seed(1_000);
+            let actor_ref = spawn(MyActor);
+            let child_id = actor_ref.ask(()).await?;
+            Ok::<_, SendError>((actor_ref.id().sequence_id(), child_id))
+        };
+        let (first, first_child) = ids().await?;
+        let (second, second_child) = ids().await?;
+        assert_eq!(first, 1 << 63 | 1_000);
+        assert_eq!(first_child, 1 << 63 | 1_001);
+        assert_eq!((first, first_child), (second, second_child));
+
+        // Ids come from the process-wide counter again once the guard is dropped
+        assert!(spawn(MyActor).id().sequence_id() < 1 << 63);
+
+        Ok(())
+    }
+}
@@ -1,28 +1,45 @@
-use std::{collections::VecDeque, mem, panic::AssertUnwindSafe};
+use std::{
+    any,
+    collections::VecDeque,
+    panic::AssertUnwindSafe,
+    sync::Arc,
+    time::{Instant, SystemTime},
+};
 
 use futures::{Future, FutureExt};
+use tokio::sync::oneshot::{self, error::TryRecvError};
 
 use crate::{
     actor::{Actor, ActorRef, WeakActorRef},
-    error::{ActorStopReason, PanicError},
+    error::{ActorStopReason, MessageRecord, PanicError, SendError},
     mailbox::Signal,
-    message::DynMessage,
-    reply::BoxReplySender,
+    message::{ActorLocals, DynMessage, Extensions, MessageMeta, Redelivery},
+    reply::{BoxReplyResult, BoxReplySender, StrandedReply},
 };
 
-use super::ActorID;
+use super::{watchdog, ActorID};
 
 pub(crate) trait ActorState<A: Actor>: Sized {
     fn new_from_actor(actor: A, actor_ref: WeakActorRef<A>) -> Self;
 
     fn handle_startup_finished(&mut self) -> impl Future<Output = Option<ActorStopReason>> + Send;
 
+    #[allow(clippy::too_many_arguments)]
     fn handle_message(
         &mut self,
         message: Box<dyn DynMessage<A>>,
         actor_ref: ActorRef<A>,
         reply: Option<BoxReplySender>,
         sent_within_actor: bool,
+        extensions: Extensions,
+        meta: MessageMeta<A>,
+        sent_at: Instant,
+        mailbox_len: usize,
+    ) -> impl Future<Output = Option<ActorStopReason>> + Send;
+
+    fn handle_resume(
+        &mut self,
+        mailbox_len: usize,
     ) -> impl Future<Output = Option<ActorStopReason>> + Send;
 
     fn handle_link_died(
@@ -31,6 +48,10 @@ pub(crate) trait ActorState<A: Actor>: Sized {
         reason: ActorStopReason,
     ) -> impl Future<Output = Option<ActorStopReason>> + Send;
 
+    fn handle_flush(&mut self, tx: oneshot::Sender<()>);
+
+    fn handle_fork(&mut self, fork: Box<dyn FnOnce(&A) + Send>);
+
     fn handle_stop(&mut self) -> impl Future<Output = Option<ActorStopReason>> + Send;
 
     fn on_shutdown(
@@ -38,7 +59,11 @@ pub(crate) trait ActorState<A: Actor>: Sized {
         reason: ActorStopReason,
     ) -> impl Future<Output = Option<ActorStopReason>> + Send;
 
-    fn shutdown(self) -> impl Future<Output = A> + Send;
+    /// Returns the actor, along with any signals which were received but never handled.
+    fn shutdown(
+        self,
+        reason: &ActorStopReason,
+    ) -> impl Future<Output = (A, VecDeque<Signal<A>>)> + Send;
 }
 
 pub(crate) struct ActorBehaviour<A: Actor> {
@@ -46,6 +71,95 @@ pub(crate) struct ActorBehaviour<A: Actor> {
     state: A,
     finished_startup: bool,
     startup_buffer: VecDeque<Signal<A>>,
+    locals: ActorLocals,
+    reply: Option<BoxReplySender>,
+    /// The type id and name of the message most recently handled, for replying to its caller if it panicked.
+    reply_message: Option<(any::TypeId, &'static str)>,
+    redelivery: Option<PendingRedelivery<A>>,
+    /// The most recently handled messages, if enabled with [`Actor::record_history`].
+    history: VecDeque<MessageRecord>,
+}
+
+impl<A: Actor> ActorBehaviour<A> {
+    /// Attaches the recorded message history to an error from a message handler, logging it.
+    fn with_history(&self, err: PanicError) -> PanicError {
+        if self.history.is_empty() {
+            return err;
+        }
+
+        let history: Arc<[MessageRecord]> = self.history.iter().cloned().collect();
+        #[cfg(feature = "tracing")]
+        tracing::error!(
+            id = %self.actor_ref.id(),
+            name = %self.actor_ref.name(),
+            %err,
+            history = ?history,
+            "message handler failed after handling {} recorded messages",
+            history.len(),
+        );
+        err.with_history(history)
+    }
+
+    /// Lets the actor send a fallback reply to the caller of a message which panicked, now the actor is continuing.
+    async fn reply_after_panic(&mut self, err: Option<PanicError>) -> Option<ActorStopReason> {
+        let (Some(err), Some(tx), Some((message, message_name))) =
+            (err, self.reply.take(), self.reply_message)
+        else {
+            return None;
+        };
+        let reply = StrandedReply::new(tx, message, message_name);
+        match self
+            .state
+            .on_panic_reply(self.actor_ref.clone(), err, reply)
+            .await
+        {
+            Ok(()) => None,
+            Err(err) => Some(ActorStopReason::Panicked(PanicError::new_hook_error::<A>(
+                err,
+            ))),
+        }
+    }
+
+    /// Passes a reply relayed from a handler which could be aborted on to the caller.
+    fn relay_reply(&mut self, tx: BoxReplySender, mut relay_rx: oneshot::Receiver<BoxReplyResult>) {
+        if tx.is_closed() {
+            // The caller stopped waiting, so there's nobody to reply to
+            self.reply = None;
+            return;
+        }
+        if self.reply.take().is_some() {
+            // The handler didn't reply, such as if it panicked, so the caller is notified directly
+            self.reply = Some(tx);
+            return;
+        }
+
+        match relay_rx.try_recv() {
+            Ok(reply) => {
+                let _ = tx.send(reply);
+            }
+            Err(TryRecvError::Empty) => {
+                // The reply was delegated, so it's passed on once it's sent
+                self.actor_ref.info.spawn(
+                    async move {
+                        if let Ok(reply) = relay_rx.await {
+                            let _ = tx.send(reply);
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+            Err(TryRecvError::Closed) => {}
+        }
+    }
+}
+
+/// A message which may be redelivered if its handler panics.
+struct PendingRedelivery<A: Actor> {
+    redelivery: Box<Redelivery<A>>,
+    actor_ref: ActorRef<A>,
+    sent_within_actor: bool,
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    message_name: &'static str,
 }
 
 impl<A> ActorState<A> for ActorBehaviour<A>
@@ -59,26 +173,49 @@ where
             state: actor,
             finished_startup: false,
             startup_buffer: VecDeque::new(),
+            locals: ActorLocals::default(),
+            reply: None,
+            reply_message: None,
+            redelivery: None,
+            history: VecDeque::new(),
         }
     }
 
     async fn handle_startup_finished(&mut self) -> Option<ActorStopReason> {
         self.finished_startup = true;
-        for signal in mem::take(&mut self.startup_buffer).drain(..) {
+        while let Some(signal) = self.startup_buffer.pop_front() {
             match signal {
                 Signal::Message {
                     message,
                     actor_ref,
                     reply,
                     sent_within_actor,
+                    extensions,
+                    meta,
+                    sent_at,
                 } => {
+                    let mailbox_len = self.startup_buffer.len();
                     if let Some(reason) = self
-                        .handle_message(message, actor_ref, reply, sent_within_actor)
+                        .handle_message(
+                            message,
+                            actor_ref,
+                            reply,
+                            sent_within_actor,
+                            extensions,
+                            meta,
+                            sent_at,
+                            mailbox_len,
+                        )
                         .await
                     {
                         return Some(reason);
                     }
                 }
+                Signal::Flush(tx) => {
+                    let _ = tx.send(());
+                }
+                Signal::Fork(fork) => fork(&self.state),
+                Signal::Stop => return Some(ActorStopReason::Normal),
                 _ => unreachable!(),
             }
         }
@@ -93,6 +230,10 @@ where
         actor_ref: ActorRef<A>,
         reply: Option<BoxReplySender>,
         sent_within_actor: bool,
+        extensions: Extensions,
+        mut meta: MessageMeta<A>,
+        sent_at: Instant,
+        mailbox_len: usize,
     ) -> Option<ActorStopReason> {
         if !sent_within_actor && !self.finished_startup {
             // The actor is still starting up, so we'll push this message to a buffer to be processed upon startup
@@ -101,18 +242,149 @@ where
                 actor_ref,
                 reply,
                 sent_within_actor,
+                extensions,
+                meta,
+                sent_at,
             });
             return None;
         }
 
-        let res = AssertUnwindSafe(message.handle_dyn(&mut self.state, actor_ref, reply))
-            .catch_unwind()
-            .await;
+        self.redelivery = meta.redelivery.take().map(|redelivery| PendingRedelivery {
+            redelivery,
+            actor_ref: actor_ref.clone(),
+            sent_within_actor,
+            message_name: (*message).message_name(),
+        });
+        let _bulkhead_permit = match self.actor_ref.info.bulkhead.get() {
+            Some(bulkhead) => Some(bulkhead.acquire().await),
+            None => None,
+        };
+        let message_name = (*message).message_name();
+        self.reply_message = Some(((*message).message_type(), message_name));
+        let _watchdog_guard =
+            watchdog::track(self.actor_ref.id(), &self.actor_ref.info, message_name);
+        let received_at = (A::record_history() > 0).then(|| (SystemTime::now(), Instant::now()));
+        self.actor_ref
+            .info
+            .stats
+            .message_started(mailbox_len, sent_at.elapsed());
+        // The handler borrows the reply sender, so to watch for the caller dropping its reply future while the
+        // handler runs, the reply is relayed through a channel of our own
+        let mut caller = None;
+        self.reply = match reply {
+            Some(tx) if A::abort_abandoned_requests() => {
+                let (relay_tx, relay_rx) = oneshot::channel();
+                caller = Some((tx, relay_rx));
+                Some(relay_tx.into())
+            }
+            reply => reply,
+        };
+        let handler = AssertUnwindSafe(message.handle_dyn(
+            &mut self.state,
+            actor_ref,
+            &mut self.reply,
+            extensions,
+            meta,
+            &mut self.locals,
+            mailbox_len,
+        ))
+        .catch_unwind();
+        let res = match &mut caller {
+            Some((tx, _)) => {
+                tokio::select! {
+                    biased;
+                    res = handler => res,
+                    // The caller stopped waiting, so the handler is dropped and the message is treated as handled
+                    _ = tx.closed() => Ok(None),
+                }
+            }
+            None => handler.await,
+        };
+        if let Some((tx, relay_rx)) = caller {
+            self.relay_reply(tx, relay_rx);
+        }
+        self.actor_ref.info.stats.message_handled();
+        if let Some((received_at, started_at)) = received_at {
+            if self.history.len() == A::record_history() {
+                self.history.pop_front();
+            }
+            self.history.push_back(MessageRecord {
+                name: message_name,
+                received_at,
+                elapsed: started_at.elapsed(),
+            });
+        }
+        if res.is_ok() {
+            self.redelivery = None;
+            self.actor_ref.info.state_watch.publish(&self.state);
+        }
+        let stop = self.locals.stop_request.take();
         match res {
-            Ok(None) => None,
-            Ok(Some(err)) => Some(ActorStopReason::Panicked(PanicError::new(err))), // The reply was an error
-            Err(err) => Some(ActorStopReason::Panicked(PanicError::new_boxed(err))), // The handler panicked
+            Ok(None) => stop,
+            Ok(Some(err)) => {
+                // The reply was an error with no caller to receive it
+                match self
+                    .state
+                    .on_async_error(
+                        self.actor_ref.clone(),
+                        self.with_history(PanicError::new(err)),
+                    )
+                    .await
+                {
+                    Ok(Some(reason)) => Some(reason),
+                    Ok(None) => stop,
+                    Err(err) => Some(ActorStopReason::Panicked(PanicError::new_hook_error::<A>(
+                        err,
+                    ))),
+                }
+            }
+            Err(err) => {
+                // The handler panicked
+                Some(ActorStopReason::Panicked(
+                    self.with_history(PanicError::new_panic(err)),
+                ))
+            }
+        }
+    }
+
+    async fn handle_resume(&mut self, mailbox_len: usize) -> Option<ActorStopReason> {
+        // Redeliver the message which panicked before any other message
+        if let Some(PendingRedelivery {
+            mut redelivery,
+            actor_ref,
+            sent_within_actor,
+            ..
+        }) = self.redelivery.take()
+        {
+            redelivery.remaining -= 1;
+            let message = redelivery.message();
+            let meta = MessageMeta {
+                redelivery: Some(redelivery),
+                ..MessageMeta::default()
+            };
+            if let Some(reason) = self
+                .handle_message(
+                    message,
+                    actor_ref,
+                    None,
+                    sent_within_actor,
+                    Extensions::new(),
+                    meta,
+                    Instant::now(),
+                    mailbox_len + self.startup_buffer.len(),
+                )
+                .await
+            {
+                return Some(reason);
+            }
         }
+
+        // A panic while handling the startup buffer leaves the remaining messages in it
+        if self.finished_startup && !self.startup_buffer.is_empty() {
+            return self.handle_startup_finished().await;
+        }
+
+        None
     }
 
     #[inline]
@@ -132,26 +404,60 @@ where
         match res {
             Ok(Ok(Some(reason))) => Some(reason),
             Ok(Ok(None)) => None,
-            Ok(Err(err)) => Some(ActorStopReason::Panicked(PanicError::new(err))),
-            Err(err) => Some(ActorStopReason::Panicked(PanicError::new_boxed(err))),
+            Ok(Err(err)) => Some(ActorStopReason::Panicked(PanicError::new_hook_error::<A>(
+                err,
+            ))),
+            Err(err) => Some(ActorStopReason::Panicked(PanicError::new_panic(err))),
         }
     }
 
+    #[inline]
+    fn handle_flush(&mut self, tx: oneshot::Sender<()>) {
+        if !self.finished_startup {
+            // Messages buffered during startup must be handled before the flush resolves
+            self.startup_buffer.push_back(Signal::Flush(tx));
+            return;
+        }
+
+        let _ = tx.send(());
+    }
+
+    #[inline]
+    fn handle_fork(&mut self, fork: Box<dyn FnOnce(&A) + Send>) {
+        if !self.finished_startup {
+            // The copy must include messages buffered during startup
+            self.startup_buffer.push_back(Signal::Fork(fork));
+            return;
+        }
+
+        fork(&self.state);
+    }
+
     #[inline]
     async fn handle_stop(&mut self) -> Option<ActorStopReason> {
+        if !self.finished_startup {
+            // Messages buffered during startup were sent before the stop signal, so must be handled first
+            self.startup_buffer.push_back(Signal::Stop);
+            return None;
+        }
+
         Some(ActorStopReason::Normal)
     }
 
     #[inline]
     async fn on_shutdown(&mut self, reason: ActorStopReason) -> Option<ActorStopReason> {
-        match reason {
+        let reason = match reason {
             ActorStopReason::Normal => Some(ActorStopReason::Normal),
             ActorStopReason::Killed => Some(ActorStopReason::Killed),
             ActorStopReason::Panicked(err) => {
+                self.actor_ref.info.stats.panicked();
+                let panic_err = self.reply.is_some().then(|| err.clone());
                 match self.state.on_panic(self.actor_ref.clone(), err).await {
                     Ok(Some(reason)) => Some(reason),
-                    Ok(None) => None,
-                    Err(err) => Some(ActorStopReason::Panicked(PanicError::new(err))),
+                    Ok(None) => self.reply_after_panic(panic_err).await,
+                    Err(err) => Some(ActorStopReason::Panicked(PanicError::new_hook_error::<A>(
+                        err,
+                    ))),
                 }
             }
             ActorStopReason::LinkDied { id, reason } => {
@@ -159,11 +465,36 @@ where
             }
             #[cfg(feature = "remote")]
             ActorStopReason::PeerDisconnected => Some(ActorStopReason::PeerDisconnected),
+        };
+        if reason.is_none() {
+            // The actor is continuing after a panic, so the caller of the failed message won't receive a reply
+            self.reply = None;
+            if let Some(pending) = &self.redelivery {
+                if pending.redelivery.remaining == 0 {
+                    // The message has panicked on every delivery, so drop it rather than redelivering it forever
+                    #[cfg(feature = "tracing")]
+                    tracing::error!(
+                        id = %self.actor_ref.id(),
+                        name = %self.actor_ref.name(),
+                        message = %pending.message_name,
+                        "dropping message which panicked on every redelivery"
+                    );
+                    self.redelivery = None;
+                }
+            }
         }
+        reason
     }
 
     #[inline]
-    async fn shutdown(self) -> A {
-        self.state
+    async fn shutdown(mut self, reason: &ActorStopReason) -> (A, VecDeque<Signal<A>>) {
+        for (task, _) in self.locals.spawned_tasks.drain(..) {
+            task.abort();
+        }
+        if let Some(tx) = self.reply {
+            // The actor stopped while handling a message, so notify the caller why
+            let _ = tx.send(Err(SendError::ActorDied(reason.clone())));
+        }
+        (self.state, self.startup_buffer)
     }
 }
@@ -0,0 +1,141 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    error::SendError,
+    message::Message,
+    request::{AskRequest, LocalAskRequest, MessageSend, WithoutRequestTimeout},
+    Actor, Reply,
+};
+
+use super::{ActorID, ActorRef};
+
+/// A handle to an actor which transforms the replies of its ask requests.
+///
+/// A `MappedRef` is created with [`ActorRef::map_reply`], and applies the same function to the reply of every
+/// message sent through it. This keeps cross-cutting reply handling, such as decompressing or decrypting
+/// replies, with the reference rather than repeating it at every call site.
+///
+/// The function is infallible, and is only applied to successful replies. Errors, including the handler's own
+/// errors, are returned unchanged. For a transformation which can fail, return a `Result` from the function.
+pub struct MappedRef<A: Actor, T, U> {
+    actor_ref: ActorRef<A>,
+    f: Arc<dyn Fn(T) -> U + Send + Sync>,
+}
+
+impl<A: Actor, T, U> MappedRef<A, T, U> {
+    pub(crate) fn new(actor_ref: ActorRef<A>, f: impl Fn(T) -> U + Send + Sync + 'static) -> Self {
+        MappedRef {
+            actor_ref,
+            f: Arc::new(f),
+        }
+    }
+
+    /// Returns the actor identifier.
+    #[inline]
+    pub fn id(&self) -> ActorID {
+        self.actor_ref.id()
+    }
+
+    /// Returns whether the actor is currently alive.
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        self.actor_ref.is_alive()
+    }
+
+    /// Returns the underlying actor reference, whose replies are not transformed.
+    #[inline]
+    pub fn actor_ref(&self) -> &ActorRef<A> {
+        &self.actor_ref
+    }
+
+    /// Sends a message to the actor, waiting for a reply and transforming it.
+    ///
+    /// See [`ActorRef::ask`].
+    pub async fn ask<M>(&self, msg: M) -> Result<U, SendError<M, <A::Reply as Reply>::Error>>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        for<'a> AskRequest<
+            LocalAskRequest<'a, A, A::Mailbox>,
+            A::Mailbox,
+            M,
+            WithoutRequestTimeout,
+            WithoutRequestTimeout,
+        >: MessageSend<Ok = T, Error = SendError<M, <A::Reply as Reply>::Error>>,
+    {
+        self.actor_ref
+            .ask(msg)
+            .send()
+            .await
+            .map(|reply| (self.f)(reply))
+    }
+}
+
+impl<A: Actor, T, U> Clone for MappedRef<A, T, U> {
+    fn clone(&self) -> Self {
+        MappedRef {
+            actor_ref: self.actor_ref.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<A: Actor, T, U> fmt::Debug for MappedRef<A, T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedRef")
+            .field("id", &self.actor_ref.id())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        actor::Actor,
+        error::SendError,
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+    };
+
+    #[tokio::test]
+    async fn mapped_actor_ref_maps_replies() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Get(u32);
+
+        impl Message<Get> for MyActor {
+            type Reply = Result<u32, &'static str>;
+
+            async fn handle(
+                &mut self,
+                Get(n): Get,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                if n == 0 {
+                    return Err("zero");
+                }
+                Ok(n)
+            }
+        }
+
+        let actor_ref = crate::spawn(MyActor);
+        let mapped_ref = actor_ref.map_reply(|n: u32| n.to_string());
+        assert_eq!(mapped_ref.id(), actor_ref.id());
+
+        assert_eq!(mapped_ref.ask(Get(7)).await?, "7");
+        assert_eq!(mapped_ref.clone().ask(Get(12)).await?, "12");
+
+        // Handler errors are returned without applying the function
+        assert_eq!(
+            mapped_ref.ask(Get(0)).await,
+            Err(SendError::HandlerError("zero"))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,338 @@
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{ready, Context, Poll},
+    thread,
+};
+
+use futures::{
+    future::{self, BoxFuture, Either},
+    task::AtomicWaker,
+    FutureExt,
+};
+use tokio::{runtime::Handle, sync::oneshot};
+
+use super::actor_ref::ActorInfo;
+
+/// Coordinates moving an actor's task to another executor.
+#[derive(Default)]
+pub(crate) struct MigrationSlot {
+    /// Whether the actor was spawned as a task which can be moved.
+    supported: AtomicBool,
+    /// Whether the actor is waiting for its next message.
+    idle: AtomicBool,
+    /// A pending request to hand over the actor's task.
+    request: Mutex<Option<oneshot::Sender<BoxFuture<'static, ()>>>>,
+    /// Hands the actor's task back to the executor awaiting its output, if the executor it migrated to drops it.
+    origin: Mutex<Option<oneshot::Sender<BoxFuture<'static, ()>>>>,
+    /// Serializes concurrent migrations of the same actor.
+    pub(crate) lock: tokio::sync::Mutex<()>,
+    waker: AtomicWaker,
+}
+
+impl MigrationSlot {
+    pub(crate) fn is_supported(&self) -> bool {
+        self.supported.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn set_idle(&self, idle: bool) {
+        self.idle.store(idle, Ordering::Release);
+    }
+}
+
+/// A request for an actor's task, which resolves once the actor is waiting for its next message.
+///
+/// If the request is dropped after the task was handed over, the actor resumes on the current runtime
+/// rather than being lost.
+pub(crate) struct MigrationRequest {
+    rx: Option<oneshot::Receiver<BoxFuture<'static, ()>>>,
+    info: Arc<ActorInfo>,
+}
+
+impl MigrationRequest {
+    pub(crate) fn new(info: Arc<ActorInfo>) -> Self {
+        let (tx, rx) = oneshot::channel();
+        let slot = &info.migration;
+        *slot.request.lock().unwrap_or_else(|err| err.into_inner()) = Some(tx);
+        slot.waker.wake();
+        MigrationRequest { rx: Some(rx), info }
+    }
+}
+
+impl Future for MigrationRequest {
+    type Output = Option<BoxFuture<'static, ()>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Some(rx) = self.rx.as_mut() else {
+            return Poll::Ready(None);
+        };
+        let res = ready!(rx.poll_unpin(cx));
+        self.rx = None;
+        Poll::Ready(res.ok())
+    }
+}
+
+impl Drop for MigrationRequest {
+    fn drop(&mut self) {
+        let Some(mut rx) = self.rx.take() else {
+            return;
+        };
+        if let Ok(task) = rx.try_recv() {
+            if let Ok(handle) = Handle::try_current() {
+                handle.spawn(Migratable::new(task, self.info.clone()));
+            }
+        }
+    }
+}
+
+/// Runs an actor's task, allowing it to be moved to another executor with
+/// [`ActorRef::migrate`](crate::actor::ActorRef::migrate).
+///
+/// If the actor is migrated, this keeps waiting until it stops on its new executor, so the output is
+/// always the result of the actor's task. If the executor it migrated to drops its task, such as when shutting
+/// down, the actor is killed and its task is handed back here to finish stopping with
+/// [`ActorStopReason::Killed`](crate::error::ActorStopReason::Killed).
+pub(crate) fn migratable<F>(info: Arc<ActorInfo>, task: F) -> impl Future<Output = F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let (tx, mut rx) = oneshot::channel();
+    // Panics are caught so the task can always be handed back, and resumed once it's returned here
+    let mut task = Migratable::new(
+        async move {
+            let _ = tx.send(AssertUnwindSafe(task).catch_unwind().await);
+        }
+        .boxed(),
+        info.clone(),
+    );
+    task.is_origin = true;
+    async move {
+        loop {
+            let (origin_tx, origin_rx) = oneshot::channel();
+            *info
+                .migration
+                .origin
+                .lock()
+                .unwrap_or_else(|err| err.into_inner()) = Some(origin_tx);
+            (&mut task).await;
+            match future::select(&mut rx, origin_rx).await {
+                Either::Left((Ok(res), _)) => match res {
+                    Ok(output) => return output,
+                    Err(payload) => panic::resume_unwind(payload),
+                },
+                Either::Right((Ok(returned), _)) => {
+                    task = Migratable::new(returned, info.clone());
+                    task.is_origin = true;
+                }
+                // The task is only dropped without being handed back if this future is dropped too
+                Either::Left((Err(_), _)) | Either::Right((Err(_), _)) => {
+                    future::pending::<()>().await
+                }
+            }
+        }
+    }
+}
+
+/// A task which hands itself over when the actor is migrated, rather than completing.
+///
+/// If the task is dropped before it completes, such as when its `JoinHandle` is aborted, the actor is killed and
+/// its task resumes on the current runtime, so that [`Actor::on_stop`](crate::Actor::on_stop) is still called.
+pub(crate) struct Migratable {
+    task: Option<BoxFuture<'static, ()>>,
+    info: Arc<ActorInfo>,
+    /// Whether this runs on the executor awaiting the task's output, rather than one it migrated to.
+    is_origin: bool,
+}
+
+impl Migratable {
+    pub(crate) fn new(task: BoxFuture<'static, ()>, info: Arc<ActorInfo>) -> Self {
+        info.migration.supported.store(true, Ordering::Release);
+        Migratable {
+            task: Some(task),
+            info,
+            is_origin: false,
+        }
+    }
+}
+
+impl Future for Migratable {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+        let Some(task) = this.task.as_mut() else {
+            return Poll::Ready(());
+        };
+        if task.poll_unpin(cx).is_ready() {
+            this.task = None;
+            return Poll::Ready(());
+        }
+
+        let slot = &this.info.migration;
+        slot.waker.register(cx.waker());
+        // Only hand over the task between messages, so handlers never move mid-execution
+        if !slot.idle.load(Ordering::Acquire) {
+            return Poll::Pending;
+        }
+        let Some(tx) = slot
+            .request
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .take()
+        else {
+            return Poll::Pending;
+        };
+        match tx.send(this.task.take().unwrap()) {
+            Ok(()) => Poll::Ready(()),
+            Err(task) => {
+                // The migration was cancelled, so keep running here
+                this.task = Some(task);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for Migratable {
+    fn drop(&mut self) {
+        let Some(mut task) = self.task.take() else {
+            return;
+        };
+        if !self.is_origin {
+            let origin = self
+                .info
+                .migration
+                .origin
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .take();
+            if let Some(origin) = origin {
+                self.info.abort_handle.abort();
+                match origin.send(task) {
+                    Ok(()) => return,
+                    Err(returned) => task = returned,
+                }
+            }
+        }
+        // A task which panicked can't be polled again
+        if thread::panicking() {
+            return;
+        }
+        if let Ok(handle) = Handle::try_current() {
+            self.info.abort_handle.abort();
+            handle.spawn(task);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        actor::{Actor, PreparedActor},
+        error::{ActorStopReason, MigrateError},
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+        request::MessageSend,
+    };
+
+    #[tokio::test]
+    async fn migrate_moves_actor_to_spawner() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor {
+            count: u32,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Inc;
+
+        impl Message<Inc> for MyActor {
+            type Reply = (u32, Option<String>);
+
+            async fn handle(
+                &mut self,
+                _msg: Inc,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.count += 1;
+                (
+                    self.count,
+                    std::thread::current().name().map(ToString::to_string),
+                )
+            }
+        }
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("migrated")
+            .build()?;
+
+        let prepared_actor = PreparedActor::new();
+        let actor_ref = prepared_actor.actor_ref().clone();
+        let id = actor_ref.id();
+        let handle = prepared_actor.spawn(MyActor { count: 0 });
+
+        assert_eq!(actor_ref.ask(Inc).send().await?.0, 1);
+        actor_ref.tell(Inc).send().await?;
+        actor_ref.migrate(runtime.handle()).await?;
+        assert_eq!(actor_ref.id(), id);
+        assert_eq!(
+            actor_ref.ask(Inc).send().await?,
+            (3, Some("migrated".to_string()))
+        );
+
+        actor_ref.stop_gracefully().await?;
+        let (actor, reason) = handle.await?;
+        assert_eq!(actor.count, 3);
+        assert_eq!(reason, ActorStopReason::Normal);
+        assert_eq!(
+            actor_ref.migrate(runtime.handle()).await,
+            Err(MigrateError::ActorNotRunning)
+        );
+
+        let prepared_actor = PreparedActor::<MyActor>::new();
+        let actor_ref = prepared_actor.actor_ref().clone();
+        assert_eq!(
+            actor_ref.migrate(runtime.handle()).await,
+            Err(MigrateError::NotMigratable)
+        );
+        drop(prepared_actor);
+
+        runtime.shutdown_background();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn migrated_actor_is_killed_when_its_runtime_shuts_down(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .build()?;
+
+        let prepared_actor = PreparedActor::new();
+        let actor_ref = prepared_actor.actor_ref().clone();
+        let handle = prepared_actor.spawn(MyActor);
+
+        actor_ref.migrate(runtime.handle()).await?;
+        runtime.shutdown_background();
+
+        let (_, reason) = handle.await?;
+        assert_eq!(reason, ActorStopReason::Killed);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use std::{
+    backtrace::{Backtrace, BacktraceStatus},
+    panic,
+};
+
+use tracing::warn;
+
+use crate::error::set_panic_backtrace;
+
+use super::CURRENT_ACTOR_ID;
+
+/// Installs a panic hook which logs panics occurring within actors using [`tracing`], instead of printing to stderr.
+///
+/// Panics within actors are caught and handled by kameo through [`Actor::on_panic`](crate::Actor::on_panic), however
+/// Rust's default panic hook still prints each panic to stderr, which can flood logs in systems where actors are
+/// frequently restarted. Once installed, panics within an actor are logged as a `WARN` event with the actor's id and
+/// the location of the panic, while all other panics are passed to the previously installed hook.
+///
+/// Actor panics are identified by the actor id which kameo sets as a task-local value while polling each actor's task.
+/// Since the panic hook runs on the panicking thread during the poll, the id is visible to the hook. Panics in tasks or
+/// threads spawned by an actor are not attributed to the actor, and are passed to the previous hook.
+///
+/// The hook also captures a backtrace for each panic within an actor, which is available to
+/// [`on_panic`](crate::Actor::on_panic) through [`PanicError::backtrace`](crate::error::PanicError::backtrace).
+/// Backtraces are only captured if enabled with the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment variables.
+///
+/// This function should be called once, early in the program, after any other panic hooks have been installed.
+///
+/// # Example
+///
+/// ```
+/// kameo::install_panic_hook();
+/// ```
+pub fn install_panic_hook() {
+    let prev_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let Ok(id) = CURRENT_ACTOR_ID.try_with(Clone::clone) else {
+            prev_hook(info);
+            return;
+        };
+
+        let backtrace = Backtrace::capture();
+        if backtrace.status() == BacktraceStatus::Captured {
+            set_panic_backtrace(backtrace);
+        }
+
+        let payload = info
+            .payload()
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| info.payload().downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Box<dyn Any>");
+        match info.location() {
+            Some(location) => warn!(%id, %location, "actor panicked: {payload}"),
+            None => warn!(%id, "actor panicked: {payload}"),
+        }
+    }));
+}
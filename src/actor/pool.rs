@@ -7,10 +7,22 @@
 //!
 //! `ActorPool` must be spawned as an actor, and tasks can be sent to it using the `WorkerMsg` message
 //! for individual workers or the `BroadcastMsg` to send a message to all workers in the pool.
+//! Workers can be replaced without downtime using the `RollingRestart` message.
+//! The pool and all of its workers can be stopped together with the `Shutdown` message.
+//! Related messages can be routed to the same worker by pinning one with the `Pin` message.
+//! Messages can be scattered across the workers, with their replies folded into one result, using `MapReduce`.
+//! Degraded workers can be detected by probing them periodically with [`ActorPool::with_health_check`].
+//! To configure how workers are spawned, such as the capacity of their mailboxes, use [`ActorPool::builder`].
 //!
 //! # Features
 //! - **Load Balancing**: Messages are distributed among a fixed set of actors in a round-robin manner.
 //! - **Resilience**: Workers that stop or fail are automatically replaced to ensure continued operation.
+//! - **Rolling Restarts**: Workers can be gradually replaced with fresh instances from the factory.
+//! - **Graceful Shutdown**: Workers can be stopped together, with the pool waiting for all of them before stopping.
+//! - **Worker Affinity**: A worker can be pinned so that follow-up messages are handled by the same worker.
+//! - **Map-Reduce**: Messages can be spread across workers concurrently, and their replies reduced into one result.
+//! - **Health Checks**: Workers which fail a periodic probe are excluded from routing until they recover.
+//! - **Backpressure**: Workers can be spawned with bounded mailboxes, so a saturated pool slows down its senders.
 //! - **Flexible Actor Management**: The pool can manage any type of actor that implements the [Actor] trait,
 //!   allowing it to be used for various tasks.
 //!
@@ -41,32 +53,37 @@
 //! ```
 
 use std::{
+    collections::HashSet,
     fmt,
-    iter::repeat,
+    iter::repeat_n,
+    mem,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
     },
+    time::Duration,
 };
 
 use futures::{
     future::{join_all, BoxFuture},
-    Future, FutureExt,
+    stream::FuturesUnordered,
+    Future, FutureExt, StreamExt,
 };
+use tokio::time::MissedTickBehavior;
 
 use crate::{
     actor::{Actor, ActorRef},
-    error::{ActorStopReason, BoxError, SendError},
-    mailbox::bounded::BoundedMailbox,
+    error::{ActorStopReason, BoxError, Infallible, SendError},
+    mailbox::{bounded::BoundedMailbox, Mailbox},
     message::{BoxDebug, Context, Message},
-    reply::Reply,
+    reply::{DelegatedReply, Reply, ReplySender},
     request::{
         AskRequest, ForwardMessageSend, LocalAskRequest, LocalTellRequest, MessageSend,
         TellRequest, WithoutRequestTimeout,
     },
 };
 
-use super::{ActorID, WeakActorRef};
+use super::{ActorID, PreparedActor, WeakActorRef};
 
 /// A pool of actor workers designed to distribute tasks among a fixed set of actors.
 ///
@@ -82,6 +99,9 @@ pub struct ActorPool<A: Actor> {
     workers: Vec<(ActorRef<A>, Arc<AtomicUsize>)>,
     size: usize,
     factory: Factory<A>,
+    health_check: Option<HealthCheck<A>>,
+    unhealthy: HashSet<ActorID>,
+    shutdown: Option<PoolShutdown>,
 }
 
 impl<A> ActorPool<A>
@@ -119,6 +139,27 @@ where
             workers,
             size,
             factory: Factory::Sync(Box::new(factory)),
+            health_check: None,
+            unhealthy: HashSet::new(),
+            shutdown: None,
+        }
+    }
+
+    /// Returns a [`PoolBuilder`] for configuring how the pool spawns its workers.
+    ///
+    /// Unlike [`ActorPool::new`], the builder's factory returns the worker's state, and the pool spawns each worker
+    /// itself. This allows the pool to control how workers are spawned, such as spawning them with a bounded
+    /// mailbox of a given capacity.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `size` is set to 0, as an actor pool cannot function without workers.
+    pub fn builder(size: usize) -> PoolBuilder<A> {
+        assert_ne!(size, 0);
+
+        PoolBuilder {
+            size,
+            mailbox: Box::new(A::new_mailbox),
         }
     }
 
@@ -147,17 +188,204 @@ where
                 let mut factory = factory.clone();
                 Box::pin(async move { factory().await })
             })),
+            health_check: None,
+            unhealthy: HashSet::new(),
+            shutdown: None,
         }
     }
 
-    /// Returns a worker with the least amount of load.
+    /// Periodically probes each worker with a health check message once the pool is spawned.
+    ///
+    /// Every `interval`, a message created by `msg_fn` is sent to each worker with [`ask`](ActorRef::ask).
+    /// If the request fails, the reply is an error, or no reply is received within `timeout`, the worker is
+    /// marked as unhealthy and excluded from routing until a later probe succeeds. Workers which stop are
+    /// replaced using the factory as usual, and replacements start out healthy.
+    ///
+    /// If every worker is unhealthy, messages are routed to all workers rather than being rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::Actor;
+    /// use kameo::actor::pool::{ActorPool, Stats};
+    /// # use kameo::message::{Context, Message};
+    ///
+    /// #[derive(Actor)]
+    /// struct MyWorker;
+    ///
+    /// struct Ping;
+    /// #
+    /// # impl Message<Ping> for MyWorker {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, msg: Ping, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+    /// # }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let pool = ActorPool::new(4, || kameo::spawn(MyWorker))
+    ///     .with_health_check(|| Ping, Duration::from_secs(10), Duration::from_secs(1));
+    /// let pool_actor = kameo::spawn(pool);
+    ///
+    /// let stats = pool_actor.ask(Stats).await?;
+    /// assert_eq!(stats.healthy, 4);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn with_health_check<M, F>(
+        mut self,
+        msg_fn: F,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Self
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        F: Fn() -> M + Send + Sync + 'static,
+        for<'a> AskRequest<
+            LocalAskRequest<'a, A, A::Mailbox>,
+            A::Mailbox,
+            M,
+            WithoutRequestTimeout,
+            WithoutRequestTimeout,
+        >: MessageSend<
+            Ok = <A::Reply as Reply>::Ok,
+            Error = SendError<M, <A::Reply as Reply>::Error>,
+        >,
+    {
+        self.health_check = Some(HealthCheck {
+            probe: Arc::new(move |worker| {
+                let msg = msg_fn();
+                async move {
+                    matches!(
+                        tokio::time::timeout(timeout, worker.ask(msg).send()).await,
+                        Ok(Ok(_))
+                    )
+                }
+                .boxed()
+            }),
+            interval,
+        });
+        self
+    }
+
+    /// Returns a worker with the least amount of load, preferring workers which passed their last health check.
     pub fn get_least_loaded_worker(&self) -> ActorRef<A> {
         self.workers
             .iter()
+            .filter(|(worker, _)| self.is_healthy(worker))
             .min_by_key(|(_, load)| load.load(Ordering::Relaxed))
+            .or_else(|| {
+                self.workers
+                    .iter()
+                    .min_by_key(|(_, load)| load.load(Ordering::Relaxed))
+            })
             .map(|(worker, _)| worker.clone())
             .expect("ActorPool should have at least one worker")
     }
+
+    /// Returns the number of healthy and unhealthy workers in the pool.
+    pub fn stats(&self) -> PoolStats {
+        let unhealthy = self
+            .workers
+            .iter()
+            .filter(|(worker, _)| !self.is_healthy(worker))
+            .count();
+
+        PoolStats {
+            workers: self.workers.len(),
+            healthy: self.workers.len() - unhealthy,
+            unhealthy,
+        }
+    }
+
+    fn is_healthy(&self, worker: &ActorRef<A>) -> bool {
+        !self.unhealthy.contains(&worker.id())
+    }
+
+    /// Pins a worker, returning a handle which sends all messages to the same worker.
+    ///
+    /// The worker is selected in the same way as [`WorkerMsg`], skipping any workers which are no longer running.
+    ///
+    /// Since only a spawned pool replaces workers which have stopped, a handle returned by this method keeps
+    /// targeting the same worker even if it stops. To have the handle re-resolve a new worker, pin it through
+    /// the spawned pool with the [`Pin`] message instead.
+    pub fn pin(&self) -> PinnedWorker<A> {
+        let worker = self
+            .workers
+            .iter()
+            .filter(|(worker, _)| worker.is_alive() && self.is_healthy(worker))
+            .min_by_key(|(_, load)| load.load(Ordering::Relaxed))
+            .map(|(worker, _)| worker.clone())
+            .unwrap_or_else(|| self.get_least_loaded_worker());
+
+        PinnedWorker { worker, pool: None }
+    }
+
+    /// Scatters messages across the workers, folding their replies into a single result with `reducer`.
+    ///
+    /// Messages are distributed round-robin across the workers which passed their last health check, and sent
+    /// concurrently. Each successful reply is folded into the accumulator, starting with `init`, in the order the
+    /// replies arrive, which may differ from the order of `msgs`. The reducer should therefore not depend on the
+    /// order of replies, such as when summing counts across shards.
+    ///
+    /// If any message fails, such as when its worker stops or the handler returns an error, the returned future
+    /// fails with that error straight away, and the remaining replies are discarded. Messages which were already
+    /// sent are still handled by their workers. To fold errors as well, return a `Result` from the workers'
+    /// handlers with an infallible error type.
+    ///
+    /// The returned future doesn't borrow the pool, so it can be awaited after the pool has been spawned or moved.
+    /// When the pool is spawned as an actor, use the [`MapReduce`] message instead.
+    pub fn map_reduce<M, T, F>(
+        &self,
+        msgs: impl IntoIterator<Item = M>,
+        init: T,
+        mut reducer: F,
+    ) -> impl Future<Output = Result<T, SendError<M, <A::Reply as Reply>::Error>>> + Send + 'static
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        T: Send + 'static,
+        F: FnMut(T, <A::Reply as Reply>::Ok) -> T + Send + 'static,
+        for<'a> AskRequest<
+            LocalAskRequest<'a, A, A::Mailbox>,
+            A::Mailbox,
+            M,
+            WithoutRequestTimeout,
+            WithoutRequestTimeout,
+        >: MessageSend<
+            Ok = <A::Reply as Reply>::Ok,
+            Error = SendError<M, <A::Reply as Reply>::Error>,
+        >,
+    {
+        let mut workers: Vec<_> = self
+            .workers
+            .iter()
+            .filter(|(worker, _)| self.is_healthy(worker))
+            .map(|(worker, _)| worker.clone())
+            .collect();
+        if workers.is_empty() {
+            workers = self
+                .workers
+                .iter()
+                .map(|(worker, _)| worker.clone())
+                .collect();
+        }
+        let mut replies: FuturesUnordered<_> = msgs
+            .into_iter()
+            .zip(workers.into_iter().cycle())
+            .map(|(msg, worker)| async move { worker.ask(msg).send().await })
+            .collect();
+
+        async move {
+            let mut acc = init;
+            while let Some(reply) = replies.next().await {
+                acc = reducer(acc, reply?);
+            }
+
+            Ok(acc)
+        }
+    }
 }
 
 impl<A> Actor for ActorPool<A>
@@ -175,6 +403,24 @@ where
             worker.link(&actor_ref).await;
         }
 
+        if let Some(HealthCheck { interval, .. }) = self.health_check {
+            let pool = actor_ref.downgrade();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(interval);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    let Some(pool) = pool.upgrade() else {
+                        break;
+                    };
+                    if pool.tell(ProbeWorkers).send().await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
         Ok(())
     }
 
@@ -182,8 +428,22 @@ where
         &mut self,
         actor_ref: WeakActorRef<Self>,
         id: ActorID,
-        _reason: ActorStopReason,
+        reason: ActorStopReason,
     ) -> Result<Option<ActorStopReason>, BoxError> {
+        if let Some(shutdown) = &mut self.shutdown {
+            if shutdown.remaining.remove(&id) {
+                shutdown.stopped.push((id, reason));
+            }
+            if !shutdown.remaining.is_empty() {
+                return Ok(None);
+            }
+            let shutdown = self.shutdown.take().unwrap();
+            for tx in shutdown.reply_senders {
+                tx.send(shutdown.stopped.clone());
+            }
+            return Ok(Some(ActorStopReason::Normal));
+        }
+
         let Some(actor_ref) = actor_ref.upgrade() else {
             return Ok(None);
         };
@@ -196,6 +456,7 @@ where
             return Ok(None);
         };
 
+        self.unhealthy.remove(&id);
         self.workers[i] = match &mut self.factory {
             Factory::Sync(f) => (f(), Arc::new(AtomicUsize::new(0))),
             Factory::Async(f) => (f().await, Arc::new(AtomicUsize::new(0))),
@@ -206,6 +467,87 @@ where
     }
 }
 
+/// A builder for an [`ActorPool`], created with [`ActorPool::builder`].
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::actor::pool::{ActorPool, WorkerMsg};
+/// # use kameo::message::{Context, Message};
+///
+/// #[derive(Actor)]
+/// #[actor(mailbox = bounded)]
+/// struct MyWorker;
+/// #
+/// # impl Message<&'static str> for MyWorker {
+/// #     type Reply = ();
+/// #     async fn handle(&mut self, msg: &'static str, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+/// # }
+///
+/// # tokio_test::block_on(async {
+/// // Each worker queues at most 16 messages, after which sending to the pool waits for capacity
+/// let pool = ActorPool::builder(4).bounded_mailbox(16).build(|| MyWorker);
+/// let pool_actor = kameo::spawn(pool);
+///
+/// pool_actor.tell(WorkerMsg("Hello worker!")).await?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct PoolBuilder<A: Actor> {
+    size: usize,
+    mailbox: MailboxFactory<A>,
+}
+
+impl<A> PoolBuilder<A>
+where
+    A: Actor,
+{
+    /// Sets the function used to create each worker's mailbox.
+    ///
+    /// By default, workers use the mailbox created by [`Actor::new_mailbox`].
+    pub fn mailbox(
+        mut self,
+        mailbox: impl Fn() -> (A::Mailbox, <A::Mailbox as Mailbox<A>>::Receiver) + Send + Sync + 'static,
+    ) -> Self {
+        self.mailbox = Box::new(mailbox);
+        self
+    }
+
+    /// Spawns each worker with a bounded mailbox of the given capacity.
+    ///
+    /// When a worker's mailbox is full, sending it a message through the pool waits for capacity, applying
+    /// backpressure to the senders of the pool rather than queueing messages without limit.
+    pub fn bounded_mailbox(self, capacity: usize) -> Self
+    where
+        A: Actor<Mailbox = BoundedMailbox<A>>,
+    {
+        self.mailbox(move || BoundedMailbox::new(capacity))
+    }
+
+    /// Builds the pool, spawning its workers with state created by `factory`.
+    ///
+    /// The factory is also used to replace workers which stop.
+    pub fn build(self, mut factory: impl FnMut() -> A + Send + Sync + 'static) -> ActorPool<A> {
+        let PoolBuilder { size, mailbox } = self;
+        ActorPool::new(size, move || {
+            let (mailbox, mailbox_rx) = mailbox();
+            let prepared_actor = PreparedActor::new_with_mailbox(mailbox, mailbox_rx);
+            let actor_ref = prepared_actor.actor_ref().clone();
+            prepared_actor.spawn(factory());
+            actor_ref
+        })
+    }
+}
+
+impl<A: Actor> fmt::Debug for PoolBuilder<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoolBuilder")
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
 /// A reply from a worker message.
 #[allow(missing_debug_implementations)]
 pub enum WorkerReply<A, M>
@@ -240,6 +582,10 @@ where
         }
     }
 
+    fn into_boxed_validation_err(err: Self::Error) -> BoxDebug {
+        Box::new(err)
+    }
+
     fn into_value(self) -> Self::Value {
         unimplemented!("a WorkerReply cannot be converted to a value and is only a marker type")
     }
@@ -268,6 +614,10 @@ where
         WorkerMsg(mut msg): WorkerMsg<M>,
         mut ctx: Context<'_, Self, Self::Reply>,
     ) -> Self::Reply {
+        if self.shutdown.is_some() {
+            return WorkerReply::Err(SendError::Closed(msg));
+        }
+
         let (_, mut reply_sender) = ctx.reply_sender();
         for _ in 0..self.workers.len() {
             let worker = self.get_least_loaded_worker();
@@ -307,10 +657,156 @@ where
     }
 }
 
+/// A message to scatter messages across the workers of an actor pool, folding their replies into a single result.
+///
+/// The replies are folded in the background, so the pool keeps routing other messages in the meantime.
+/// See [`ActorPool::map_reduce`] for how messages are distributed, and how errors are handled.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::actor::pool::{ActorPool, MapReduce};
+/// # use kameo::message::{Context, Message};
+///
+/// #[derive(Actor)]
+/// struct Shard;
+///
+/// struct CountWords(&'static str);
+/// #
+/// # impl Message<CountWords> for Shard {
+/// #     type Reply = usize;
+/// #     async fn handle(&mut self, msg: CountWords, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+/// #         msg.0.split_whitespace().count()
+/// #     }
+/// # }
+///
+/// # tokio_test::block_on(async {
+/// let pool_actor = kameo::spawn(ActorPool::new(4, || kameo::spawn(Shard)));
+///
+/// let docs = ["hello world", "the quick brown fox", "kameo"];
+/// let total = pool_actor
+///     .ask(MapReduce::new(docs.map(CountWords), 0, |total, count| total + count))
+///     .await?;
+/// assert_eq!(total, 7);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct MapReduce<M, T, F> {
+    msgs: Vec<M>,
+    init: T,
+    reducer: F,
+}
+
+impl<M, T, F> MapReduce<M, T, F> {
+    /// Creates a new map-reduce, folding the reply to each of `msgs` into `init` with `reducer`.
+    pub fn new(msgs: impl IntoIterator<Item = M>, init: T, reducer: F) -> Self {
+        MapReduce {
+            msgs: msgs.into_iter().collect(),
+            init,
+            reducer,
+        }
+    }
+}
+
+impl<M, T, F> fmt::Debug for MapReduce<M, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapReduce")
+            .field("msgs", &self.msgs.len())
+            .finish()
+    }
+}
+
+impl<A, M, T, F> Message<MapReduce<M, T, F>> for ActorPool<A>
+where
+    A: Actor + Message<M>,
+    M: Send + Sync + 'static,
+    T: Send + 'static,
+    F: FnMut(T, <A::Reply as Reply>::Ok) -> T + Send + 'static,
+    <A::Reply as Reply>::Error: fmt::Debug + Sync,
+    for<'a> AskRequest<
+        LocalAskRequest<'a, A, A::Mailbox>,
+        A::Mailbox,
+        M,
+        WithoutRequestTimeout,
+        WithoutRequestTimeout,
+    >: MessageSend<Ok = <A::Reply as Reply>::Ok, Error = SendError<M, <A::Reply as Reply>::Error>>,
+{
+    type Reply = DelegatedReply<Result<T, SendError<M, <A::Reply as Reply>::Error>>>;
+
+    async fn handle(
+        &mut self,
+        MapReduce {
+            msgs,
+            init,
+            reducer,
+        }: MapReduce<M, T, F>,
+        mut ctx: Context<'_, Self, Self::Reply>,
+    ) -> Self::Reply {
+        let reduced = self.map_reduce(msgs, init, reducer);
+        let (delegated_reply, reply_sender) = ctx.reply_sender();
+        tokio::spawn(async move {
+            let res = reduced.await;
+            if let Some(tx) = reply_sender {
+                tx.send(res);
+            }
+        });
+
+        delegated_reply
+    }
+}
+
 /// A message broadcasted to all workers in an actor pool.
+///
+/// The message is told to each worker, and the reply is the result of sending it to each worker, in the order of the
+/// workers in the pool. The message is cloned for each worker, so for large messages, use
+/// [`BroadcastMsg::shared`] to share a single copy between the workers instead.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct BroadcastMsg<M>(pub M);
 
+impl<M> BroadcastMsg<Arc<M>> {
+    /// Creates a broadcast which shares the message between workers, rather than cloning it for each worker.
+    ///
+    /// Each worker receives a clone of the `Arc`, and handles the message by implementing `Message<Arc<M>>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use kameo::actor::pool::{ActorPool, BroadcastMsg};
+    /// use kameo::message::{Context, Message};
+    ///
+    /// struct Config {
+    ///     rules: Vec<String>,
+    /// }
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct MyWorker {
+    ///     config: Option<Arc<Config>>,
+    /// }
+    ///
+    /// impl Message<Arc<Config>> for MyWorker {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, config: Arc<Config>, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.config = Some(config);
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let pool_actor = kameo::spawn(ActorPool::new(4, || kameo::spawn(MyWorker { config: None })));
+    ///
+    /// let config = Config { rules: vec!["allow all".to_string()] };
+    /// pool_actor.ask(BroadcastMsg::shared(config)).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn shared(msg: M) -> Self {
+        BroadcastMsg(Arc::new(msg))
+    }
+}
+
 impl<A, M> Message<BroadcastMsg<M>> for ActorPool<A>
 where
     A: Actor + Message<M>,
@@ -330,7 +826,7 @@ where
             self.workers
                 .iter()
                 .zip(
-                    repeat(msg).take(self.workers.len()), // Avoids unnecessary clone of msg on last iteration
+                    repeat_n(msg, self.workers.len()), // Avoids unnecessary clone of msg on last iteration
                 )
                 .map(|((worker, _), msg)| worker.tell(msg).send()),
         )
@@ -338,16 +834,886 @@ where
     }
 }
 
+/// A message to pin a worker in an actor pool, returning a [`PinnedWorker`].
+///
+/// All messages sent through the pinned worker are handled by the same worker, which is useful for multi-step
+/// interactions which depend on state held by the worker. If the pinned worker stops, the handle re-resolves
+/// a new worker from the pool the next time a message is sent.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::actor::pool::{ActorPool, Pin};
+/// # use kameo::message::{Context, Message};
+///
+/// #[derive(Actor)]
+/// struct MyWorker;
+/// #
+/// # impl Message<&'static str> for MyWorker {
+/// #     type Reply = ();
+/// #     async fn handle(&mut self, msg: &'static str, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+/// # }
+///
+/// # tokio_test::block_on(async {
+/// let pool_actor = kameo::spawn(ActorPool::new(4, || kameo::spawn(MyWorker)));
+///
+/// let mut worker = pool_actor.ask(Pin).await?;
+/// worker.ask("begin").await?;
+/// worker.ask("commit").await?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pin;
+
+impl<A> Message<Pin> for ActorPool<A>
+where
+    A: Actor,
+{
+    type Reply = PinnedWorker<A>;
+
+    async fn handle(&mut self, _msg: Pin, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+        let mut pinned = self.pin();
+        pinned.pool = Some(ctx.actor_ref().downgrade());
+        pinned
+    }
+}
+
+/// A handle to a worker in an actor pool, created with [`ActorPool::pin`] or the [`Pin`] message.
+///
+/// Messages sent with [`PinnedWorker::ask`] and [`PinnedWorker::tell`] always target the pinned worker.
+/// If the handle was created with the [`Pin`] message and the worker has stopped, a new worker is pinned
+/// from the pool before the message is sent.
+pub struct PinnedWorker<A: Actor> {
+    worker: ActorRef<A>,
+    pool: Option<WeakActorRef<ActorPool<A>>>,
+}
+
+impl<A> PinnedWorker<A>
+where
+    A: Actor,
+{
+    /// Returns the currently pinned worker.
+    pub fn worker(&self) -> &ActorRef<A> {
+        &self.worker
+    }
+
+    /// Sends a message to the pinned worker, waiting for a reply.
+    pub async fn ask<M>(
+        &mut self,
+        msg: M,
+    ) -> Result<<A::Reply as Reply>::Ok, SendError<M, <A::Reply as Reply>::Error>>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        for<'a> AskRequest<
+            LocalAskRequest<'a, A, A::Mailbox>,
+            A::Mailbox,
+            M,
+            WithoutRequestTimeout,
+            WithoutRequestTimeout,
+        >: MessageSend<
+            Ok = <A::Reply as Reply>::Ok,
+            Error = SendError<M, <A::Reply as Reply>::Error>,
+        >,
+    {
+        if !self.worker.is_alive() {
+            self.repin().await;
+        }
+        let msg = match self.worker.ask(msg).send().await {
+            Err(SendError::ActorNotRunning(msg)) => msg,
+            res => return res,
+        };
+        if !self.repin().await {
+            return Err(SendError::ActorNotRunning(msg));
+        }
+        self.worker.ask(msg).send().await
+    }
+
+    /// Sends a message to the pinned worker without waiting for a reply.
+    pub async fn tell<M>(&mut self, msg: M) -> Result<(), SendError<M, <A::Reply as Reply>::Error>>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+            MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+    {
+        if !self.worker.is_alive() {
+            self.repin().await;
+        }
+        let msg = match self.worker.tell(msg).send().await {
+            Err(SendError::ActorNotRunning(msg)) => msg,
+            res => return res,
+        };
+        if !self.repin().await {
+            return Err(SendError::ActorNotRunning(msg));
+        }
+        self.worker.tell(msg).send().await
+    }
+
+    /// Pins a new worker from the pool, returning `false` if the pool is no longer running.
+    async fn repin(&mut self) -> bool {
+        let Some(pool) = self.pool.as_ref().and_then(WeakActorRef::upgrade) else {
+            return false;
+        };
+        match pool.ask(Pin).send().await {
+            Ok(pinned) => {
+                self.worker = pinned.worker;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+impl<A: Actor> Reply for PinnedWorker<A> {
+    type Ok = Self;
+    type Error = Infallible;
+    type Value = Self;
+
+    const CAN_ERROR: bool = false;
+
+    fn to_result(self) -> Result<Self, Infallible> {
+        Ok(self)
+    }
+
+    fn into_boxed_err(self) -> Option<BoxDebug> {
+        None
+    }
+
+    fn into_value(self) -> Self::Value {
+        self
+    }
+}
+
+impl<A: Actor> Clone for PinnedWorker<A> {
+    fn clone(&self) -> Self {
+        PinnedWorker {
+            worker: self.worker.clone(),
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for PinnedWorker<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PinnedWorker")
+            .field("worker", &self.worker)
+            .finish()
+    }
+}
+
+/// A message to perform a rolling restart of all workers in an actor pool.
+///
+/// Workers are replaced in batches of `concurrency`. For each worker in a batch, a new worker is created
+/// with the pool's factory and takes its place before the old worker is stopped gracefully, so the
+/// pool never has fewer than its configured number of workers available. Messages already queued on
+/// an old worker are processed before it stops, and the restart completes once all old workers have stopped.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::actor::pool::{ActorPool, RollingRestart};
+///
+/// #[derive(Actor)]
+/// struct MyWorker;
+///
+/// # tokio_test::block_on(async {
+/// let pool_actor = kameo::spawn(ActorPool::new(4, || kameo::spawn(MyWorker)));
+///
+/// // Replace two workers at a time
+/// pool_actor.ask(RollingRestart { concurrency: 2 }).await?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RollingRestart {
+    /// The number of workers to replace at once. A value of `0` is treated as `1`.
+    pub concurrency: usize,
+}
+
+impl Default for RollingRestart {
+    fn default() -> Self {
+        RollingRestart { concurrency: 1 }
+    }
+}
+
+impl<A> Message<RollingRestart> for ActorPool<A>
+where
+    A: Actor,
+{
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        RollingRestart { concurrency }: RollingRestart,
+        ctx: Context<'_, Self, Self::Reply>,
+    ) -> Self::Reply {
+        let actor_ref = ctx.actor_ref();
+        let indexes: Vec<_> = (0..self.workers.len()).collect();
+        for batch in indexes.chunks(concurrency.max(1)) {
+            let mut old_workers = Vec::with_capacity(batch.len());
+            for &i in batch {
+                let new_worker = match &mut self.factory {
+                    Factory::Sync(f) => f(),
+                    Factory::Async(f) => f().await,
+                };
+                new_worker.link(&actor_ref).await;
+                let (old_worker, _) = mem::replace(
+                    &mut self.workers[i],
+                    (new_worker, Arc::new(AtomicUsize::new(0))),
+                );
+                old_worker.unlink(&actor_ref).await;
+                self.unhealthy.remove(&old_worker.id());
+                old_workers.push(old_worker);
+            }
+
+            join_all(old_workers.iter().map(|worker| async move {
+                let _ = worker.stop_gracefully().await;
+                worker.wait_for_stop().await;
+            }))
+            .await;
+        }
+    }
+}
+
+/// A message to stop every worker in an actor pool, and then the pool itself.
+///
+/// Once the pool handles this message, it's closed, so any message sent to it afterwards fails with
+/// [`SendError::Closed`], as does any [`WorkerMsg`] which was already queued behind the shutdown. Stopped workers
+/// are no longer replaced, and the pool's health check stops probing them.
+///
+/// By default, each worker is stopped gracefully, handling the messages already in its mailbox before stopping. If
+/// `kill` is set, the workers are killed immediately instead, dropping their queued messages. The reply is sent once
+/// every worker has stopped, and contains the reason each worker stopped. The pool then stops as well.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::actor::pool::{ActorPool, Shutdown};
+///
+/// #[derive(Actor)]
+/// struct MyWorker;
+///
+/// # tokio_test::block_on(async {
+/// let pool_actor = kameo::spawn(ActorPool::new(4, || kameo::spawn(MyWorker)));
+///
+/// let stopped = pool_actor.ask(Shutdown::default()).await?;
+/// assert_eq!(stopped.len(), 4);
+/// pool_actor.wait_for_stop().await;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Shutdown {
+    /// Whether to kill the workers immediately, rather than letting them drain their mailboxes.
+    pub kill: bool,
+}
+
+impl<A> Message<Shutdown> for ActorPool<A>
+where
+    A: Actor,
+{
+    type Reply = DelegatedReply<Vec<(ActorID, ActorStopReason)>>;
+
+    async fn handle(
+        &mut self,
+        Shutdown { kill }: Shutdown,
+        mut ctx: Context<'_, Self, Self::Reply>,
+    ) -> Self::Reply {
+        let (delegated_reply, reply_sender) = ctx.reply_sender();
+        // Reject new messages, while still receiving the link notifications of stopping workers
+        ctx.actor_ref().info.closed.store(true, Ordering::Release);
+
+        if let Some(shutdown) = &mut self.shutdown {
+            shutdown.reply_senders.extend(reply_sender);
+            return delegated_reply;
+        }
+
+        self.shutdown = Some(PoolShutdown {
+            remaining: self.workers.iter().map(|(worker, _)| worker.id()).collect(),
+            stopped: Vec::with_capacity(self.workers.len()),
+            reply_senders: reply_sender.into_iter().collect(),
+        });
+        for (worker, _) in &self.workers {
+            if kill {
+                worker.kill();
+            } else {
+                let _ = worker.stop_gracefully().await;
+            }
+        }
+
+        delegated_reply
+    }
+}
+
+struct PoolShutdown {
+    /// The workers which haven't stopped yet.
+    remaining: HashSet<ActorID>,
+    stopped: Vec<(ActorID, ActorStopReason)>,
+    reply_senders: Vec<ReplySender<Vec<(ActorID, ActorStopReason)>>>,
+}
+
 impl<A: Actor> fmt::Debug for ActorPool<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ActorPool")
             .field("workers", &self.workers)
             .field("size", &self.size)
+            .field("unhealthy", &self.unhealthy)
+            .field("shutting_down", &self.shutdown.is_some())
             .finish()
     }
 }
 
+type MailboxFactory<A> = Box<
+    dyn Fn() -> (
+            <A as Actor>::Mailbox,
+            <<A as Actor>::Mailbox as Mailbox<A>>::Receiver,
+        ) + Send
+        + Sync
+        + 'static,
+>;
+
 enum Factory<A: Actor> {
     Sync(Box<dyn FnMut() -> ActorRef<A> + Send + Sync + 'static>),
     Async(Box<dyn FnMut() -> BoxFuture<'static, ActorRef<A>> + Send + Sync + 'static>),
 }
+
+/// A message to get the [`PoolStats`] of an actor pool.
+///
+/// See [`ActorPool::with_health_check`] for an example.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Stats;
+
+impl<A> Message<Stats> for ActorPool<A>
+where
+    A: Actor,
+{
+    type Reply = PoolStats;
+
+    async fn handle(&mut self, _msg: Stats, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+        self.stats()
+    }
+}
+
+/// Statistics about the workers in an actor pool, returned by [`ActorPool::stats`] and the [`Stats`] message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolStats {
+    /// The number of workers in the pool.
+    pub workers: usize,
+    /// The number of workers which are routed to.
+    ///
+    /// Without a health check, this is always the number of workers.
+    pub healthy: usize,
+    /// The number of workers which failed their last health check.
+    pub unhealthy: usize,
+}
+
+impl Reply for PoolStats {
+    type Ok = Self;
+    type Error = Infallible;
+    type Value = Self;
+
+    const CAN_ERROR: bool = false;
+
+    fn to_result(self) -> Result<Self, Infallible> {
+        Ok(self)
+    }
+
+    fn into_boxed_err(self) -> Option<BoxDebug> {
+        None
+    }
+
+    fn into_value(self) -> Self::Value {
+        self
+    }
+}
+
+struct HealthCheck<A: Actor> {
+    probe: Arc<dyn Fn(ActorRef<A>) -> BoxFuture<'static, bool> + Send + Sync>,
+    interval: Duration,
+}
+
+/// Sent to the pool on each health check interval to probe all workers.
+struct ProbeWorkers;
+
+impl<A> Message<ProbeWorkers> for ActorPool<A>
+where
+    A: Actor,
+{
+    type Reply = ();
+
+    async fn handle(&mut self, _msg: ProbeWorkers, ctx: Context<'_, Self, Self::Reply>) {
+        let Some(health_check) = &self.health_check else {
+            return;
+        };
+        let probes: Vec<_> = self
+            .workers
+            .iter()
+            .map(|(worker, _)| {
+                let id = worker.id();
+                let probe = (health_check.probe)(worker.clone());
+                async move { (id, probe.await) }
+            })
+            .collect();
+
+        // Probes run in the background so that the pool keeps routing messages while waiting for replies
+        let pool = ctx.actor_ref().downgrade();
+        tokio::spawn(async move {
+            let results = join_all(probes).await;
+            if let Some(pool) = pool.upgrade() {
+                let _ = pool.tell(ProbeResults(results)).send().await;
+            }
+        });
+    }
+}
+
+/// The result of probing each worker, as `(id, healthy)` pairs.
+struct ProbeResults(Vec<(ActorID, bool)>);
+
+impl<A> Message<ProbeResults> for ActorPool<A>
+where
+    A: Actor,
+{
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        ProbeResults(results): ProbeResults,
+        _ctx: Context<'_, Self, Self::Reply>,
+    ) {
+        for (id, healthy) in results {
+            if healthy {
+                self.unhealthy.remove(&id);
+            } else if self.workers.iter().any(|(worker, _)| worker.id() == id) {
+                // Workers which were replaced while being probed are no longer tracked
+                self.unhealthy.insert(id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    use crate::{
+        actor::Actor,
+        error::{ActorStopReason, SendError},
+        mailbox::{bounded::BoundedMailbox, unbounded::UnboundedMailbox},
+        message::{Context, Message},
+        request::MessageSend,
+        spawn,
+    };
+
+    use super::{
+        ActorPool, BroadcastMsg, MapReduce, Pin, RollingRestart, Shutdown, Stats, WorkerMsg,
+    };
+
+    #[tokio::test]
+    async fn pool_pins_follow_up_messages_to_worker() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyWorker;
+
+        impl Actor for MyWorker {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct WorkerId;
+
+        impl Message<WorkerId> for MyWorker {
+            type Reply = u64;
+
+            async fn handle(
+                &mut self,
+                _msg: WorkerId,
+                ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                ctx.actor_ref().id().sequence_id()
+            }
+        }
+
+        let pool = spawn(ActorPool::new(2, || spawn(MyWorker)));
+        let mut pinned = pool.ask(Pin).send().await?;
+        let first = pinned.ask(WorkerId).await?;
+        assert_eq!(pinned.ask(WorkerId).await?, first);
+        assert_eq!(pinned.worker().id().sequence_id(), first);
+
+        pinned.worker().kill();
+        pinned.worker().wait_for_stop().await;
+        let second = pinned.ask(WorkerId).await?;
+        assert_ne!(second, first);
+        assert_eq!(pinned.ask(WorkerId).await?, second);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pool_health_check_excludes_unhealthy_workers() -> Result<(), Box<dyn std::error::Error>>
+    {
+        static UNHEALTHY_WORKER: AtomicU64 = AtomicU64::new(0);
+        static RECOVERED: AtomicBool = AtomicBool::new(false);
+
+        struct MyWorker;
+
+        impl Actor for MyWorker {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Ping;
+
+        impl Message<Ping> for MyWorker {
+            type Reply = Result<(), &'static str>;
+
+            async fn handle(
+                &mut self,
+                _msg: Ping,
+                ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                if ctx.actor_ref().id().sequence_id() == UNHEALTHY_WORKER.load(Ordering::SeqCst)
+                    && !RECOVERED.load(Ordering::SeqCst)
+                {
+                    return Err("degraded");
+                }
+                Ok(())
+            }
+        }
+
+        struct WorkerId;
+
+        impl Message<WorkerId> for MyWorker {
+            type Reply = u64;
+
+            async fn handle(
+                &mut self,
+                _msg: WorkerId,
+                ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                ctx.actor_ref().id().sequence_id()
+            }
+        }
+
+        let pool = ActorPool::new(2, || spawn(MyWorker)).with_health_check(
+            || Ping,
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        );
+        let unhealthy = pool.get_least_loaded_worker().id().sequence_id();
+        UNHEALTHY_WORKER.store(unhealthy, Ordering::SeqCst);
+        let pool = spawn(pool);
+        assert_eq!(pool.ask(Stats).send().await?.healthy, 2);
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let stats = pool.ask(Stats).send().await?;
+        assert_eq!((stats.healthy, stats.unhealthy), (1, 1));
+        for _ in 0..3 {
+            assert_ne!(pool.ask(WorkerMsg(WorkerId)).send().await?, unhealthy);
+        }
+
+        RECOVERED.store(true, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(pool.ask(Stats).send().await?.healthy, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pool_builder_configures_worker_mailboxes() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyWorker;
+
+        impl Actor for MyWorker {
+            type Mailbox = BoundedMailbox<Self>;
+        }
+
+        struct Double(u32);
+
+        impl Message<Double> for MyWorker {
+            type Reply = u32;
+
+            async fn handle(
+                &mut self,
+                Double(n): Double,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                n * 2
+            }
+        }
+
+        let pool = ActorPool::builder(2).bounded_mailbox(4).build(|| MyWorker);
+        for _ in 0..2 {
+            assert_eq!(pool.get_least_loaded_worker().mailbox().0.max_capacity(), 4);
+        }
+
+        let pool_ref = spawn(pool);
+        for i in 0..8 {
+            let reply = pool_ref.ask(WorkerMsg(Double(i))).send().await?;
+            assert_eq!(reply, i * 2);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pool_map_reduce_folds_worker_replies() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Square(u32);
+
+        impl Message<Square> for MyActor {
+            type Reply = Result<u32, &'static str>;
+
+            async fn handle(
+                &mut self,
+                Square(n): Square,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                if n == 0 {
+                    return Err("zero");
+                }
+                Ok(n * n)
+            }
+        }
+
+        let pool = ActorPool::new(3, || spawn(MyActor));
+        assert_eq!(
+            pool.map_reduce((1..=4).map(Square), 0, |sum, n| sum + n)
+                .await?,
+            30
+        );
+
+        let pool_ref = spawn(pool);
+        let sum = pool_ref
+            .ask(MapReduce::new((1..=10).map(Square), 0, |sum, n| sum + n))
+            .await?;
+        assert_eq!(sum, 385);
+
+        // Empty map-reduces reply with the initial value
+        let sum = pool_ref
+            .ask(MapReduce::new(Vec::<Square>::new(), 7, |sum, n| sum + n))
+            .await?;
+        assert_eq!(sum, 7);
+
+        // The first error is returned
+        let res = pool_ref
+            .ask(MapReduce::new([Square(1), Square(0)], 0, |sum, n| sum + n))
+            .await;
+        assert!(matches!(
+            res,
+            Err(SendError::HandlerError(SendError::HandlerError("zero")))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pool_rolling_restart_replaces_every_worker() -> Result<(), Box<dyn std::error::Error>>
+    {
+        struct MyWorker {
+            handled: Arc<AtomicUsize>,
+        }
+
+        impl Actor for MyWorker {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Work;
+
+        impl Message<Work> for MyWorker {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Work, _ctx: Context<'_, Self, Self::Reply>) {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                self.handled.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let handled = Arc::new(AtomicUsize::new(0));
+        let spawned = Arc::new(Mutex::new(Vec::new()));
+        let pool = spawn(ActorPool::new(3, {
+            let handled = handled.clone();
+            let spawned = spawned.clone();
+            move || {
+                let worker = spawn(MyWorker {
+                    handled: handled.clone(),
+                });
+                spawned.lock().unwrap().push(worker.clone());
+                worker
+            }
+        }));
+        for _ in 0..9 {
+            pool.tell(WorkerMsg(Work)).send().await?;
+        }
+
+        // Old workers drain their mailboxes before stopping
+        pool.ask(RollingRestart { concurrency: 2 }).send().await?;
+        assert_eq!(handled.load(Ordering::Relaxed), 9);
+        let workers = spawned.lock().unwrap().clone();
+        assert_eq!(workers.len(), 6);
+        assert!(workers[..3].iter().all(|worker| !worker.is_alive()));
+        assert!(workers[3..].iter().all(|worker| worker.is_alive()));
+        assert_eq!(pool.ask(Stats).send().await?.workers, 3);
+
+        // The replacements are supervised by the pool
+        workers[3].kill();
+        workers[3].wait_for_stop().await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(spawned.lock().unwrap().len(), 7);
+        pool.ask(WorkerMsg(Work)).send().await?;
+        assert_eq!(handled.load(Ordering::Relaxed), 10);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pool_shutdown_stops_every_worker() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyWorker {
+            handled: Arc<AtomicUsize>,
+        }
+
+        impl Actor for MyWorker {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Work;
+
+        impl Message<Work> for MyWorker {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Work, _ctx: Context<'_, Self, Self::Reply>) {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                self.handled.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        // Queued messages are drained before the workers stop
+        let handled = Arc::new(AtomicUsize::new(0));
+        let pool = spawn(ActorPool::new(3, {
+            let handled = handled.clone();
+            move || {
+                spawn(MyWorker {
+                    handled: handled.clone(),
+                })
+            }
+        }));
+        for _ in 0..9 {
+            pool.tell(WorkerMsg(Work)).send().await?;
+        }
+        let stopped = pool.ask(Shutdown::default()).send().await?;
+        assert_eq!(handled.load(Ordering::Relaxed), 9);
+        assert_eq!(stopped.len(), 3);
+        assert!(stopped
+            .iter()
+            .all(|(_, reason)| *reason == ActorStopReason::Normal));
+        assert!(matches!(
+            pool.tell(WorkerMsg(Work)).send().await,
+            Err(SendError::Closed(WorkerMsg(Work)))
+        ));
+        pool.wait_for_stop().await;
+
+        // Killed workers drop their queued messages
+        let handled = Arc::new(AtomicUsize::new(0));
+        let pool = spawn(ActorPool::new(2, {
+            let handled = handled.clone();
+            move || {
+                spawn(MyWorker {
+                    handled: handled.clone(),
+                })
+            }
+        }));
+        for _ in 0..20 {
+            pool.tell(WorkerMsg(Work)).send().await?;
+        }
+        let stopped = pool.ask(Shutdown { kill: true }).send().await?;
+        assert!(handled.load(Ordering::Relaxed) < 20);
+        assert_eq!(stopped.len(), 2);
+        assert!(stopped
+            .iter()
+            .all(|(_, reason)| *reason == ActorStopReason::Killed));
+        pool.wait_for_stop().await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pool_broadcasts_shared_messages() -> Result<(), Box<dyn std::error::Error>> {
+        struct Config(Vec<u8>);
+
+        #[derive(Default)]
+        struct MyActor {
+            config: Option<Arc<Config>>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        impl Message<Arc<Config>> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                config: Arc<Config>,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.config = Some(config);
+            }
+        }
+
+        struct GetConfig;
+
+        impl Message<GetConfig> for MyActor {
+            type Reply = Option<Arc<Config>>;
+
+            async fn handle(
+                &mut self,
+                _: GetConfig,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.config.clone()
+            }
+        }
+
+        let workers = Arc::new(Mutex::new(Vec::new()));
+        let pool = spawn(ActorPool::new(2, {
+            let workers = workers.clone();
+            move || {
+                let worker = spawn(MyActor::default());
+                workers.lock().unwrap().push(worker.clone());
+                worker
+            }
+        }));
+        let results = pool
+            .ask(BroadcastMsg::shared(Config(vec![1; 1024])))
+            .await?;
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(Result::is_ok));
+
+        // Every worker receives the same allocation
+        let workers = workers.lock().unwrap().clone();
+        let first = workers[0]
+            .ask(GetConfig)
+            .await?
+            .expect("config was broadcast");
+        let second = workers[1]
+            .ask(GetConfig)
+            .await?
+            .expect("config was broadcast");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.0, [1; 1024]);
+
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@
 //! - **Message Broadcasting**: Messages published to the `PubSub` actor are sent to all subscribed actors.
 //! - **Subscriber Management**: Actors can subscribe and unsubscribe dynamically, allowing flexible message routing.
 //! - **Message Filtering**: Messages can be filtered out with a filter-function to allow topic subscription or conditional sending.
+//! - **Topics**: [`TopicPubSub`] groups subscribers by a topic key, publishing messages only to subscribers of a given topic.
 //!
 //! # Example
 //!
@@ -45,7 +46,7 @@
 //! # });
 //! ```
 
-use std::collections::HashMap;
+use std::{collections::HashMap, hash::Hash};
 
 use futures::future::{join_all, BoxFuture};
 
@@ -104,6 +105,8 @@ impl<M> PubSub<M> {
     ///
     /// # Requirements
     /// The message type `M` must implement `Clone` and `Send`, since it needs to be duplicated for each subscriber.
+    /// For large messages, use a `PubSub<Arc<M>>`, so each subscriber receives a clone of the `Arc` rather than a
+    /// copy of the message.
     pub async fn publish(&mut self, msg: M)
     where
         M: Clone + Send + 'static,
@@ -120,12 +123,18 @@ impl<M> PubSub<M> {
         for (id, result) in results.into_iter() {
             match result {
                 Ok(_) => {}
-                Err(SendError::ActorNotRunning(_)) | Err(SendError::ActorStopped) => {
+                Err(SendError::ActorNotRunning(_))
+                | Err(SendError::ActorStopped)
+                | Err(SendError::ActorDied(_))
+                | Err(SendError::Closed(_)) => {
                     self.subscribers.remove(&id);
                 }
                 Err(SendError::MailboxFull(_))
+                | Err(SendError::TooLarge(_))
+                | Err(SendError::CircuitOpen(_))
                 | Err(SendError::HandlerError(_))
-                | Err(SendError::Timeout(_)) => {}
+                | Err(SendError::Timeout(_))
+                | Err(SendError::NoReply) => {}
             }
         }
     }
@@ -218,6 +227,26 @@ impl<M> PubSub<M> {
         self.subscribers
             .insert(actor_ref.id(), (Box::new(actor_ref), Box::new(filter)));
     }
+
+    /// Unsubscribes an actor, returning `true` if it was subscribed.
+    ///
+    /// Actors which have stopped are unsubscribed automatically when a message is next published.
+    #[inline]
+    pub fn unsubscribe(&mut self, id: ActorID) -> bool {
+        self.subscribers.remove(&id).is_some()
+    }
+
+    /// Returns the number of subscribed actors.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    /// Returns `true` if there are no subscribed actors.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.subscribers.is_empty()
+    }
 }
 
 impl<M: 'static> Actor for PubSub<M> {
@@ -304,6 +333,197 @@ where
     }
 }
 
+/// A message used to unsubscribe an actor from a `PubSub` actor.
+///
+/// Replies with `true` if the actor was subscribed.
+#[derive(Clone, Copy, Debug)]
+pub struct Unsubscribe(pub ActorID);
+
+impl<M: 'static> Message<Unsubscribe> for PubSub<M> {
+    type Reply = bool;
+
+    async fn handle(
+        &mut self,
+        Unsubscribe(id): Unsubscribe,
+        _ctx: Context<'_, Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.unsubscribe(id)
+    }
+}
+
+/// A publish-subscribe actor where subscriptions are grouped by topic.
+///
+/// Each topic behaves like its own [`PubSub`], with messages published to a topic only being sent to actors
+/// subscribed to that topic. Topics are created when the first actor subscribes, and removed once they have
+/// no subscribers left.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::actor::pubsub::{PublishTopic, SubscribeTopic, TopicPubSub};
+/// # use kameo::message::{Context, Message};
+///
+/// #[derive(Actor)]
+/// struct MyActor;
+/// #
+/// # impl Message<&'static str> for MyActor {
+/// #     type Reply = ();
+/// #     async fn handle(&mut self, msg: &'static str, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+/// # }
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::spawn(MyActor);
+/// let pubsub_actor_ref = kameo::spawn(TopicPubSub::new());
+/// pubsub_actor_ref.tell(SubscribeTopic("news", actor_ref)).await?;
+/// pubsub_actor_ref.tell(PublishTopic("news", "Hello, subscribers!")).await?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct TopicPubSub<T, M> {
+    topics: HashMap<T, PubSub<M>>,
+}
+
+impl<T, M> TopicPubSub<T, M>
+where
+    T: Eq + Hash,
+{
+    /// Creates a new topic pubsub instance with no topics.
+    pub fn new() -> Self {
+        TopicPubSub {
+            topics: HashMap::new(),
+        }
+    }
+
+    /// Publishes a message to all actors subscribed to the topic.
+    ///
+    /// See [`PubSub::publish`].
+    pub async fn publish(&mut self, topic: &T, msg: M)
+    where
+        M: Clone + Send + 'static,
+    {
+        if let Some(pubsub) = self.topics.get_mut(topic) {
+            pubsub.publish(msg).await;
+            if pubsub.is_empty() {
+                self.topics.remove(topic);
+            }
+        }
+    }
+
+    /// Subscribes an actor to receive all messages published to the topic.
+    ///
+    /// See [`PubSub::subscribe`].
+    pub fn subscribe<A>(&mut self, topic: T, actor_ref: ActorRef<A>)
+    where
+        A: Actor + Message<M>,
+        M: Send + 'static,
+        for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+            MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+    {
+        self.topics.entry(topic).or_default().subscribe(actor_ref);
+    }
+
+    /// Unsubscribes an actor from the topic, returning `true` if it was subscribed.
+    pub fn unsubscribe(&mut self, topic: &T, id: ActorID) -> bool {
+        let Some(pubsub) = self.topics.get_mut(topic) else {
+            return false;
+        };
+        let unsubscribed = pubsub.unsubscribe(id);
+        if pubsub.is_empty() {
+            self.topics.remove(topic);
+        }
+        unsubscribed
+    }
+
+    /// Returns an iterator over the topics which have subscribers.
+    pub fn topics(&self) -> impl Iterator<Item = &T> {
+        self.topics.keys()
+    }
+}
+
+impl<T, M> Actor for TopicPubSub<T, M>
+where
+    T: Send + 'static,
+    M: 'static,
+{
+    type Mailbox = BoundedMailbox<Self>;
+}
+
+impl<T, M> Default for TopicPubSub<T, M>
+where
+    T: Eq + Hash,
+{
+    fn default() -> Self {
+        TopicPubSub::new()
+    }
+}
+
+/// A message used to publish data to a topic of a `TopicPubSub` actor.
+#[derive(Clone, Debug)]
+pub struct PublishTopic<T, M>(pub T, pub M);
+
+impl<T, M> Message<PublishTopic<T, M>> for TopicPubSub<T, M>
+where
+    T: Eq + Hash + Send + Sync + 'static,
+    M: Clone + Send + 'static,
+{
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        PublishTopic(topic, msg): PublishTopic<T, M>,
+        _ctx: Context<'_, Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.publish(&topic, msg).await
+    }
+}
+
+/// A message used to subscribe an actor to a topic of a `TopicPubSub` actor.
+#[derive(Clone, Debug)]
+pub struct SubscribeTopic<T, A: Actor>(pub T, pub ActorRef<A>);
+
+impl<T, A, M> Message<SubscribeTopic<T, A>> for TopicPubSub<T, M>
+where
+    T: Eq + Hash + Send + 'static,
+    A: Actor + Message<M>,
+    M: Send + 'static,
+    for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+        MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+{
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        SubscribeTopic(topic, actor_ref): SubscribeTopic<T, A>,
+        _ctx: Context<'_, Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.subscribe(topic, actor_ref)
+    }
+}
+
+/// A message used to unsubscribe an actor from a topic of a `TopicPubSub` actor.
+///
+/// Replies with `true` if the actor was subscribed to the topic.
+#[derive(Clone, Debug)]
+pub struct UnsubscribeTopic<T>(pub T, pub ActorID);
+
+impl<T, M> Message<UnsubscribeTopic<T>> for TopicPubSub<T, M>
+where
+    T: Eq + Hash + Send + 'static,
+    M: 'static,
+{
+    type Reply = bool;
+
+    async fn handle(
+        &mut self,
+        UnsubscribeTopic(topic, id): UnsubscribeTopic<T>,
+        _ctx: Context<'_, Self, Self::Reply>,
+    ) -> Self::Reply {
+        self.unsubscribe(&topic, id)
+    }
+}
+
 trait MessageSubscriber<M> {
     fn tell(&self, msg: M) -> BoxFuture<'_, Result<(), SendError<M, ()>>>;
 }
@@ -325,3 +545,105 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        actor::{Actor, ActorRef},
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+        request::MessageSend,
+    };
+
+    use super::{PublishTopic, SubscribeTopic, TopicPubSub, UnsubscribeTopic};
+
+    struct MyActor {
+        received: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl Actor for MyActor {
+        type Mailbox = UnboundedMailbox<Self>;
+    }
+
+    impl Message<&'static str> for MyActor {
+        type Reply = ();
+
+        async fn handle(
+            &mut self,
+            msg: &'static str,
+            _ctx: Context<'_, Self, Self::Reply>,
+        ) -> Self::Reply {
+            self.received.lock().unwrap().push(msg);
+        }
+    }
+
+    fn subscriber() -> (ActorRef<MyActor>, Arc<Mutex<Vec<&'static str>>>) {
+        let received = Arc::default();
+        let actor_ref = crate::spawn(MyActor {
+            received: Arc::clone(&received),
+        });
+        (actor_ref, received)
+    }
+
+    #[tokio::test]
+    async fn topic_pubsub_publishes_to_topic_subscribers() {
+        let (a, a_received) = subscriber();
+        let (b, b_received) = subscriber();
+        let mut pubsub = TopicPubSub::new();
+        pubsub.subscribe("news", a.clone());
+        pubsub.subscribe("news", b.clone());
+        pubsub.subscribe("sport", b.clone());
+
+        pubsub.publish(&"news", "headline").await;
+        pubsub.publish(&"sport", "score").await;
+        pubsub.publish(&"weather", "rain").await;
+        a.flush().await.unwrap();
+        b.flush().await.unwrap();
+        assert_eq!(*a_received.lock().unwrap(), ["headline"]);
+        assert_eq!(*b_received.lock().unwrap(), ["headline", "score"]);
+
+        let mut topics: Vec<_> = pubsub.topics().copied().collect();
+        topics.sort();
+        assert_eq!(topics, ["news", "sport"]);
+    }
+
+    #[tokio::test]
+    async fn topic_pubsub_removes_empty_topics() {
+        let (a, _) = subscriber();
+        let (b, _) = subscriber();
+        let mut pubsub = TopicPubSub::new();
+        pubsub.subscribe("news", a.clone());
+        pubsub.subscribe("sport", b.clone());
+
+        assert!(pubsub.unsubscribe(&"news", a.id()));
+        assert!(!pubsub.unsubscribe(&"news", a.id()));
+        assert!(!pubsub.unsubscribe(&"sport", a.id()));
+        assert_eq!(pubsub.topics().collect::<Vec<_>>(), [&"sport"]);
+
+        // Subscribers which stopped are removed when publishing
+        b.kill();
+        b.wait_for_stop().await;
+        pubsub.publish(&"sport", "score").await;
+        assert_eq!(pubsub.topics().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn topic_pubsub_actor_handles_messages() -> Result<(), Box<dyn std::error::Error>> {
+        let (a, a_received) = subscriber();
+        let pubsub = crate::spawn(TopicPubSub::new());
+        pubsub
+            .tell(SubscribeTopic("news", a.clone()))
+            .send()
+            .await?;
+        pubsub.tell(PublishTopic("news", "headline")).send().await?;
+        assert!(pubsub.ask(UnsubscribeTopic("news", a.id())).send().await?);
+        pubsub.tell(PublishTopic("news", "ignored")).send().await?;
+        pubsub.flush().await?;
+        a.flush().await?;
+        assert_eq!(*a_received.lock().unwrap(), ["headline"]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,428 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use futures::future::BoxFuture;
+
+use crate::{
+    error::SendError,
+    message::Message,
+    request::{LocalTellRequest, MessageSend, TellRequest, WithoutRequestTimeout},
+    Actor, Reply,
+};
+
+use super::{ActorID, ActorRef, WeakActorRef};
+
+/// A type erased, send-only handle to an actor which handles messages of type `M`.
+///
+/// A `Recipient` is created with [`ActorRef::recipient`], and allows actors of different types to be stored together,
+/// as long as they all handle the same message type. This is useful for subscriber lists and callbacks.
+///
+/// Recipients are compared and hashed by their actor's id, making them easy to deduplicate.
+///
+/// To group actors by a set of messages rather than a single one, define a handle with the
+/// [`recipient!`](crate::recipient!) macro.
+///
+/// Like an [`ActorRef`], the actor will remain alive for as long as any `Recipient` exists.
+/// Use a [`WeakRecipient`] to store a handle without keeping the actor alive.
+pub struct Recipient<M: Send + 'static> {
+    id: ActorID,
+    handler: Arc<dyn RecipientHandler<M>>,
+}
+
+impl<M: Send + 'static> Recipient<M> {
+    pub(crate) fn new<A>(actor_ref: ActorRef<A>) -> Self
+    where
+        A: Actor + Message<M>,
+        for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+            MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+    {
+        Recipient {
+            id: actor_ref.id(),
+            handler: Arc::new(actor_ref),
+        }
+    }
+
+    /// Returns the actor identifier.
+    #[inline]
+    pub fn id(&self) -> ActorID {
+        self.id
+    }
+
+    /// Returns whether the actor is currently alive.
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        self.handler.is_alive()
+    }
+
+    /// Sends a message to the actor without waiting for a reply.
+    pub async fn tell(&self, msg: M) -> Result<(), SendError<M>> {
+        self.handler.tell(msg).await
+    }
+
+    /// Downgrades the recipient to a [`WeakRecipient`], which does not keep the actor alive.
+    pub fn downgrade(&self) -> WeakRecipient<M> {
+        WeakRecipient {
+            id: self.id,
+            handler: self.handler.downgrade(),
+        }
+    }
+}
+
+impl<M: Send + 'static> Clone for Recipient<M> {
+    fn clone(&self) -> Self {
+        Recipient {
+            id: self.id,
+            handler: self.handler.clone(),
+        }
+    }
+}
+
+impl<M: Send + 'static> fmt::Debug for Recipient<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recipient").field("id", &self.id).finish()
+    }
+}
+
+impl<M: Send + 'static> PartialEq for Recipient<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<M: Send + 'static> Eq for Recipient<M> {}
+
+impl<M: Send + 'static> Hash for Recipient<M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// A weak, type erased, send-only handle to an actor which handles messages of type `M`.
+///
+/// A `WeakRecipient` does not keep the actor alive, and must be upgraded to a [`Recipient`] to send messages.
+/// Upgrading fails once the actor has stopped, or all of its strong references have been dropped.
+///
+/// Weak recipients are compared and hashed by their actor's id.
+pub struct WeakRecipient<M: Send + 'static> {
+    id: ActorID,
+    handler: Arc<dyn WeakRecipientHandler<M>>,
+}
+
+impl<M: Send + 'static> WeakRecipient<M> {
+    pub(crate) fn new<A>(actor_ref: WeakActorRef<A>) -> Self
+    where
+        A: Actor + Message<M>,
+        for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+            MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+    {
+        WeakRecipient {
+            id: actor_ref.id(),
+            handler: Arc::new(actor_ref),
+        }
+    }
+
+    /// Returns the actor identifier.
+    #[inline]
+    pub fn id(&self) -> ActorID {
+        self.id
+    }
+
+    /// Tries to upgrade to a [`Recipient`], returning `None` if the actor is no longer alive.
+    pub fn upgrade(&self) -> Option<Recipient<M>> {
+        self.handler.upgrade()
+    }
+}
+
+impl<M: Send + 'static> Clone for WeakRecipient<M> {
+    fn clone(&self) -> Self {
+        WeakRecipient {
+            id: self.id,
+            handler: self.handler.clone(),
+        }
+    }
+}
+
+impl<M: Send + 'static> fmt::Debug for WeakRecipient<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakRecipient")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl<M: Send + 'static> PartialEq for WeakRecipient<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<M: Send + 'static> Eq for WeakRecipient<M> {}
+
+impl<M: Send + 'static> Hash for WeakRecipient<M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+trait RecipientHandler<M: Send + 'static>: Send + Sync {
+    fn is_alive(&self) -> bool;
+
+    fn tell(&self, msg: M) -> BoxFuture<'_, Result<(), SendError<M>>>;
+
+    fn downgrade(&self) -> Arc<dyn WeakRecipientHandler<M>>;
+}
+
+impl<A, M> RecipientHandler<M> for ActorRef<A>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+    for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+        MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+{
+    fn is_alive(&self) -> bool {
+        ActorRef::is_alive(self)
+    }
+
+    fn tell(&self, msg: M) -> BoxFuture<'_, Result<(), SendError<M>>> {
+        Box::pin(async move {
+            ActorRef::tell(self, msg).send().await.map_err(|err| {
+                err.map_err(|_| unreachable!("tell requests don't return handler errors"))
+            })
+        })
+    }
+
+    fn downgrade(&self) -> Arc<dyn WeakRecipientHandler<M>> {
+        Arc::new(ActorRef::downgrade(self))
+    }
+}
+
+trait WeakRecipientHandler<M: Send + 'static>: Send + Sync {
+    fn upgrade(&self) -> Option<Recipient<M>>;
+}
+
+impl<A, M> WeakRecipientHandler<M> for WeakActorRef<A>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+    for<'a> TellRequest<LocalTellRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>:
+        MessageSend<Ok = (), Error = SendError<M, <A::Reply as Reply>::Error>>,
+{
+    fn upgrade(&self) -> Option<Recipient<M>> {
+        WeakActorRef::upgrade(self).map(Recipient::new)
+    }
+}
+
+/// Defines a type erased handle to an actor which handles a set of messages, similar to a [`Recipient`] for
+/// several message types at once.
+///
+/// A [`Recipient`] groups actors of different types by a single message they handle. When the actors share a
+/// whole set of messages, such as `Ping` and `Pong`, this macro generates a struct holding a [`Recipient`] for each
+/// message, which can be created from any actor handling all of them and stored in the same collection.
+///
+/// Each field names a message handled by the actor. The generated struct provides:
+/// - `new(&ActorRef<A>)` and `From<ActorRef<A>>`, for any actor `A` which handles every message.
+/// - `id()` and `is_alive()`.
+/// - `tell(msg)` for any of the messages, and `recipient::<M>()` to get the [`Recipient`] for a single message.
+/// - [`AsRef<Recipient<M>>`](AsRef) for each message, along with `Clone`, `Debug`, `PartialEq`, `Eq` and `Hash`,
+///   which compare by the actor's id like [`Recipient`].
+///
+/// # Example
+///
+/// ```
+/// use kameo::message::{Context, Message};
+/// use kameo::Actor;
+///
+/// struct Ping;
+/// struct Pong;
+///
+/// kameo::recipient! {
+///     /// An actor which handles both `Ping` and `Pong`.
+///     pub struct Peer {
+///         ping: Ping,
+///         pong: Pong,
+///     }
+/// }
+///
+/// # #[derive(Actor)]
+/// # struct MyActor;
+/// #
+/// # #[derive(Actor)]
+/// # struct OtherActor;
+/// #
+/// # impl Message<Ping> for MyActor {
+/// #     type Reply = ();
+/// #     async fn handle(&mut self, msg: Ping, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+/// # }
+/// #
+/// # impl Message<Pong> for MyActor {
+/// #     type Reply = ();
+/// #     async fn handle(&mut self, msg: Pong, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+/// # }
+/// #
+/// # impl Message<Ping> for OtherActor {
+/// #     type Reply = ();
+/// #     async fn handle(&mut self, msg: Ping, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+/// # }
+/// #
+/// # impl Message<Pong> for OtherActor {
+/// #     type Reply = ();
+/// #     async fn handle(&mut self, msg: Pong, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+/// # }
+/// #
+/// # tokio_test::block_on(async {
+/// let peers: Vec<Peer> = vec![
+///     Peer::new(&kameo::spawn(MyActor)),
+///     kameo::spawn(OtherActor).into(),
+/// ];
+/// for peer in &peers {
+///     peer.tell(Ping).await?;
+///     peer.tell(Pong).await?;
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[macro_export]
+macro_rules! recipient {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $field:ident : $msg:ty ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            id: $crate::actor::ActorID,
+            $( $field: $crate::actor::Recipient<$msg>, )+
+        }
+
+        impl $name {
+            /// Creates a handle to an actor which handles every message.
+            pub fn new<A>(actor_ref: &$crate::actor::ActorRef<A>) -> Self
+            where
+                A: $crate::Actor $( + $crate::message::Message<$msg> )+,
+                $(
+                    for<'a> $crate::request::TellRequest<
+                        $crate::request::LocalTellRequest<'a, A, A::Mailbox>,
+                        A::Mailbox,
+                        $msg,
+                        $crate::request::WithoutRequestTimeout,
+                    >: $crate::request::MessageSend<
+                        Ok = (),
+                        Error = $crate::error::SendError<
+                            $msg,
+                            <<A as $crate::message::Message<$msg>>::Reply as $crate::Reply>::Error,
+                        >,
+                    >,
+                )+
+            {
+                $name {
+                    id: actor_ref.id(),
+                    $( $field: actor_ref.recipient::<$msg>(), )+
+                }
+            }
+
+            /// Returns the actor identifier.
+            #[allow(dead_code)]
+            pub fn id(&self) -> $crate::actor::ActorID {
+                self.id
+            }
+
+            /// Returns whether the actor is currently alive.
+            #[allow(dead_code)]
+            pub fn is_alive(&self) -> bool {
+                $crate::__first_recipient!(self, $($field),+).is_alive()
+            }
+
+            /// Returns the recipient for a single message.
+            #[allow(dead_code)]
+            pub fn recipient<M>(&self) -> &$crate::actor::Recipient<M>
+            where
+                M: ::std::marker::Send + 'static,
+                Self: ::std::convert::AsRef<$crate::actor::Recipient<M>>,
+            {
+                self.as_ref()
+            }
+
+            /// Sends a message to the actor without waiting for a reply.
+            #[allow(dead_code)]
+            pub async fn tell<M>(&self, msg: M) -> ::std::result::Result<(), $crate::error::SendError<M>>
+            where
+                M: ::std::marker::Send + 'static,
+                Self: ::std::convert::AsRef<$crate::actor::Recipient<M>>,
+            {
+                self.recipient::<M>().tell(msg).await
+            }
+        }
+
+        $(
+            impl ::std::convert::AsRef<$crate::actor::Recipient<$msg>> for $name {
+                fn as_ref(&self) -> &$crate::actor::Recipient<$msg> {
+                    &self.$field
+                }
+            }
+        )+
+
+        impl<A> ::std::convert::From<$crate::actor::ActorRef<A>> for $name
+        where
+            A: $crate::Actor $( + $crate::message::Message<$msg> )+,
+            $(
+                for<'a> $crate::request::TellRequest<
+                    $crate::request::LocalTellRequest<'a, A, A::Mailbox>,
+                    A::Mailbox,
+                    $msg,
+                    $crate::request::WithoutRequestTimeout,
+                >: $crate::request::MessageSend<
+                    Ok = (),
+                    Error = $crate::error::SendError<
+                        $msg,
+                        <<A as $crate::message::Message<$msg>>::Reply as $crate::Reply>::Error,
+                    >,
+                >,
+            )+
+        {
+            fn from(actor_ref: $crate::actor::ActorRef<A>) -> Self {
+                $name::new(&actor_ref)
+            }
+        }
+
+        impl ::std::clone::Clone for $name {
+            fn clone(&self) -> Self {
+                $name {
+                    id: self.id,
+                    $( $field: self.$field.clone(), )+
+                }
+            }
+        }
+
+        impl ::std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.debug_struct(stringify!($name)).field("id", &self.id).finish()
+            }
+        }
+
+        impl ::std::cmp::PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.id == other.id
+            }
+        }
+
+        impl ::std::cmp::Eq for $name {}
+
+        impl ::std::hash::Hash for $name {
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                ::std::hash::Hash::hash(&self.id, state);
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __first_recipient {
+    ($self:ident, $first:ident $(, $rest:ident)*) => {
+        $self.$first
+    };
+}
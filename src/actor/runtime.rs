@@ -0,0 +1,120 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use super::watchdog::Watchdog;
+
+const DEFAULT_MAILBOX_CAPACITY: usize = 1000;
+
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+static MAILBOX_CAPACITY: AtomicUsize = AtomicUsize::new(DEFAULT_MAILBOX_CAPACITY);
+
+/// Process-wide configuration for kameo, applied once at startup with [`init`].
+///
+/// Every setting has a built-in default, which is used for any setting that isn't configured, and by actors spawned
+/// before [`init`] is called.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use kameo::actor::{watchdog::Watchdog, RuntimeConfig};
+///
+/// let config = RuntimeConfig::new()
+///     .default_mailbox_capacity(64)
+///     .watchdog(Watchdog::new(Duration::from_secs(5)));
+/// assert!(kameo::init(config));
+///
+/// // Later calls have no effect
+/// assert!(!kameo::init(RuntimeConfig::new()));
+/// ```
+#[derive(Default)]
+pub struct RuntimeConfig {
+    mailbox_capacity: Option<usize>,
+    #[cfg(feature = "tracing")]
+    panic_hook: bool,
+    watchdog: Option<Watchdog>,
+}
+
+impl RuntimeConfig {
+    /// Creates a new configuration with the built-in defaults.
+    pub fn new() -> Self {
+        RuntimeConfig::default()
+    }
+
+    /// Sets the capacity of mailboxes created with
+    /// [`BoundedMailbox::default_mailbox`](crate::mailbox::Mailbox::default_mailbox).
+    ///
+    /// This applies to every actor using a bounded mailbox which doesn't override
+    /// [`Actor::new_mailbox`](super::Actor::new_mailbox). Defaults to 1000.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is zero.
+    pub fn default_mailbox_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "mailbox capacity must be greater than zero");
+        self.mailbox_capacity = Some(capacity);
+        self
+    }
+
+    /// Installs the panic hook which logs panics within actors with `tracing`.
+    ///
+    /// See [`install_panic_hook`](super::install_panic_hook).
+    #[cfg(feature = "tracing")]
+    pub fn panic_hook(mut self) -> Self {
+        self.panic_hook = true;
+        self
+    }
+
+    /// Starts a watchdog for detecting actors which appear to be stuck handling a message.
+    ///
+    /// See [`Watchdog`].
+    pub fn watchdog(mut self, watchdog: Watchdog) -> Self {
+        self.watchdog = Some(watchdog);
+        self
+    }
+}
+
+impl fmt::Debug for RuntimeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_struct("RuntimeConfig");
+        f.field("mailbox_capacity", &self.mailbox_capacity);
+        #[cfg(feature = "tracing")]
+        f.field("panic_hook", &self.panic_hook);
+        f.field("watchdog", &self.watchdog).finish()
+    }
+}
+
+/// Applies a process-wide [`RuntimeConfig`].
+///
+/// This should be called once, early in the program, before any actors are spawned. Actors spawned beforehand
+/// keep the built-in defaults they were created with.
+///
+/// Only the first call has any effect. Re-initializing is not supported, since settings such as the panic hook
+/// and watchdog can't be undone, so later calls ignore their configuration and return `false`.
+pub fn init(config: RuntimeConfig) -> bool {
+    if INITIALIZED.swap(true, Ordering::AcqRel) {
+        return false;
+    }
+
+    if let Some(capacity) = config.mailbox_capacity {
+        MAILBOX_CAPACITY.store(capacity, Ordering::Relaxed);
+    }
+    #[cfg(feature = "tracing")]
+    if config.panic_hook {
+        super::install_panic_hook();
+    }
+    if let Some(watchdog) = config.watchdog {
+        watchdog.start();
+    }
+
+    true
+}
+
+/// Returns the capacity of default bounded mailboxes.
+#[inline]
+pub(crate) fn default_mailbox_capacity() -> usize {
+    MAILBOX_CAPACITY.load(Ordering::Relaxed)
+}
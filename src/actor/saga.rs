@@ -0,0 +1,346 @@
+//! Provides sagas for coordinating a sequence of steps across actors, with compensation on failure.
+//!
+//! A [`Saga`] sends a message to each of its steps in order, waiting for each reply before moving on to the next
+//! step. If a step fails, the compensating messages of the steps which already completed are sent in reverse
+//! order, undoing their effects. This is commonly used for multi-actor operations which can't share a transaction,
+//! such as reserving stock, charging a payment, and scheduling a delivery.
+//!
+//! # Features
+//! - **Ordered Steps**: Each step is sent with [`ask`](ActorRef::ask) once the previous step has replied.
+//! - **Compensation**: Completed steps are compensated in reverse order when a later step fails.
+//! - **Timeouts**: Steps and compensations can be limited to a maximum duration with [`Saga::timeout`].
+//!
+//! # Example
+//!
+//! ```
+//! use kameo::Actor;
+//! use kameo::actor::saga::Saga;
+//! # use kameo::message::{Context, Message};
+//!
+//! #[derive(Actor)]
+//! struct Inventory;
+//!
+//! #[derive(Actor)]
+//! struct Payments;
+//!
+//! struct Reserve(u32);
+//! struct Release(u32);
+//! struct Charge(u32);
+//! struct Refund(u32);
+//! #
+//! # impl Message<Reserve> for Inventory {
+//! #     type Reply = ();
+//! #     async fn handle(&mut self, msg: Reserve, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+//! # }
+//! #
+//! # impl Message<Release> for Inventory {
+//! #     type Reply = ();
+//! #     async fn handle(&mut self, msg: Release, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+//! # }
+//! #
+//! # impl Message<Charge> for Payments {
+//! #     type Reply = Result<(), &'static str>;
+//! #     async fn handle(&mut self, msg: Charge, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { Ok(()) }
+//! # }
+//! #
+//! # impl Message<Refund> for Payments {
+//! #     type Reply = ();
+//! #     async fn handle(&mut self, msg: Refund, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+//! # }
+//!
+//! # tokio_test::block_on(async {
+//! let inventory = kameo::spawn(Inventory);
+//! let payments = kameo::spawn(Payments);
+//!
+//! // If charging fails, the reservation is released
+//! Saga::new()
+//!     .step(&inventory, Reserve(1), Release(1))
+//!     .step(&payments, Charge(100), Refund(100))
+//!     .run()
+//!     .await?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+
+use std::{any::Any, error, fmt, future::Future, time::Duration};
+
+use futures::{future::BoxFuture, FutureExt};
+
+use crate::{
+    error::{BoxSendError, SendError},
+    message::Message,
+    request::{AskRequest, LocalAskRequest, MessageSend, WithoutRequestTimeout},
+    Reply,
+};
+
+use super::ActorRef;
+
+type BoxStep = Box<
+    dyn FnOnce(Option<Duration>) -> BoxFuture<'static, Result<Box<dyn Any + Send>, BoxSendError>>
+        + Send,
+>;
+type BoxCompensation =
+    Box<dyn FnOnce(Option<Duration>) -> BoxFuture<'static, Result<(), BoxSendError>> + Send>;
+
+/// A sequence of steps sent to actors in order, which are compensated in reverse order if a step fails.
+///
+/// See the [module level documentation](self) for more information.
+#[derive(Default)]
+pub struct Saga {
+    steps: Vec<(BoxStep, BoxCompensation)>,
+    timeout: Option<Duration>,
+}
+
+impl Saga {
+    /// Creates a new saga with no steps.
+    pub fn new() -> Self {
+        Saga::default()
+    }
+
+    /// Adds a step to the saga.
+    ///
+    /// When the saga is run, `forward` is sent to the actor once all previous steps have succeeded. If a later step
+    /// fails, `compensate` is sent to the same actor to undo the effects of `forward`.
+    pub fn step<A, M, C>(mut self, actor_ref: &ActorRef<A>, forward: M, compensate: C) -> Self
+    where
+        A: Message<M> + Message<C>,
+        M: Send + 'static,
+        C: Send + 'static,
+        for<'a> AskRequest<
+            LocalAskRequest<'a, A, A::Mailbox>,
+            A::Mailbox,
+            M,
+            WithoutRequestTimeout,
+            WithoutRequestTimeout,
+        >: MessageSend<
+            Ok = <<A as Message<M>>::Reply as Reply>::Ok,
+            Error = SendError<M, <<A as Message<M>>::Reply as Reply>::Error>,
+        >,
+        for<'a> AskRequest<
+            LocalAskRequest<'a, A, A::Mailbox>,
+            A::Mailbox,
+            C,
+            WithoutRequestTimeout,
+            WithoutRequestTimeout,
+        >: MessageSend<
+            Ok = <<A as Message<C>>::Reply as Reply>::Ok,
+            Error = SendError<C, <<A as Message<C>>::Reply as Reply>::Error>,
+        >,
+    {
+        let forward_ref = actor_ref.clone();
+        let compensate_ref = actor_ref.clone();
+        self.steps.push((
+            Box::new(move |timeout| {
+                async move {
+                    within(timeout, forward_ref.ask(forward).send())
+                        .await
+                        .map(|reply| Box::new(reply) as Box<dyn Any + Send>)
+                        .map_err(SendError::boxed)
+                }
+                .boxed()
+            }),
+            Box::new(move |timeout| {
+                async move {
+                    within(timeout, compensate_ref.ask(compensate).send())
+                        .await
+                        .map(|_| ())
+                        .map_err(SendError::boxed)
+                }
+                .boxed()
+            }),
+        ));
+        self
+    }
+
+    /// Sets the maximum duration to wait for each step and compensation, including waiting for mailbox capacity.
+    ///
+    /// A step which doesn't reply in time fails with [`SendError::Timeout`]. Since the actor may still handle the
+    /// message, the step which timed out is not compensated, only the steps before it.
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = Some(duration);
+        self
+    }
+
+    /// Runs the saga, sending each step in order.
+    ///
+    /// On success, the replies of every step are returned in order, and can be downcast to each step's
+    /// [`Reply::Ok`] type.
+    ///
+    /// If a step fails, no further steps are sent, and the compensating messages of all previous steps are sent
+    /// in reverse order before the error is returned. A failing compensation doesn't stop the remaining
+    /// compensations from being sent.
+    pub async fn run(self) -> Result<Vec<Box<dyn Any + Send>>, SagaError> {
+        let Saga { steps, timeout } = self;
+        let mut replies = Vec::with_capacity(steps.len());
+        let mut compensations = Vec::with_capacity(steps.len());
+        for (step, (forward, compensate)) in steps.into_iter().enumerate() {
+            match forward(timeout).await {
+                Ok(reply) => {
+                    replies.push(reply);
+                    compensations.push(compensate);
+                }
+                Err(error) => {
+                    let mut compensation_errors = Vec::new();
+                    for (i, compensate) in compensations.into_iter().enumerate().rev() {
+                        if let Err(err) = compensate(timeout).await {
+                            compensation_errors.push((i, err));
+                        }
+                    }
+
+                    return Err(SagaError {
+                        step,
+                        error,
+                        compensation_errors,
+                    });
+                }
+            }
+        }
+
+        Ok(replies)
+    }
+}
+
+impl fmt::Debug for Saga {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Saga")
+            .field("steps", &self.steps.len())
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// An error returned by [`Saga::run`] when a step fails.
+///
+/// The errors contain boxed messages and handler errors, which can be downcast with [`BoxSendError::downcast`].
+#[derive(Debug)]
+pub struct SagaError {
+    /// The index of the step which failed.
+    pub step: usize,
+    /// The error returned by the failed step.
+    pub error: BoxSendError,
+    /// The errors returned by compensating messages which failed, along with the index of their step.
+    ///
+    /// This is empty if every completed step was compensated successfully.
+    pub compensation_errors: Vec<(usize, BoxSendError)>,
+}
+
+impl fmt::Display for SagaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "saga step {} failed", self.step)?;
+        if !self.compensation_errors.is_empty() {
+            write!(
+                f,
+                ", and {} compensations failed",
+                self.compensation_errors.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for SagaError {}
+
+async fn within<T, M, E>(
+    timeout: Option<Duration>,
+    fut: impl Future<Output = Result<T, SendError<M, E>>>,
+) -> Result<T, SendError<M, E>> {
+    match timeout {
+        Some(duration) => tokio::time::timeout(duration, fut)
+            .await
+            .unwrap_or_else(|elapsed| Err(elapsed.into())),
+        None => fut.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        actor::Actor,
+        error::SendError,
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+        request::MessageSend,
+        spawn,
+    };
+
+    use super::Saga;
+
+    #[tokio::test]
+    async fn saga_compensates_completed_steps_on_failure() -> Result<(), Box<dyn std::error::Error>>
+    {
+        #[derive(Default)]
+        struct Account {
+            balance: i64,
+        }
+
+        impl Actor for Account {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Deposit(i64);
+
+        impl Message<Deposit> for Account {
+            type Reply = Result<i64, &'static str>;
+
+            async fn handle(
+                &mut self,
+                Deposit(amount): Deposit,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                if self.balance + amount < 0 {
+                    return Err("insufficient funds");
+                }
+                self.balance += amount;
+                Ok(self.balance)
+            }
+        }
+
+        struct Balance;
+
+        impl Message<Balance> for Account {
+            type Reply = i64;
+
+            async fn handle(
+                &mut self,
+                _msg: Balance,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.balance
+            }
+        }
+
+        let a = spawn(Account { balance: 10 });
+        let b = spawn(Account::default());
+        let c = spawn(Account::default());
+
+        let replies = Saga::new()
+            .step(&a, Deposit(-5), Deposit(5))
+            .step(&b, Deposit(5), Deposit(-5))
+            .run()
+            .await?;
+        assert_eq!(*replies[0].downcast_ref::<i64>().unwrap(), 5);
+        assert_eq!(*replies[1].downcast_ref::<i64>().unwrap(), 5);
+
+        let err = Saga::new()
+            .step(&a, Deposit(-5), Deposit(5))
+            .step(&b, Deposit(5), Deposit(-5))
+            .step(&c, Deposit(-5), Deposit(5))
+            .timeout(Duration::from_secs(1))
+            .run()
+            .await
+            .unwrap_err();
+        assert_eq!(err.step, 2);
+        assert!(err.compensation_errors.is_empty());
+        assert!(matches!(
+            err.error.downcast::<Deposit, &'static str>(),
+            SendError::HandlerError("insufficient funds")
+        ));
+        assert_eq!(a.ask(Balance).send().await?, 5);
+        assert_eq!(b.ask(Balance).send().await?, 5);
+        assert_eq!(c.ask(Balance).send().await?, 0);
+
+        Ok(())
+    }
+}
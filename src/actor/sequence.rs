@@ -0,0 +1,193 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::Range,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::{
+    message::Message,
+    request::{LocalTellRequest, TellRequest, WithoutRequestTimeout},
+    Actor,
+};
+
+use super::ActorRef;
+
+static NEXT_SENDER_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The position of a message in the stream of messages sent by a [`SequencedSender`].
+///
+/// Every message told through a [`SequencedSender`] carries a `Sequence` as an extension, which handlers can read
+/// with [`Context::extensions`](crate::message::Context::extensions), and pass to a [`SequenceTracker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Sequence {
+    /// The unique identifier of the sender which sent the message.
+    pub sender: u64,
+    /// The number of messages the sender created before this one, starting at zero.
+    pub seq: u64,
+}
+
+/// A sender which numbers each message it tells an actor, so the actor can verify it receives them in order.
+///
+/// Messages sent by a single task are always enqueued in the order they were sent, even while other tasks send to
+/// the same actor; messages from other senders may be interleaved between them, but never reorder them. This holds
+/// for every mailbox which handles messages in the order they were sent, including the unbounded, bounded and fair
+/// mailboxes, but not for the [priority](crate::mailbox::priority) and [LIFO](crate::mailbox::lifo) mailboxes, which
+/// reorder messages by design.
+///
+/// A `SequencedSender` makes this guarantee checkable end to end. It attaches a [`Sequence`] extension to every
+/// message, numbering them from zero, and the actor can pass each sequence to a [`SequenceTracker`] to detect gaps
+/// and reordering. Sending takes `&mut self`, so the previous request must be sent or dropped before the next one
+/// is numbered, and a sequenced sender can't be shared between tasks.
+///
+/// # Example
+///
+/// ```
+/// use kameo::actor::{Sequence, SequenceCheck, SequenceTracker, SequencedSender};
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(kameo::Actor, Default)]
+/// struct Ledger {
+///     tracker: SequenceTracker,
+/// }
+///
+/// struct Entry(i64);
+///
+/// impl Message<Entry> for Ledger {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, _: Entry, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         if let Some(seq) = ctx.extensions().get::<Sequence>() {
+///             let check = self.tracker.check(*seq);
+///             assert_eq!(check, SequenceCheck::InOrder);
+///         }
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::spawn(Ledger::default());
+/// let mut sender = SequencedSender::new(actor_ref);
+/// for amount in [10, -4, 7] {
+///     sender.tell(Entry(amount)).await?;
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct SequencedSender<A: Actor> {
+    actor_ref: ActorRef<A>,
+    sender: u64,
+    next_seq: u64,
+}
+
+impl<A: Actor> SequencedSender<A> {
+    /// Creates a new sequenced sender for the actor, with a unique sender identifier.
+    pub fn new(actor_ref: ActorRef<A>) -> Self {
+        SequencedSender {
+            actor_ref,
+            sender: NEXT_SENDER_ID.fetch_add(1, Ordering::Relaxed),
+            next_seq: 0,
+        }
+    }
+
+    /// Returns the unique identifier attached to every message sent by this sender.
+    pub fn sender_id(&self) -> u64 {
+        self.sender
+    }
+
+    /// Returns the sequence number which will be attached to the next message.
+    pub fn next_seq(&self) -> u64 {
+        self.next_seq
+    }
+
+    /// Returns the actor messages are sent to.
+    pub fn actor_ref(&self) -> &ActorRef<A> {
+        &self.actor_ref
+    }
+
+    /// Sends a message to the actor without waiting for a reply, attaching the next [`Sequence`].
+    ///
+    /// The sequence number is used up when the request is created, so a request which is dropped or fails to send
+    /// shows up as a gap in the sequence.
+    ///
+    /// See [`ActorRef::tell`].
+    #[track_caller]
+    pub fn tell<M>(
+        &mut self,
+        msg: M,
+    ) -> TellRequest<LocalTellRequest<'_, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+    {
+        let seq = Sequence {
+            sender: self.sender,
+            seq: self.next_seq,
+        };
+        self.next_seq += 1;
+        self.actor_ref.tell(msg).extension(seq)
+    }
+}
+
+impl<A: Actor> fmt::Debug for SequencedSender<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SequencedSender")
+            .field("actor_id", &self.actor_ref.id())
+            .field("sender", &self.sender)
+            .field("next_seq", &self.next_seq)
+            .finish()
+    }
+}
+
+/// The result of checking a [`Sequence`] with a [`SequenceTracker`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SequenceCheck {
+    /// The message was the next one expected from its sender.
+    InOrder,
+    /// The message skipped over the given sequence numbers, which were never received.
+    ///
+    /// Later messages are expected to follow on from this message.
+    Gap(Range<u64>),
+    /// The message was expected earlier, and was either reordered or received twice.
+    OutOfOrder {
+        /// The sequence number which was expected instead.
+        expected: u64,
+    },
+}
+
+/// Tracks the sequences received from each [`SequencedSender`], detecting gaps and reordering.
+///
+/// See [`SequencedSender`] for more information.
+#[derive(Clone, Debug, Default)]
+pub struct SequenceTracker {
+    expected: HashMap<u64, u64>,
+}
+
+impl SequenceTracker {
+    /// Creates a new tracker which hasn't received any messages.
+    pub fn new() -> Self {
+        SequenceTracker::default()
+    }
+
+    /// Records a received sequence, returning whether it followed on from the last one received from its sender.
+    pub fn check(&mut self, seq: Sequence) -> SequenceCheck {
+        let expected = self.expected.entry(seq.sender).or_default();
+        if seq.seq < *expected {
+            return SequenceCheck::OutOfOrder {
+                expected: *expected,
+            };
+        }
+
+        let missing = *expected..seq.seq;
+        *expected = seq.seq + 1;
+        if missing.is_empty() {
+            SequenceCheck::InOrder
+        } else {
+            SequenceCheck::Gap(missing)
+        }
+    }
+
+    /// Returns the sequence number expected next from a sender.
+    pub fn expected(&self, sender: u64) -> u64 {
+        self.expected.get(&sender).copied().unwrap_or(0)
+    }
+}
@@ -0,0 +1,204 @@
+use std::{collections::HashMap, fmt, panic};
+
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, StreamExt};
+
+use crate::{error::ActorStopReason, Actor};
+
+use super::{ActorID, ActorRef, PreparedActor, WeakActorRef};
+
+/// A collection of spawned actors, which can be awaited as each one stops.
+///
+/// An `ActorSet` is similar to tokio's [`JoinSet`](tokio::task::JoinSet), but for actors. Actors spawned into the set
+/// can be awaited in the order they stop with [`ActorSet::join_next`], making it easy to react to the termination of
+/// a dynamic group of actors, such as one actor per connection, without linking them to a supervisor.
+///
+/// The set only holds weak references to its actors, which are used by [`ActorSet::abort_all`]. Dropping the set
+/// does not stop the actors.
+///
+/// # Example
+///
+/// ```
+/// use kameo::actor::ActorSet;
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct Connection;
+///
+/// # tokio_test::block_on(async {
+/// let mut set = ActorSet::new();
+/// let conn_ref = set.spawn(Connection);
+///
+/// conn_ref.stop_gracefully().await?;
+/// while let Some((id, reason)) = set.join_next().await {
+///     println!("connection {id} closed: {reason}");
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct ActorSet<A: Actor> {
+    actors: HashMap<ActorID, WeakActorRef<A>>,
+    stopped: FuturesUnordered<BoxFuture<'static, (ActorID, ActorStopReason)>>,
+}
+
+impl<A: Actor> ActorSet<A> {
+    /// Creates a new empty set.
+    pub fn new() -> Self {
+        ActorSet {
+            actors: HashMap::new(),
+            stopped: FuturesUnordered::new(),
+        }
+    }
+
+    /// Spawns an actor in a new tokio task, adding it to the set.
+    ///
+    /// See [`spawn`](crate::actor::spawn) for more information.
+    pub fn spawn(&mut self, actor: A) -> ActorRef<A> {
+        let prepared_actor = PreparedActor::new();
+        let actor_ref = prepared_actor.actor_ref().clone();
+        let id = actor_ref.id();
+        let handle = prepared_actor.spawn(actor);
+
+        self.actors.insert(id, actor_ref.downgrade());
+        self.stopped.push(
+            async move {
+                match handle.await {
+                    Ok((_, reason)) => (id, reason),
+                    Err(err) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+                    Err(_) => (id, ActorStopReason::Killed),
+                }
+            }
+            .boxed(),
+        );
+
+        actor_ref
+    }
+
+    /// Waits for the next actor in the set to stop, returning its id and the reason it stopped.
+    ///
+    /// Returns `None` if the set is empty.
+    pub async fn join_next(&mut self) -> Option<(ActorID, ActorStopReason)> {
+        let (id, reason) = self.stopped.next().await?;
+        self.actors.remove(&id);
+        Some((id, reason))
+    }
+
+    /// Kills every actor in the set.
+    ///
+    /// The actors remain in the set, and can still be awaited with [`ActorSet::join_next`].
+    pub fn abort_all(&self) {
+        for actor_ref in self.actors.values() {
+            if let Some(actor_ref) = actor_ref.upgrade() {
+                actor_ref.kill();
+            }
+        }
+    }
+
+    /// Returns the number of actors in the set which have not yet been joined.
+    pub fn len(&self) -> usize {
+        self.actors.len()
+    }
+
+    /// Returns `true` if the set contains no actors.
+    pub fn is_empty(&self) -> bool {
+        self.actors.is_empty()
+    }
+}
+
+impl<A: Actor> Default for ActorSet<A> {
+    fn default() -> Self {
+        ActorSet::new()
+    }
+}
+
+impl<A: Actor> fmt::Debug for ActorSet<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActorSet")
+            .field("actors", &self.actors.keys())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        actor::Actor,
+        error::ActorStopReason,
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+        request::MessageSend,
+    };
+
+    use super::ActorSet;
+
+    struct MyActor;
+
+    impl Actor for MyActor {
+        type Mailbox = UnboundedMailbox<Self>;
+    }
+
+    struct Panic;
+
+    impl Message<Panic> for MyActor {
+        type Reply = ();
+
+        async fn handle(&mut self, _msg: Panic, _ctx: Context<'_, Self, Self::Reply>) {
+            panic!("oh no");
+        }
+    }
+
+    #[tokio::test]
+    async fn actor_set_joins_actors_as_they_stop() {
+        let mut set = ActorSet::new();
+        assert!(set.join_next().await.is_none());
+
+        let stopped = set.spawn(MyActor);
+        let panicked = set.spawn(MyActor);
+        let killed = set.spawn(MyActor);
+        let running = set.spawn(MyActor);
+        assert_eq!(set.len(), 4);
+
+        stopped.stop_gracefully().await.unwrap();
+        assert_eq!(
+            set.join_next().await,
+            Some((stopped.id(), ActorStopReason::Normal))
+        );
+
+        killed.kill();
+        assert_eq!(
+            set.join_next().await,
+            Some((killed.id(), ActorStopReason::Killed))
+        );
+
+        panicked.tell(Panic).send().await.unwrap();
+        let (id, reason) = set.join_next().await.unwrap();
+        assert_eq!(id, panicked.id());
+        assert!(matches!(reason, ActorStopReason::Panicked(_)));
+
+        assert_eq!(set.len(), 1);
+        assert!(running.is_alive());
+
+        // Dropping the set doesn't stop its actors
+        drop(set);
+        tokio::task::yield_now().await;
+        assert!(running.is_alive());
+    }
+
+    #[tokio::test]
+    async fn actor_set_abort_all_kills_every_actor() {
+        let mut set = ActorSet::new();
+        let actor_refs: Vec<_> = (0..3).map(|_| set.spawn(MyActor)).collect();
+
+        set.abort_all();
+        let mut joined = Vec::new();
+        while let Some((id, reason)) = set.join_next().await {
+            assert_eq!(reason, ActorStopReason::Killed);
+            joined.push(id);
+        }
+        joined.sort();
+
+        let mut ids: Vec<_> = actor_refs.iter().map(|actor_ref| actor_ref.id()).collect();
+        ids.sort();
+        assert_eq!(joined, ids);
+        assert!(set.is_empty());
+    }
+}
@@ -1,16 +1,27 @@
-use std::{convert, panic::AssertUnwindSafe, sync::Arc, thread};
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    convert,
+    panic::AssertUnwindSafe,
+    sync::{atomic::Ordering, Arc},
+    thread,
+};
 
 use futures::{
+    future::BoxFuture,
     stream::{AbortHandle, AbortRegistration, Abortable, FuturesUnordered},
     FutureExt, StreamExt,
 };
+#[cfg(feature = "tracing")]
+use std::collections::BTreeMap;
 use tokio::{
     runtime::{Handle, RuntimeFlavor},
-    sync::Semaphore,
+    sync::{oneshot, Semaphore},
     task::JoinHandle,
+    time::Instant,
 };
 #[cfg(feature = "tracing")]
-use tracing::{error, trace};
+use tracing::{error, trace, warn};
 
 #[cfg(feature = "remote")]
 use crate::remote;
@@ -18,13 +29,17 @@ use crate::remote;
 use crate::{
     actor::{
         kind::{ActorBehaviour, ActorState},
-        Actor, ActorRef, Link, Links, CURRENT_ACTOR_ID,
+        Actor, ActorRef, InitActor, Link, Links, CURRENT_ACTOR_ID,
     },
-    error::{ActorStopReason, PanicError, SendError},
+    error::{ActorStopReason, PanicError, SendError, SpawnError},
     mailbox::{Mailbox, MailboxReceiver, Signal},
 };
 
-use super::ActorID;
+use super::{
+    graph,
+    migrate::{migratable, Migratable, MigrationSlot},
+    ActorID,
+};
 
 /// Spawns an actor in a Tokio task, running asynchronously.
 ///
@@ -56,6 +71,161 @@ where
     actor_ref
 }
 
+/// Spawns an actor in a Tokio task with a name, running asynchronously.
+///
+/// This is the same as [`spawn`], but overrides [`Actor::name`] for this instance, which is useful for telling apart
+/// many actors of the same type. The name is returned by [`ActorRef::name`], and is used in logs, task names,
+/// and the [supervision graph](crate::export_graph).
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct Worker;
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::actor::spawn_named(Worker, "worker-3");
+/// assert_eq!(actor_ref.name(), "worker-3");
+/// # })
+/// ```
+pub fn spawn_named<A>(actor: A, name: impl Into<String>) -> ActorRef<A>
+where
+    A: Actor,
+{
+    let prepared_actor = PreparedActor::new_named(name);
+    let actor_ref = prepared_actor.actor_ref().clone();
+    prepared_actor.spawn(actor);
+    actor_ref
+}
+
+/// Spawns an actor in a Tokio task, using a mailbox which was created by the caller.
+///
+/// This is intended for white-box tests of the actor runtime. Keeping a clone of `mailbox` allows signals to be
+/// sent to the actor directly, for example to simulate a linked actor dying without spawning one.
+///
+/// Signals are an implementation detail of kameo and may change in any release, so this should not be used
+/// outside of tests.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+/// use kameo::mailbox::{bounded::BoundedMailbox, Mailbox, Signal};
+///
+/// #[derive(Actor)]
+/// #[actor(mailbox = bounded(8))]
+/// struct MyActor;
+///
+/// # tokio_test::block_on(async {
+/// let (mailbox, mailbox_rx) = BoundedMailbox::new(8);
+/// let actor_ref = kameo::actor::spawn_with_mailbox_parts(MyActor, mailbox.clone(), mailbox_rx);
+/// mailbox.send(Signal::Stop).await?;
+/// actor_ref.wait_for_stop().await;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[doc(hidden)]
+pub fn spawn_with_mailbox_parts<A>(
+    actor: A,
+    mailbox: A::Mailbox,
+    mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
+) -> ActorRef<A>
+where
+    A: Actor,
+{
+    let prepared_actor = PreparedActor::new_with_mailbox(mailbox, mailbox_rx);
+    let actor_ref = prepared_actor.actor_ref().clone();
+    prepared_actor.spawn(actor);
+    actor_ref
+}
+
+/// Spawns an actor in a Tokio task, constructing it asynchronously with [`InitActor::init`].
+///
+/// The returned [`ActorRef`] can be used immediately, and messages sent before the actor has been constructed
+/// are queued until it starts.
+///
+/// See [`InitActor`] for more information.
+pub fn spawn_init<A>(args: A::Args) -> ActorRef<A>
+where
+    A: InitActor,
+{
+    let prepared_actor = PreparedActor::new();
+    let actor_ref = prepared_actor.actor_ref().clone();
+    prepared_actor.spawn_init(args);
+    actor_ref
+}
+
+/// Spawns an actor in a Tokio task, waiting for it to be constructed with [`InitActor::init`].
+///
+/// Unlike [`spawn_init`], this waits until `init` has completed, returning its error if the actor
+/// could not be constructed. The actor's [`Actor::on_start`] hook may still be running when this returns.
+///
+/// If the actor's task is dropped before `init` completes, such as when the runtime shuts down,
+/// [`SpawnError::ActorNotRunning`] is returned.
+///
+/// See [`InitActor`] for more information.
+///
+/// # Example
+///
+/// ```
+/// # use kameo::actor::{spawn_with_init, ActorRef, InitActor};
+/// # use kameo::error::BoxError;
+/// # use kameo::mailbox::unbounded::UnboundedMailbox;
+/// # use kameo::Actor;
+/// #
+/// # struct MyActor { port: u16 }
+/// #
+/// # impl Actor for MyActor {
+/// #     type Mailbox = UnboundedMailbox<Self>;
+/// # }
+/// #
+/// # impl InitActor for MyActor {
+/// #     type Args = u16;
+/// #     async fn init(port: u16, _actor_ref: ActorRef<Self>) -> Result<Self, BoxError> {
+/// #         Ok(MyActor { port })
+/// #     }
+/// # }
+/// #
+/// # tokio_test::block_on(async {
+/// match spawn_with_init::<MyActor>(8080).await {
+///     Ok(actor_ref) => { /* The actor was constructed */ }
+///     Err(err) => eprintln!("failed to construct actor: {err}"),
+/// }
+/// # });
+/// ```
+pub async fn spawn_with_init<A>(args: A::Args) -> Result<ActorRef<A>, SpawnError>
+where
+    A: InitActor,
+{
+    let prepared_actor = PreparedActor::new();
+    let actor_ref = prepared_actor.actor_ref().clone();
+    let (init_tx, init_rx) = oneshot::channel();
+    let run = migratable(
+        actor_ref.info.clone(),
+        CURRENT_ACTOR_ID.scope(
+            actor_ref.id(),
+            prepared_actor.run_init_notify(args, Some(init_tx)),
+        ),
+    );
+
+    #[cfg(not(tokio_unstable))]
+    tokio::spawn(run);
+
+    #[cfg(tokio_unstable)]
+    tokio::task::Builder::new()
+        .name(actor_ref.name())
+        .spawn(run)
+        .unwrap();
+
+    init_rx
+        .await
+        .map_err(|_| SpawnError::ActorNotRunning)?
+        .map_err(SpawnError::InitFailed)?;
+    Ok(actor_ref)
+}
+
 /// Spawns and links an actor in a Tokio task, running asynchronously.
 ///
 /// This function is used to ensure an actor is linked with another actor before its truly spawned,
@@ -79,6 +249,9 @@ where
 /// ```
 ///
 /// The actor will continue running in the background, and messages can be sent to it via `actor_ref`.
+///
+/// If `link_ref` has already stopped, the actor is still spawned, but is never notified of it. Use
+/// [`try_spawn_link`] to avoid spawning the actor in this case.
 pub async fn spawn_link<A, L>(link_ref: &ActorRef<L>, actor: A) -> ActorRef<A>
 where
     A: Actor,
@@ -91,6 +264,54 @@ where
     actor_ref
 }
 
+/// Spawns and links an actor in a Tokio task, unless the actor to link to has already stopped.
+///
+/// Checking that `link_ref` is running and linking to it happen atomically, so the spawned actor can never be
+/// left unsupervised. If `link_ref` has stopped, or is stopping, the actor is not spawned, and is returned in
+/// [`SendError::ActorNotRunning`].
+///
+/// A stopping actor notifies its links before calling [`Actor::on_stop`], and stops accepting new links at the
+/// same time. This means that if `try_spawn_link` succeeds, the spawned actor will be notified through
+/// [`Actor::on_link_died`] when `link_ref` stops, and any call made once `link_ref` has reached `on_stop` fails.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct Parent;
+///
+/// #[derive(Actor)]
+/// struct Child;
+///
+/// # tokio_test::block_on(async {
+/// let parent_ref = kameo::spawn(Parent);
+/// let child_ref = kameo::actor::try_spawn_link(&parent_ref, Child).await?;
+///
+/// parent_ref.stop_gracefully().await?;
+/// parent_ref.wait_for_stop().await;
+/// assert!(kameo::actor::try_spawn_link(&parent_ref, Child).await.is_err());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub async fn try_spawn_link<A, L>(
+    link_ref: &ActorRef<L>,
+    actor: A,
+) -> Result<ActorRef<A>, SendError<A>>
+where
+    A: Actor,
+    L: Actor,
+{
+    let prepared_actor = PreparedActor::new();
+    let actor_ref = prepared_actor.actor_ref().clone();
+    if !actor_ref.link_if_running(link_ref).await {
+        return Err(SendError::ActorNotRunning(actor));
+    }
+    prepared_actor.spawn(actor);
+    Ok(actor_ref)
+}
+
 /// Spawns an actor in its own dedicated thread, allowing for blocking operations.
 ///
 /// This function spawns the actor in a separate thread, making it suitable for actors that perform blocking
@@ -138,6 +359,118 @@ where
     actor_ref
 }
 
+/// Spawns an actor pinned to its own OS thread, running on a dedicated single threaded tokio runtime.
+///
+/// Unlike [`spawn`], where the actor's task may be moved between the worker threads of the shared runtime, a pinned
+/// actor always runs on the same thread, and never waits behind other tasks to be scheduled. This reduces
+/// scheduling jitter and keeps the actor's state warm in the thread's cache, which suits latency sensitive
+/// actors. The returned [`ActorRef`] can be used from any thread, with messages sent through the mailbox as usual.
+///
+/// Unlike [`spawn_in_thread`], this doesn't require a multi threaded runtime, or any runtime at all, since the
+/// actor drives its own runtime.
+///
+/// # Overhead
+///
+/// Each pinned actor costs a dedicated OS thread, with its own stack, along with a runtime and its timer, for the
+/// whole lifetime of the actor. Messages sent from other threads wake the actor's thread rather than a shared
+/// worker, so pinning many actors, or actors which are mostly idle, uses more memory and context switches than
+/// spawning them as tasks. Pinning is best kept to a handful of actors where latency matters.
+///
+/// Tasks spawned by the actor with [`tokio::spawn`] run on its dedicated runtime too, and are dropped once the
+/// actor stops.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct OrderBook;
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::actor::spawn_pinned(OrderBook);
+/// actor_ref.wait_startup().await;
+/// # })
+/// ```
+pub fn spawn_pinned<A>(actor: A) -> ActorRef<A>
+where
+    A: Actor,
+{
+    let prepared_actor = PreparedActor::new();
+    let actor_ref = prepared_actor.actor_ref().clone();
+    prepared_actor.spawn_pinned(actor);
+    actor_ref
+}
+
+/// Spawns an actor using a custom [`Spawner`], rather than the global tokio runtime.
+///
+/// Tasks spawned internally for the actor, such as ones passing on delegated replies, are spawned with the same
+/// spawner.
+///
+/// # Example
+///
+/// ```
+/// use kameo::Actor;
+///
+/// #[derive(Actor)]
+/// struct MyActor;
+///
+/// # tokio_test::block_on(async {
+/// let runtime = tokio::runtime::Runtime::new()?;
+/// let actor_ref = kameo::actor::spawn_with(runtime.handle(), MyActor);
+/// actor_ref.wait_startup().await;
+/// # actor_ref.kill();
+/// # actor_ref.wait_for_stop().await;
+/// # runtime.shutdown_background();
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+///
+/// The actor will run for as long as the spawner drives it.
+pub fn spawn_with<A, S>(spawner: &S, actor: A) -> ActorRef<A>
+where
+    A: Actor,
+    S: Spawner + Clone + Send + Sync + 'static,
+{
+    let prepared_actor = PreparedActor::new();
+    let actor_ref = prepared_actor.actor_ref().clone();
+    prepared_actor.spawn_with(spawner, actor);
+    actor_ref
+}
+
+/// An executor which actor tasks can be spawned onto.
+///
+/// By default, actors are spawned with [`tokio::spawn`]. Implementing `Spawner` allows actors to be run on
+/// a specific runtime [`Handle`], or any other executor able to drive a future to completion.
+///
+/// Closures of the form `Fn(BoxFuture<'static, ()>)` implement `Spawner`.
+///
+/// # Limitations
+///
+/// Spawned futures are [`Send`], since actors must be `Send`, and the spawner may be called from any thread, such
+/// as when a reply sent from another task is passed on to the actor. Executors confined to a single thread, such as
+/// a [`LocalSet`](tokio::task::LocalSet), are therefore not supported, and `!Send` actors can't be spawned.
+/// To keep an actor on a thread of its own, use [`spawn_pinned`] instead.
+pub trait Spawner {
+    /// Spawns a future, driving it to completion in the background.
+    fn spawn(&self, future: BoxFuture<'static, ()>);
+}
+
+impl<F> Spawner for F
+where
+    F: Fn(BoxFuture<'static, ()>),
+{
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        self(future)
+    }
+}
+
+impl Spawner for Handle {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        Handle::spawn(self, future);
+    }
+}
+
 /// A `PreparedActor` represents an actor that has been initialized and is ready to be either run
 /// in the current task or spawned into a new task.
 ///
@@ -149,6 +482,7 @@ pub struct PreparedActor<A: Actor> {
     actor_ref: ActorRef<A>,
     mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
     abort_registration: AbortRegistration,
+    graph_guard: graph::RegisteredGuard,
 }
 
 impl<A: Actor> PreparedActor<A> {
@@ -172,7 +506,37 @@ impl<A: Actor> PreparedActor<A> {
     /// # });
     /// ```
     pub fn new() -> Self {
+        Self::new_with_name(Cow::Borrowed(A::name()))
+    }
+
+    /// Creates a new prepared actor with a name, overriding [`Actor::name`] for this instance.
+    ///
+    /// See [`spawn_named`] for more information.
+    pub fn new_named(name: impl Into<String>) -> Self {
+        Self::new_with_name(Cow::Owned(name.into()))
+    }
+
+    /// Creates a new prepared actor which receives signals from a mailbox created by the caller.
+    ///
+    /// See [`spawn_with_mailbox_parts`] for more information.
+    #[doc(hidden)]
+    pub fn new_with_mailbox(
+        mailbox: A::Mailbox,
+        mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
+    ) -> Self {
+        Self::new_with_parts(Cow::Borrowed(A::name()), mailbox, mailbox_rx)
+    }
+
+    pub(crate) fn new_with_name(name: Cow<'static, str>) -> Self {
         let (mailbox, mailbox_rx) = A::new_mailbox();
+        Self::new_with_parts(name, mailbox, mailbox_rx)
+    }
+
+    fn new_with_parts(
+        name: Cow<'static, str>,
+        mailbox: A::Mailbox,
+        mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
+    ) -> Self {
         let (abort_handle, abort_registration) = AbortHandle::new_pair();
         let links = Links::default();
         let startup_semaphore = Arc::new(Semaphore::new(0));
@@ -181,12 +545,20 @@ impl<A: Actor> PreparedActor<A> {
             abort_handle,
             links.clone(),
             startup_semaphore.clone(),
+            name,
+        );
+        let graph_guard = graph::register(
+            actor_ref.id(),
+            links,
+            startup_semaphore,
+            actor_ref.info.clone(),
         );
 
         PreparedActor {
             actor_ref,
             mailbox_rx,
             abort_registration,
+            graph_guard,
         }
     }
 
@@ -235,28 +607,143 @@ impl<A: Actor> PreparedActor<A> {
             self.actor_ref,
             self.mailbox_rx,
             self.abort_registration,
+            self.graph_guard,
         )
         .await
     }
 
+    /// Constructs the actor with [`InitActor::init`] and runs it in the current context, until the actor is stopped.
+    ///
+    /// Returns an error if the actor failed to be constructed, in which case any pending messages are rejected.
+    pub async fn run_init(self, args: A::Args) -> Result<(A, ActorStopReason), PanicError>
+    where
+        A: InitActor,
+    {
+        self.run_init_notify(args, None).await
+    }
+
+    /// Runs the actor like [`PreparedActor::run_init`], notifying `init_tx` of the result of [`InitActor::init`].
+    async fn run_init_notify(
+        self,
+        args: A::Args,
+        init_tx: Option<oneshot::Sender<Result<(), PanicError>>>,
+    ) -> Result<(A, ActorStopReason), PanicError>
+    where
+        A: InitActor,
+    {
+        let init_res = AssertUnwindSafe(A::init(args, self.actor_ref.clone()))
+            .catch_unwind()
+            .await
+            .map(|res| res.map_err(PanicError::new_hook_error::<A>))
+            .map_err(PanicError::new_panic)
+            .and_then(convert::identity);
+        if let Some(init_tx) = init_tx {
+            let _ = init_tx.send(init_res.as_ref().map(|_| ()).map_err(Clone::clone));
+        }
+
+        match init_res {
+            Ok(actor) => Ok(self.run(actor).await),
+            Err(err) => {
+                let PreparedActor {
+                    actor_ref,
+                    mut mailbox_rx,
+                    ..
+                } = self;
+                let reason = ActorStopReason::Panicked(err.clone());
+                log_actor_stop_reason(actor_ref.id(), actor_ref.name(), &reason);
+                reject_pending_signals::<A>(
+                    VecDeque::new(),
+                    &mut mailbox_rx,
+                    actor_ref.id(),
+                    actor_ref.name(),
+                )
+                .await;
+                Err(err)
+            }
+        }
+    }
+
+    /// Spawns the actor in a new background tokio task, constructing it with [`InitActor::init`].
+    ///
+    /// See [`spawn_init`] for more information.
+    pub fn spawn_init(self, args: A::Args) -> JoinHandle<Result<(A, ActorStopReason), PanicError>>
+    where
+        A: InitActor,
+    {
+        #[cfg(not(tokio_unstable))]
+        {
+            let info = self.actor_ref.info.clone();
+            tokio::spawn(migratable(
+                info,
+                CURRENT_ACTOR_ID.scope(self.actor_ref.id(), self.run_init(args)),
+            ))
+        }
+
+        #[cfg(tokio_unstable)]
+        {
+            let name = self.actor_ref.name().to_string();
+            tokio::task::Builder::new()
+                .name(&name)
+                .spawn(migratable(
+                    self.actor_ref.info.clone(),
+                    CURRENT_ACTOR_ID.scope(self.actor_ref.id(), self.run_init(args)),
+                ))
+                .unwrap()
+        }
+    }
+
     /// Spawns the actor in a new background tokio task, returning the `JoinHandle`.
     ///
+    /// Aborting the `JoinHandle` kills the actor, like [`ActorRef::kill`]. The handle resolves as cancelled
+    /// straight away, while the actor finishes stopping in the background, still calling [`Actor::on_stop`].
+    ///
     /// See [`spawn`] for more information.
     pub fn spawn(self, actor: A) -> JoinHandle<(A, ActorStopReason)> {
         #[cfg(not(tokio_unstable))]
         {
-            tokio::spawn(CURRENT_ACTOR_ID.scope(self.actor_ref.id(), self.run(actor)))
+            let info = self.actor_ref.info.clone();
+            tokio::spawn(migratable(
+                info,
+                CURRENT_ACTOR_ID.scope(self.actor_ref.id(), self.run(actor)),
+            ))
         }
 
         #[cfg(tokio_unstable)]
         {
+            let name = self.actor_ref.name().to_string();
             tokio::task::Builder::new()
-                .name(A::name())
-                .spawn(CURRENT_ACTOR_ID.scope(self.actor_ref.id(), self.run(actor)))
+                .name(&name)
+                .spawn(migratable(
+                    self.actor_ref.info.clone(),
+                    CURRENT_ACTOR_ID.scope(self.actor_ref.id(), self.run(actor)),
+                ))
                 .unwrap()
         }
     }
 
+    /// Spawns the actor using a custom [`Spawner`].
+    ///
+    /// See [`spawn_with`] for more information.
+    pub fn spawn_with<S>(self, spawner: &S, actor: A)
+    where
+        S: Spawner + Clone + Send + Sync + 'static,
+    {
+        let id = self.actor_ref.id();
+        let info = self.actor_ref.info.clone();
+        info.set_spawner(spawner);
+        spawner.spawn(
+            Migratable::new(
+                CURRENT_ACTOR_ID
+                    .scope(id, async move {
+                        self.run(actor).await;
+                    })
+                    .boxed(),
+                info,
+            )
+            .boxed(),
+        );
+    }
+
     /// Spawns the actor in a new background thread, returning the `JoinHandle`.
     ///
     /// See [`spawn_in_thread`] for more information.
@@ -267,10 +754,39 @@ impl<A: Actor> PreparedActor<A> {
         }
 
         std::thread::Builder::new()
-            .name(A::name().to_string())
+            .name(self.actor_ref.name().to_string())
+            .spawn({
+                let actor_ref = self.actor_ref.clone();
+                move || {
+                    handle.block_on(migratable(
+                        actor_ref.info.clone(),
+                        CURRENT_ACTOR_ID.scope(actor_ref.id(), self.run(actor)),
+                    ))
+                }
+            })
+            .unwrap()
+    }
+
+    /// Spawns the actor pinned to a new OS thread, running on a dedicated single threaded runtime, returning the
+    /// `JoinHandle`.
+    ///
+    /// See [`spawn_pinned`] for more information.
+    pub fn spawn_pinned(self, actor: A) -> thread::JoinHandle<(A, ActorStopReason)> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build runtime for pinned actor");
+
+        std::thread::Builder::new()
+            .name(self.actor_ref.name().to_string())
             .spawn({
                 let actor_ref = self.actor_ref.clone();
-                move || handle.block_on(CURRENT_ACTOR_ID.scope(actor_ref.id(), self.run(actor)))
+                move || {
+                    runtime.block_on(migratable(
+                        actor_ref.info.clone(),
+                        CURRENT_ACTOR_ID.scope(actor_ref.id(), self.run(actor)),
+                    ))
+                }
             })
             .unwrap()
     }
@@ -286,8 +802,9 @@ impl<A: Actor> Default for PreparedActor<A> {
 async fn run_actor_lifecycle<A, S>(
     mut actor: A,
     actor_ref: ActorRef<A>,
-    mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
+    mut mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
     abort_registration: AbortRegistration,
+    _graph_guard: graph::RegisteredGuard,
 ) -> (A, ActorStopReason)
 where
     A: Actor,
@@ -295,15 +812,16 @@ where
 {
     #[allow(unused_mut)]
     let mut id = actor_ref.id();
-    let name = A::name();
+    let info = actor_ref.info.clone();
+    let name: &str = &info.name;
     #[cfg(feature = "tracing")]
     trace!(%id, %name, "actor started");
 
     let start_res = AssertUnwindSafe(actor.on_start(actor_ref.clone()))
         .catch_unwind()
         .await
-        .map(|res| res.map_err(PanicError::new))
-        .map_err(PanicError::new_boxed)
+        .map(|res| res.map_err(PanicError::new_hook_error::<A>))
+        .map_err(PanicError::new_panic)
         .and_then(convert::identity);
 
     let mut startup_finished = false;
@@ -321,31 +839,45 @@ where
 
     if let Err(err) = start_res {
         let reason = ActorStopReason::Panicked(err);
+        {
+            let _links = links.lock().await;
+            info.links_closed.store(true, Ordering::Release);
+        }
+        info.state_watch.stop();
         let mut state = S::new_from_actor(actor, actor_ref.clone());
         let reason = state.on_shutdown(reason.clone()).await.unwrap_or(reason);
-        let mut actor = state.shutdown().await;
+        let (mut actor, unhandled) = state.shutdown(&reason).await;
         actor
             .on_stop(actor_ref.clone(), reason.clone())
             .await
             .unwrap();
         log_actor_stop_reason(id, name, &reason);
+        reject_pending_signals::<A>(unhandled, &mut mailbox_rx, id, name).await;
         return (actor, reason);
     }
 
     let mut state = S::new_from_actor(actor, actor_ref.clone());
 
     let reason = Abortable::new(
-        abortable_actor_loop(&mut state, mailbox_rx, startup_semaphore, startup_finished),
+        abortable_actor_loop(
+            &mut state,
+            &mut mailbox_rx,
+            startup_semaphore,
+            startup_finished,
+            &info.migration,
+        ),
         abort_registration,
     )
     .await
     .unwrap_or(ActorStopReason::Killed);
 
-    let mut actor = state.shutdown().await;
+    let (mut actor, unhandled) = state.shutdown(&reason).await;
+    info.state_watch.stop();
 
     let mut link_notificication_futures = FuturesUnordered::new();
     {
         let mut links = links.lock().await;
+        info.links_closed.store(true, Ordering::Release);
         #[allow(unused_variables)]
         for (link_actor_id, link) in links.drain() {
             match link {
@@ -395,28 +927,75 @@ where
     #[cfg(feature = "remote")]
     remote::REMOTE_REGISTRY.lock().await.remove(&id);
 
+    reject_pending_signals::<A>(unhandled, &mut mailbox_rx, id, name).await;
+
     on_stop_res.unwrap();
 
     (actor, reason)
 }
 
+/// Closes the mailbox and rejects any signals which were sent before the actor stopped, but never handled.
+///
+/// Signals which were already received from the mailbox, such as those buffered during startup, are rejected first.
+///
+/// This ensures pending ask requests get their message back through [`SendError::ActorNotRunning`],
+/// rather than only [`SendError::ActorStopped`].
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+async fn reject_pending_signals<A: Actor>(
+    unhandled: VecDeque<Signal<A>>,
+    mailbox_rx: &mut <A::Mailbox as Mailbox<A>>::Receiver,
+    id: ActorID,
+    name: &str,
+) {
+    #[cfg(feature = "tracing")]
+    let mut unprocessed = A::log_unprocessed_messages().then(BTreeMap::<&str, usize>::new);
+    let mut reject = |signal: Signal<A>| {
+        #[cfg(feature = "tracing")]
+        if let (Some(unprocessed), Signal::Message { message, .. }) = (&mut unprocessed, &signal) {
+            *unprocessed.entry(message.message_name()).or_default() += 1;
+        }
+        signal.reject();
+    };
+
+    unhandled.into_iter().for_each(&mut reject);
+    mailbox_rx.close();
+    while let Some(signal) = mailbox_rx.recv().await {
+        reject(signal);
+    }
+
+    #[cfg(feature = "tracing")]
+    if let Some(unprocessed) = unprocessed.filter(|unprocessed| !unprocessed.is_empty()) {
+        let count: usize = unprocessed.values().sum();
+        warn!(
+            %id,
+            %name,
+            count,
+            messages = ?unprocessed,
+            "actor stopped with {count} unprocessed messages in its mailbox"
+        );
+    }
+}
+
 async fn abortable_actor_loop<A, S>(
     state: &mut S,
-    mut mailbox_rx: <A::Mailbox as Mailbox<A>>::Receiver,
+    mailbox_rx: &mut <A::Mailbox as Mailbox<A>>::Receiver,
     startup_semaphore: Arc<Semaphore>,
     startup_finished: bool,
+    migration: &MigrationSlot,
 ) -> ActorStopReason
 where
     A: Actor,
     S: ActorState<A>,
 {
+    let deadline = A::max_lifetime().map(|max_lifetime| Instant::now() + max_lifetime);
     if startup_finished {
         if let Some(reason) = state.handle_startup_finished().await {
             return reason;
         }
     }
     loop {
-        let reason = recv_mailbox_loop(state, &mut mailbox_rx, &startup_semaphore).await;
+        let reason =
+            recv_mailbox_loop(state, mailbox_rx, &startup_semaphore, migration, deadline).await;
         if let Some(reason) = state.on_shutdown(reason).await {
             return reason;
         }
@@ -427,13 +1006,49 @@ async fn recv_mailbox_loop<A, S>(
     state: &mut S,
     mailbox_rx: &mut <A::Mailbox as Mailbox<A>>::Receiver,
     startup_semaphore: &Semaphore,
+    migration: &MigrationSlot,
+    deadline: Option<Instant>,
 ) -> ActorStopReason
 where
     A: Actor,
     S: ActorState<A>,
 {
+    if let Some(reason) = state.handle_resume(mailbox_rx.len()).await {
+        return reason;
+    }
+    let idle_timeout = A::idle_timeout();
     loop {
-        match mailbox_rx.recv().await {
+        if deadline.is_some_and(|deadline| deadline <= Instant::now()) {
+            // Checked before receiving, since a busy mailbox would always win the race below
+            return ActorStopReason::Normal;
+        }
+        migration.set_idle(true);
+        let signal = if idle_timeout.is_none() && deadline.is_none() {
+            mailbox_rx.recv().await
+        } else {
+            // Every signal received restarts the idle timeout, while the deadline stays fixed
+            let idle = async {
+                match idle_timeout {
+                    Some(timeout) => tokio::time::sleep(timeout).await,
+                    None => futures::future::pending().await,
+                }
+            };
+            let expired = async {
+                match deadline {
+                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                    None => futures::future::pending().await,
+                }
+            };
+            tokio::select! {
+                biased;
+                signal = mailbox_rx.recv() => signal,
+                // Stopping for a timeout is handled the same as the mailbox closing
+                _ = idle => None,
+                _ = expired => None,
+            }
+        };
+        migration.set_idle(false);
+        match signal {
             Some(Signal::StartupFinished) => {
                 startup_semaphore.add_permits(Semaphore::MAX_PERMITS);
                 if let Some(reason) = state.handle_startup_finished().await {
@@ -445,9 +1060,22 @@ where
                 actor_ref,
                 reply,
                 sent_within_actor,
+                extensions,
+                meta,
+                sent_at,
             }) => {
+                let mailbox_len = mailbox_rx.len();
                 if let Some(reason) = state
-                    .handle_message(message, actor_ref, reply, sent_within_actor)
+                    .handle_message(
+                        message,
+                        actor_ref,
+                        reply,
+                        sent_within_actor,
+                        extensions,
+                        meta,
+                        sent_at,
+                        mailbox_len,
+                    )
                     .await
                 {
                     return reason;
@@ -458,11 +1086,15 @@ where
                     return reason;
                 }
             }
-            Some(Signal::Stop) | None => {
+            Some(Signal::Flush(tx)) => state.handle_flush(tx),
+            Some(Signal::Fork(fork)) => state.handle_fork(fork),
+            Some(Signal::TaskPanicked(err)) => return ActorStopReason::Panicked(err),
+            Some(Signal::Stop) => {
                 if let Some(reason) = state.handle_stop().await {
                     return reason;
                 }
             }
+            None => return ActorStopReason::Normal,
         }
     }
 }
@@ -488,3 +1120,357 @@ fn log_actor_stop_reason(id: ActorID, name: &str, reason: &ActorStopReason) {
 
 #[cfg(not(feature = "tracing"))]
 fn log_actor_stop_reason(_id: ActorID, _name: &str, _reason: &ActorStopReason) {}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc, Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    use futures::future::{self, BoxFuture};
+    use tokio::{
+        runtime::{Handle, RuntimeFlavor},
+        sync::oneshot,
+    };
+
+    use crate::{
+        actor::{
+            spawn_pinned, spawn_with, spawn_with_init, Actor, ActorRef, InitActor, PreparedActor,
+        },
+        error::{ActorStopReason, BoxError, SpawnError},
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+        reply::DelegatedReply,
+    };
+
+    use super::Spawner;
+
+    #[derive(Clone)]
+    struct CountingSpawner {
+        handle: Handle,
+        spawned: Arc<AtomicUsize>,
+    }
+
+    impl Spawner for CountingSpawner {
+        fn spawn(&self, future: BoxFuture<'static, ()>) {
+            self.spawned.fetch_add(1, Ordering::Relaxed);
+            self.handle.spawn(future);
+        }
+    }
+
+    #[tokio::test]
+    async fn spawn_with_custom_spawner() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            fn abort_abandoned_requests() -> bool {
+                true
+            }
+        }
+
+        struct ThreadName;
+
+        impl Message<ThreadName> for MyActor {
+            type Reply = Option<String>;
+
+            async fn handle(
+                &mut self,
+                _msg: ThreadName,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                thread::current().name().map(ToString::to_string)
+            }
+        }
+
+        struct Delegate;
+
+        impl Message<Delegate> for MyActor {
+            type Reply = DelegatedReply<u32>;
+
+            async fn handle(
+                &mut self,
+                _msg: Delegate,
+                mut ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                let (delegated_reply, reply_sender) = ctx.reply_sender();
+                if let Some(tx) = reply_sender {
+                    tokio::spawn(async move {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        tx.send(7);
+                    });
+                }
+                delegated_reply
+            }
+        }
+
+        struct SpawnTask(oneshot::Sender<Option<String>>);
+
+        impl Message<SpawnTask> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                SpawnTask(tx): SpawnTask,
+                mut ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                ctx.spawn_task(|_| async move {
+                    let _ = tx.send(thread::current().name().map(ToString::to_string));
+                });
+                ctx.spawn_linked_task(|_| async {});
+            }
+        }
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("custom-spawner")
+            .enable_all()
+            .build()?;
+        let spawner = CountingSpawner {
+            handle: runtime.handle().clone(),
+            spawned: Arc::new(AtomicUsize::new(0)),
+        };
+
+        // The actor runs on the spawner's runtime
+        let actor_ref = spawn_with(&spawner, MyActor);
+        assert_eq!(
+            actor_ref.ask(ThreadName).await?.as_deref(),
+            Some("custom-spawner")
+        );
+        assert_eq!(spawner.spawned.load(Ordering::Relaxed), 1);
+
+        // Delegated replies are passed on by a task spawned with the same spawner
+        assert_eq!(actor_ref.ask(Delegate).await?, 7);
+        assert_eq!(spawner.spawned.load(Ordering::Relaxed), 2);
+
+        // So are background tasks spawned by the actor
+        let (tx, rx) = oneshot::channel();
+        actor_ref.ask(SpawnTask(tx)).await?;
+        assert_eq!(rx.await?.as_deref(), Some("custom-spawner"));
+        assert_eq!(spawner.spawned.load(Ordering::Relaxed), 4);
+
+        actor_ref.kill();
+        actor_ref.wait_for_stop().await;
+        runtime.shutdown_background();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spawn_with_init_dropped_during_init() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        impl InitActor for MyActor {
+            type Args = ();
+
+            async fn init(_args: (), _actor_ref: ActorRef<Self>) -> Result<Self, BoxError> {
+                future::pending().await
+            }
+        }
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .build()?;
+
+        // The actor's task is spawned on the other runtime, and never finishes init
+        let mut spawn = {
+            let _guard = runtime.enter();
+            let mut spawn = Box::pin(spawn_with_init::<MyActor>(()));
+            assert!(futures::poll!(&mut spawn).is_pending());
+            spawn
+        };
+
+        // Shutting down the runtime drops the actor's task
+        runtime.shutdown_background();
+        assert!(matches!(
+            (&mut spawn).await,
+            Err(SpawnError::ActorNotRunning)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spawn_pinned_handles_messages_on_one_thread() -> Result<(), Box<dyn std::error::Error>>
+    {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct CurrentThread;
+
+        impl Message<CurrentThread> for MyActor {
+            type Reply = Box<std::thread::ThreadId>;
+
+            async fn handle(
+                &mut self,
+                _: CurrentThread,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                tokio::task::yield_now().await;
+                Box::new(std::thread::current().id())
+            }
+        }
+
+        // Pinned actors don't need a multi threaded runtime
+        let actor_ref = spawn_pinned(MyActor);
+        let first = actor_ref.ask(CurrentThread).await?;
+        assert_ne!(*first, std::thread::current().id());
+        for _ in 0..10 {
+            assert_eq!(actor_ref.ask(CurrentThread).await?, first);
+        }
+
+        actor_ref.stop_gracefully().await?;
+        actor_ref.wait_for_stop().await;
+
+        Ok(())
+    }
+
+    #[test]
+    fn spawn_pinned_thread_stops_with_actor() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct CurrentThread;
+
+        impl Message<CurrentThread> for MyActor {
+            type Reply = Box<(thread::ThreadId, Option<String>, RuntimeFlavor)>;
+
+            async fn handle(
+                &mut self,
+                _: CurrentThread,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                let thread = thread::current();
+                Box::new((
+                    thread.id(),
+                    thread.name().map(ToString::to_string),
+                    Handle::current().runtime_flavor(),
+                ))
+            }
+        }
+
+        struct SpawnTask(mpsc::Sender<()>);
+
+        impl Message<SpawnTask> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                SpawnTask(tx): SpawnTask,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                struct NotifyOnDrop(mpsc::Sender<()>);
+
+                impl Drop for NotifyOnDrop {
+                    fn drop(&mut self) {
+                        let _ = self.0.send(());
+                    }
+                }
+
+                let guard = NotifyOnDrop(tx);
+                tokio::spawn(async move {
+                    let _guard = guard;
+                    future::pending::<()>().await;
+                });
+            }
+        }
+
+        // Spawned without any runtime running
+        let prepared_actor = PreparedActor::new_named("pinned");
+        let actor_ref = prepared_actor.actor_ref().clone();
+        let handle = prepared_actor.spawn_pinned(MyActor);
+
+        futures::executor::block_on(async {
+            let (thread_id, name, flavor) = *actor_ref.ask(CurrentThread).await?;
+            assert_eq!(thread_id, handle.thread().id());
+            assert_ne!(thread_id, thread::current().id());
+            assert_eq!(name.as_deref(), Some("pinned"));
+            assert_eq!(flavor, RuntimeFlavor::CurrentThread);
+
+            let (tx, rx) = mpsc::channel();
+            actor_ref.ask(SpawnTask(tx)).await?;
+            actor_ref.stop_gracefully().await?;
+
+            // The thread exits once the actor stops, dropping the runtime with the actor's tasks
+            let (_, reason) = handle.join().unwrap();
+            assert_eq!(reason, ActorStopReason::Normal);
+            assert!(rx.recv_timeout(Duration::from_secs(1)).is_ok());
+
+            Ok(())
+        })
+    }
+
+    #[tokio::test]
+    async fn idle_timeout_and_max_lifetime() -> Result<(), Box<dyn std::error::Error>> {
+        struct Session;
+
+        impl Actor for Session {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            fn idle_timeout() -> Option<Duration> {
+                Some(Duration::from_millis(100))
+            }
+        }
+
+        struct Touch;
+
+        impl Message<Touch> for Session {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Touch, _ctx: Context<'_, Self, Self::Reply>) {}
+        }
+
+        let prepared = PreparedActor::new();
+        let session = prepared.actor_ref().clone();
+        let handle = tokio::spawn(prepared.spawn(Session));
+        // Each message restarts the timeout, keeping the session alive past it
+        for _ in 0..4 {
+            tokio::time::sleep(Duration::from_millis(40)).await;
+            session.tell(Touch).await?;
+        }
+        assert!(session.is_alive());
+        let (_, reason) = handle.await??;
+        assert_eq!(reason, ActorStopReason::Normal);
+
+        struct Connection;
+
+        impl Actor for Connection {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            fn max_lifetime() -> Option<Duration> {
+                Some(Duration::from_millis(50))
+            }
+        }
+
+        impl Message<Touch> for Connection {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Touch, _ctx: Context<'_, Self, Self::Reply>) {}
+        }
+
+        // A busy actor still stops once its lifetime is reached
+        let connection = crate::spawn(Connection);
+        while connection.tell(Touch).await.is_ok() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        connection.wait_for_stop().await;
+
+        Ok(())
+    }
+}
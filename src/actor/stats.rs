@@ -0,0 +1,96 @@
+use std::{
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+/// A snapshot of an actor's runtime statistics, returned by [`ActorRef::stats`](crate::actor::ActorRef::stats).
+///
+/// Statistics are maintained with atomic counters as the actor runs, so taking a snapshot is cheap enough
+/// to be polled periodically by a monitoring task.
+#[derive(Clone, Copy, Debug)]
+pub struct ActorStats {
+    /// The number of messages the actor has finished handling.
+    pub messages_handled: u64,
+    /// The number of times the actor has panicked, counted each time [`Actor::on_panic`](crate::Actor::on_panic) is called.
+    pub panics: u64,
+    /// The number of signals which were waiting in the mailbox when the last message was received.
+    pub mailbox_len: usize,
+    /// The time elapsed since the actor was spawned.
+    pub uptime: Duration,
+    /// The instant the actor last started handling a message, or `None` if no messages have been handled.
+    pub last_message_at: Option<Instant>,
+    /// How long the last message waited in the mailbox after being sent, before the actor started handling it.
+    ///
+    /// A long queue wait, compared to the time spent handling messages, indicates the actor itself is the bottleneck,
+    /// with messages arriving faster than it can handle them.
+    pub queue_wait: Duration,
+    /// The total time every message has waited in the mailbox before being handled.
+    ///
+    /// Dividing this by the number of messages handled gives the mean queue wait.
+    pub total_queue_wait: Duration,
+}
+
+#[derive(Debug)]
+pub(crate) struct StatsCounters {
+    started_at: Instant,
+    messages_handled: AtomicU64,
+    panics: AtomicU64,
+    mailbox_len: AtomicUsize,
+    /// Nanoseconds since `started_at`, offset by one so that zero means no message has been received.
+    last_message_at: AtomicU64,
+    queue_wait: AtomicU64,
+    total_queue_wait: AtomicU64,
+}
+
+impl StatsCounters {
+    pub(crate) fn new() -> Self {
+        StatsCounters {
+            started_at: Instant::now(),
+            messages_handled: AtomicU64::new(0),
+            panics: AtomicU64::new(0),
+            mailbox_len: AtomicUsize::new(0),
+            last_message_at: AtomicU64::new(0),
+            queue_wait: AtomicU64::new(0),
+            total_queue_wait: AtomicU64::new(0),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn message_started(&self, mailbox_len: usize, queue_wait: Duration) {
+        let nanos = self.started_at.elapsed().as_nanos() as u64;
+        self.last_message_at
+            .store(nanos.saturating_add(1), Ordering::Relaxed);
+        self.mailbox_len.store(mailbox_len, Ordering::Relaxed);
+        let queue_wait = queue_wait.as_nanos() as u64;
+        self.queue_wait.store(queue_wait, Ordering::Relaxed);
+        self.total_queue_wait
+            .fetch_add(queue_wait, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn message_handled(&self) {
+        self.messages_handled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn panicked(&self) {
+        self.panics.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> ActorStats {
+        let last_message_at = match self.last_message_at.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(self.started_at + Duration::from_nanos(nanos - 1)),
+        };
+
+        ActorStats {
+            messages_handled: self.messages_handled.load(Ordering::Relaxed),
+            panics: self.panics.load(Ordering::Relaxed),
+            mailbox_len: self.mailbox_len.load(Ordering::Relaxed),
+            uptime: self.started_at.elapsed(),
+            last_message_at,
+            queue_wait: Duration::from_nanos(self.queue_wait.load(Ordering::Relaxed)),
+            total_queue_wait: Duration::from_nanos(self.total_queue_wait.load(Ordering::Relaxed)),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    any::Any,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, PoisonError,
+    },
+};
+
+use tokio::sync::watch;
+
+use crate::Actor;
+
+/// An actor whose state can be observed with [`ActorRef::subscribe`](super::ActorRef::subscribe).
+///
+/// After each message is handled, the actor takes a snapshot of its state with [`state`](Watchable::state), and
+/// publishes it to subscribers if it differs from the last snapshot published. Snapshots are only taken once the
+/// actor has been subscribed to.
+///
+/// Subscribers only ever see the latest state. If the state changes several times before a subscriber receives
+/// the next update, the intermediate states are skipped, so slow subscribers never hold the actor back.
+///
+/// # Example
+///
+/// ```
+/// use futures::StreamExt;
+/// use kameo::actor::Watchable;
+/// use kameo::message::{Context, Message};
+///
+/// #[derive(kameo::Actor, Default)]
+/// struct Counter {
+///     count: i64,
+/// }
+///
+/// impl Watchable for Counter {
+///     type State = i64;
+///
+///     fn state(&self) -> Self::State {
+///         self.count
+///     }
+/// }
+///
+/// struct Inc;
+///
+/// impl Message<Inc> for Counter {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, _: Inc, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         self.count += 1;
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::spawn(Counter::default());
+/// let mut updates = actor_ref.subscribe();
+///
+/// actor_ref.ask(Inc).await?;
+/// assert_eq!(updates.next().await, Some(1));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub trait Watchable: Actor {
+    /// A snapshot of the actor's state, sent to subscribers when it changes.
+    type State: Clone + PartialEq + Send + Sync + 'static;
+
+    /// Returns a snapshot of the actor's current state.
+    ///
+    /// This is called after every message once the actor has been subscribed to, so it should be cheap. For large
+    /// states, consider returning a summary or an [`Arc`](std::sync::Arc) which is replaced on each change.
+    fn state(&self) -> Self::State;
+}
+
+/// The channel publishing an actor's state to its subscribers.
+#[derive(Default)]
+pub(crate) struct StateWatch {
+    /// Whether the actor has ever been subscribed to, avoiding the lock for actors which are never watched.
+    watched: AtomicBool,
+    channel: Mutex<Channel>,
+}
+
+#[derive(Default)]
+enum Channel {
+    #[default]
+    Unwatched,
+    Watched {
+        /// The `watch::Sender<Option<A::State>>`.
+        sender: Box<dyn Any + Send + Sync>,
+        publish: fn(&dyn Any, &dyn Any),
+    },
+    Stopped,
+}
+
+impl StateWatch {
+    /// Publishes the actor's state to subscribers, if it has any.
+    #[inline]
+    pub(crate) fn publish(&self, actor: &dyn Any) {
+        if !self.watched.load(Ordering::Acquire) {
+            return;
+        }
+
+        let channel = self.channel.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Channel::Watched { sender, publish } = &*channel {
+            publish(actor, sender.as_ref());
+        }
+    }
+
+    pub(crate) fn subscribe<A: Watchable>(&self) -> watch::Receiver<Option<A::State>> {
+        let mut channel = self.channel.lock().unwrap_or_else(PoisonError::into_inner);
+        match &*channel {
+            Channel::Unwatched => {
+                let (tx, rx) = watch::channel(None);
+                *channel = Channel::Watched {
+                    sender: Box::new(tx),
+                    publish: publish::<A>,
+                };
+                self.watched.store(true, Ordering::Release);
+                rx
+            }
+            Channel::Watched { sender, .. } => sender
+                .downcast_ref::<watch::Sender<Option<A::State>>>()
+                .expect("state watch should match the actor type")
+                .subscribe(),
+            Channel::Stopped => {
+                // The sender is dropped straight away, so the subscription ends without any updates
+                watch::channel(None).1
+            }
+        }
+    }
+
+    /// Closes the channel once the actor has stopped, ending every subscription.
+    pub(crate) fn stop(&self) {
+        *self.channel.lock().unwrap_or_else(PoisonError::into_inner) = Channel::Stopped;
+        self.watched.store(false, Ordering::Release);
+    }
+}
+
+fn publish<A: Watchable>(actor: &dyn Any, sender: &dyn Any) {
+    let (Some(actor), Some(sender)) = (
+        actor.downcast_ref::<A>(),
+        sender.downcast_ref::<watch::Sender<Option<A::State>>>(),
+    ) else {
+        return;
+    };
+
+    sender.send_if_modified(|current| {
+        let state = actor.state();
+        if current.as_ref() == Some(&state) {
+            return false;
+        }
+        *current = Some(state);
+        true
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use crate::{
+        actor::{Actor, Watchable},
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+    };
+
+    #[tokio::test]
+    async fn subscribe_publishes_state_changes() -> Result<(), Box<dyn std::error::Error>> {
+        struct Counter {
+            count: i64,
+        }
+
+        impl Actor for Counter {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        impl Watchable for Counter {
+            type State = i64;
+
+            fn state(&self) -> Self::State {
+                self.count
+            }
+        }
+
+        struct Add(i64);
+
+        impl Message<Add> for Counter {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                Add(amount): Add,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.count += amount;
+            }
+        }
+
+        let actor_ref = crate::spawn(Counter { count: 0 });
+        let mut updates = actor_ref.subscribe();
+
+        // Messages which don't change the state aren't published
+        actor_ref.tell(Add(0)).await?;
+        actor_ref.tell(Add(1)).await?;
+        actor_ref.flush().await?;
+        assert_eq!(updates.next().await, Some(1));
+
+        // Changes made before the subscriber catches up are coalesced into the latest state
+        actor_ref.tell(Add(2)).await?;
+        actor_ref.tell(Add(3)).await?;
+        actor_ref.flush().await?;
+        assert_eq!(updates.next().await, Some(6));
+
+        // The stream ends once the actor stops
+        actor_ref.stop_gracefully().await?;
+        actor_ref.wait_for_stop().await;
+        assert_eq!(updates.next().await, None);
+
+        // Subscribing to a stopped actor ends straight away
+        assert_eq!(actor_ref.subscribe().next().await, None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,296 @@
+//! Provides a watchdog for detecting actors which appear to be stuck handling a message.
+//!
+//! The watchdog is a passive observability tool. Once started, every message handled by any actor is timestamped,
+//! and a background thread periodically scans for handlers which have been running for longer than a threshold.
+//! Each overdue handler is reported once, with the actor's id, name, and the type of message being handled.
+//!
+//! The watchdog never aborts a handler, since doing so could lose data. For enforcing limits on how long a request
+//! may take, use reply timeouts instead.
+//!
+//! Since the watchdog runs on its own thread, it can detect handlers which block the thread they run on,
+//! as well as handlers which are waiting on a future that never completes.
+//!
+//! When the watchdog is not started, the only overhead per message is a single atomic load.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use kameo::actor::watchdog::Watchdog;
+//!
+//! Watchdog::new(Duration::from_secs(5))
+//!     .on_stuck(|stuck| {
+//!         eprintln!(
+//!             "actor {} ({}) has been handling {} for {:?}",
+//!             stuck.id, stuck.actor_name, stuck.message_name, stuck.elapsed,
+//!         );
+//!     })
+//!     .start();
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "tracing")]
+use tracing::warn;
+
+use super::{actor_ref::ActorInfo, ActorID};
+
+type StuckHook = Box<dyn Fn(&StuckActor) + Send + Sync>;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static IN_FLIGHT: OnceLock<Mutex<HashMap<ActorID, InFlight>>> = OnceLock::new();
+
+struct InFlight {
+    info: Arc<ActorInfo>,
+    message_name: &'static str,
+    started_at: Instant,
+    reported: bool,
+}
+
+/// Information about an actor which has been handling a single message for longer than the watchdog threshold.
+#[derive(Clone, Debug)]
+pub struct StuckActor {
+    /// The id of the actor.
+    pub id: ActorID,
+    /// The name of the actor, as returned by [`ActorRef::name`](crate::actor::ActorRef::name).
+    ///
+    /// This is the name the actor was spawned with, such as with [`spawn_named`](crate::actor::spawn_named),
+    /// falling back to [`Actor::name`](crate::Actor::name).
+    pub actor_name: Arc<str>,
+    /// The type name of the message being handled.
+    pub message_name: &'static str,
+    /// How long the actor has been handling the message.
+    pub elapsed: Duration,
+}
+
+/// A watchdog which reports actors that have been handling a message for longer than a threshold.
+///
+/// See the [module level documentation](self) for more information.
+pub struct Watchdog {
+    threshold: Duration,
+    interval: Duration,
+    on_stuck: Option<StuckHook>,
+}
+
+impl Watchdog {
+    /// Creates a new watchdog which reports handlers running for longer than `threshold`.
+    ///
+    /// By default, handlers are checked every half of the threshold, and stuck actors are logged as a
+    /// warning with `tracing`.
+    pub fn new(threshold: Duration) -> Self {
+        Watchdog {
+            threshold,
+            interval: threshold / 2,
+            on_stuck: None,
+        }
+    }
+
+    /// Sets how often in-flight handlers are checked.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets a hook to be called for each stuck actor, instead of logging a warning.
+    ///
+    /// The hook is called from the watchdog's thread, and should not block.
+    pub fn on_stuck(mut self, f: impl Fn(&StuckActor) + Send + Sync + 'static) -> Self {
+        self.on_stuck = Some(Box::new(f));
+        self
+    }
+
+    /// Starts the watchdog in a background thread, which runs for the lifetime of the program.
+    ///
+    /// Only a single watchdog can be running at a time. Returns `false` if a watchdog was already started.
+    pub fn start(self) -> bool {
+        if ENABLED.swap(true, Ordering::AcqRel) {
+            return false;
+        }
+
+        thread::Builder::new()
+            .name("kameo-watchdog".to_string())
+            .spawn(move || loop {
+                thread::sleep(self.interval);
+                self.check();
+            })
+            .expect("failed to spawn watchdog thread");
+
+        true
+    }
+
+    fn check(&self) {
+        let stuck: Vec<_> = {
+            let mut in_flight = in_flight().lock().unwrap_or_else(|err| err.into_inner());
+            in_flight
+                .iter_mut()
+                .filter_map(|(id, entry)| {
+                    let elapsed = entry.started_at.elapsed();
+                    if entry.reported || elapsed < self.threshold {
+                        return None;
+                    }
+                    entry.reported = true;
+                    Some(StuckActor {
+                        id: *id,
+                        actor_name: Arc::from(&*entry.info.name),
+                        message_name: entry.message_name,
+                        elapsed,
+                    })
+                })
+                .collect()
+        };
+
+        for stuck in &stuck {
+            if let Some(on_stuck) = &self.on_stuck {
+                on_stuck(stuck);
+                continue;
+            }
+
+            #[cfg(feature = "tracing")]
+            warn!(
+                id = %stuck.id,
+                name = %stuck.actor_name,
+                message = %stuck.message_name,
+                elapsed = ?stuck.elapsed,
+                "actor appears to be stuck handling a message"
+            );
+        }
+    }
+}
+
+impl fmt::Debug for Watchdog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Watchdog")
+            .field("threshold", &self.threshold)
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+/// Tracks an in-flight message handler until the returned guard is dropped.
+///
+/// Returns `None` if no watchdog has been started.
+#[inline]
+pub(crate) fn track(
+    id: ActorID,
+    info: &Arc<ActorInfo>,
+    message_name: &'static str,
+) -> Option<InFlightGuard> {
+    if !ENABLED.load(Ordering::Acquire) {
+        return None;
+    }
+
+    in_flight()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(
+            id,
+            InFlight {
+                info: info.clone(),
+                message_name,
+                started_at: Instant::now(),
+                reported: false,
+            },
+        );
+
+    Some(InFlightGuard { id })
+}
+
+fn in_flight() -> &'static Mutex<HashMap<ActorID, InFlight>> {
+    IN_FLIGHT.get_or_init(Default::default)
+}
+
+/// Removes an actor's in-flight handler from the watchdog when dropped.
+pub(crate) struct InFlightGuard {
+    id: ActorID,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        in_flight()
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use crate::{
+        actor::{spawn_named, Actor},
+        mailbox::unbounded::UnboundedMailbox,
+        message::{Context, Message},
+    };
+
+    use super::{StuckActor, Watchdog};
+
+    #[tokio::test]
+    async fn watchdog_reports_stuck_actors() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Block(Duration);
+
+        impl Message<Block> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                Block(duration): Block,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                tokio::time::sleep(duration).await;
+            }
+        }
+
+        let stuck: Arc<Mutex<Vec<StuckActor>>> = Arc::default();
+        assert!(Watchdog::new(Duration::from_millis(50))
+            .interval(Duration::from_millis(10))
+            .on_stuck({
+                let stuck = stuck.clone();
+                move |actor| stuck.lock().unwrap().push(actor.clone())
+            })
+            .start());
+        assert!(!Watchdog::new(Duration::from_millis(50)).start());
+
+        let actor_ref = spawn_named(MyActor, "worker-1");
+
+        // Handlers finishing within the threshold aren't reported
+        actor_ref.ask(Block(Duration::from_millis(5))).await?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(stuck.lock().unwrap().iter().all(|s| s.id != actor_ref.id()));
+
+        // Overdue handlers are reported once, with the actor's instance name
+        actor_ref.ask(Block(Duration::from_millis(200))).await?;
+        let reports: Vec<_> = stuck
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.id == actor_ref.id())
+            .cloned()
+            .collect();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(&*reports[0].actor_name, "worker-1");
+        assert!(reports[0].message_name.ends_with("Block"));
+        assert!(reports[0].elapsed >= Duration::from_millis(50));
+
+        Ok(())
+    }
+}
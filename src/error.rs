@@ -6,9 +6,12 @@
 
 use std::{
     any::{self, Any},
+    backtrace::Backtrace,
+    cell::Cell,
     cmp, error, fmt,
     hash::{Hash, Hasher},
     sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, SystemTime},
 };
 
 use serde::{Deserialize, Serialize};
@@ -25,29 +28,143 @@ pub type BoxError = Box<dyn error::Error + Send + Sync + 'static>;
 pub type BoxSendError = SendError<Box<dyn any::Any + Send>, Box<dyn any::Any + Send>>;
 
 /// Error that can occur when sending a message to an actor.
-#[derive(Clone, Copy, PartialEq, Eq)]
+///
+/// `SendError` implements [`std::error::Error`] when the handler error does, so it can be propagated with `?`
+/// into a [`BoxError`] when the message is `Send + Sync`. For other messages, use [`SendError::into_box_error`].
+///
+/// New variants may be added in future releases, so matches should include a wildcard arm.
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum SendError<M = (), E = Infallible> {
     /// The actor isn't running.
     ActorNotRunning(M),
     /// The actor panicked or was stopped before a reply could be received.
     ActorStopped,
+    /// The actor stopped while handling the message, with the reason it stopped.
+    ///
+    /// Callers can use the reason to decide whether to retry, such as retrying if the actor was killed,
+    /// but not if it panicked.
+    ActorDied(ActorStopReason),
     /// The actors mailbox is full.
     MailboxFull(M),
+    /// The message is larger than the actor accepts.
+    ///
+    /// See [`Actor::max_message_bytes`].
+    TooLarge(M),
+    /// The actor has been closed, and no longer accepts messages.
+    ///
+    /// See [`ActorRef::close`](crate::actor::ActorRef::close).
+    Closed(M),
+    /// The circuit breaker guarding the actor is open, so the message wasn't sent.
+    ///
+    /// See [`CircuitBreaker`](crate::actor::circuit_breaker::CircuitBreaker).
+    CircuitOpen(M),
     /// An error returned by the actor's message handler.
     HandlerError(E),
     /// Timed out waiting for a reply.
     Timeout(Option<M>),
+    /// The actor's message handler declined to reply.
+    ///
+    /// See [`Context::decline`](crate::message::Context::decline).
+    NoReply,
 }
 
 impl<M, E> SendError<M, E> {
+    /// Returns `true` if the variant is [`ActorNotRunning`](SendError::ActorNotRunning).
+    pub fn is_actor_not_running(&self) -> bool {
+        matches!(self, SendError::ActorNotRunning(_))
+    }
+
+    /// Returns `true` if the actor stopped before a reply was received.
+    ///
+    /// This is the case for both [`ActorStopped`](SendError::ActorStopped) and [`ActorDied`](SendError::ActorDied).
+    pub fn is_actor_stopped(&self) -> bool {
+        matches!(self, SendError::ActorStopped | SendError::ActorDied(_))
+    }
+
+    /// Returns `true` if the variant is [`MailboxFull`](SendError::MailboxFull).
+    pub fn is_mailbox_full(&self) -> bool {
+        matches!(self, SendError::MailboxFull(_))
+    }
+
+    /// Returns `true` if the variant is [`TooLarge`](SendError::TooLarge).
+    pub fn is_too_large(&self) -> bool {
+        matches!(self, SendError::TooLarge(_))
+    }
+
+    /// Returns `true` if the variant is [`Closed`](SendError::Closed).
+    pub fn is_closed(&self) -> bool {
+        matches!(self, SendError::Closed(_))
+    }
+
+    /// Returns `true` if the variant is [`CircuitOpen`](SendError::CircuitOpen).
+    pub fn is_circuit_open(&self) -> bool {
+        matches!(self, SendError::CircuitOpen(_))
+    }
+
+    /// Returns `true` if the variant is [`HandlerError`](SendError::HandlerError).
+    pub fn is_handler_error(&self) -> bool {
+        matches!(self, SendError::HandlerError(_))
+    }
+
+    /// Returns `true` if the variant is [`Timeout`](SendError::Timeout).
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, SendError::Timeout(_))
+    }
+
+    /// Returns `true` if the variant is [`NoReply`](SendError::NoReply).
+    pub fn is_no_reply(&self) -> bool {
+        matches!(self, SendError::NoReply)
+    }
+
+    /// Converts the error into a [`BoxError`], dropping the message if it contains one.
+    ///
+    /// This is useful for propagating errors with `?` when the message isn't `Sync`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::error::{BoxError, SendError};
+    /// # use kameo::message::{Context, Message};
+    /// #
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # struct Msg(std::cell::Cell<u32>);
+    /// #
+    /// # impl Message<Msg> for MyActor {
+    /// #     type Reply = u32;
+    /// #     async fn handle(&mut self, msg: Msg, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { msg.0.get() }
+    /// # }
+    ///
+    /// async fn get(actor_ref: &kameo::actor::ActorRef<MyActor>) -> Result<u32, BoxError> {
+    ///     let n = actor_ref
+    ///         .ask(Msg(std::cell::Cell::new(1)))
+    ///         .await
+    ///         .map_err(SendError::into_box_error)?;
+    ///     Ok(n)
+    /// }
+    /// ```
+    pub fn into_box_error(self) -> BoxError
+    where
+        E: fmt::Debug + fmt::Display + Send + Sync + 'static,
+    {
+        Box::new(self.map_msg(|_| ()))
+    }
+
     /// Clears in inner data back to `()`.
     pub fn reset(self) -> SendError<(), ()> {
         match self {
             SendError::ActorNotRunning(_) => SendError::ActorNotRunning(()),
             SendError::ActorStopped => SendError::ActorStopped,
+            SendError::ActorDied(reason) => SendError::ActorDied(reason),
             SendError::MailboxFull(_) => SendError::MailboxFull(()),
+            SendError::TooLarge(_) => SendError::TooLarge(()),
+            SendError::Closed(_) => SendError::Closed(()),
+            SendError::CircuitOpen(_) => SendError::CircuitOpen(()),
             SendError::HandlerError(_) => SendError::HandlerError(()),
             SendError::Timeout(_) => SendError::Timeout(None),
+            SendError::NoReply => SendError::NoReply,
         }
     }
 
@@ -59,9 +176,14 @@ impl<M, E> SendError<M, E> {
         match self {
             SendError::ActorNotRunning(msg) => SendError::ActorNotRunning(f(msg)),
             SendError::ActorStopped => SendError::ActorStopped,
+            SendError::ActorDied(reason) => SendError::ActorDied(reason),
             SendError::MailboxFull(msg) => SendError::MailboxFull(f(msg)),
+            SendError::TooLarge(msg) => SendError::TooLarge(f(msg)),
+            SendError::Closed(msg) => SendError::Closed(f(msg)),
+            SendError::CircuitOpen(msg) => SendError::CircuitOpen(f(msg)),
             SendError::HandlerError(err) => SendError::HandlerError(err),
             SendError::Timeout(msg) => SendError::Timeout(msg.map(f)),
+            SendError::NoReply => SendError::NoReply,
         }
     }
 
@@ -73,9 +195,14 @@ impl<M, E> SendError<M, E> {
         match self {
             SendError::ActorNotRunning(msg) => SendError::ActorNotRunning(msg),
             SendError::ActorStopped => SendError::ActorStopped,
+            SendError::ActorDied(reason) => SendError::ActorDied(reason),
             SendError::MailboxFull(msg) => SendError::MailboxFull(msg),
+            SendError::TooLarge(msg) => SendError::TooLarge(msg),
+            SendError::Closed(msg) => SendError::Closed(msg),
+            SendError::CircuitOpen(msg) => SendError::CircuitOpen(msg),
             SendError::HandlerError(err) => SendError::HandlerError(op(err)),
             SendError::Timeout(msg) => SendError::Timeout(msg),
+            SendError::NoReply => SendError::NoReply,
         }
     }
 
@@ -88,11 +215,16 @@ impl<M, E> SendError<M, E> {
         match self {
             SendError::ActorNotRunning(err) => SendError::ActorNotRunning(Box::new(err)),
             SendError::ActorStopped => SendError::ActorStopped,
+            SendError::ActorDied(reason) => SendError::ActorDied(reason),
             SendError::MailboxFull(msg) => SendError::MailboxFull(Box::new(msg)),
+            SendError::TooLarge(msg) => SendError::TooLarge(Box::new(msg)),
+            SendError::Closed(msg) => SendError::Closed(Box::new(msg)),
+            SendError::CircuitOpen(msg) => SendError::CircuitOpen(Box::new(msg)),
             SendError::HandlerError(err) => SendError::HandlerError(Box::new(err)),
             SendError::Timeout(msg) => {
                 SendError::Timeout(msg.map(|msg| Box::new(msg) as Box<dyn any::Any + Send>))
             }
+            SendError::NoReply => SendError::NoReply,
         }
     }
 }
@@ -108,13 +240,25 @@ impl<M, E> SendError<M, SendError<M, E>> {
             SendError::ActorStopped | SendError::HandlerError(SendError::ActorStopped) => {
                 SendError::ActorStopped
             }
+            SendError::ActorDied(reason)
+            | SendError::HandlerError(SendError::ActorDied(reason)) => SendError::ActorDied(reason),
             SendError::MailboxFull(msg) | SendError::HandlerError(SendError::MailboxFull(msg)) => {
                 SendError::MailboxFull(msg)
             }
+            SendError::TooLarge(msg) | SendError::HandlerError(SendError::TooLarge(msg)) => {
+                SendError::TooLarge(msg)
+            }
+            SendError::Closed(msg) | SendError::HandlerError(SendError::Closed(msg)) => {
+                SendError::Closed(msg)
+            }
+            SendError::CircuitOpen(msg) | SendError::HandlerError(SendError::CircuitOpen(msg)) => {
+                SendError::CircuitOpen(msg)
+            }
             SendError::HandlerError(SendError::HandlerError(err)) => SendError::HandlerError(err),
             SendError::Timeout(msg) | SendError::HandlerError(SendError::Timeout(msg)) => {
                 SendError::Timeout(msg)
             }
+            SendError::NoReply | SendError::HandlerError(SendError::NoReply) => SendError::NoReply,
         }
     }
 }
@@ -129,9 +273,14 @@ impl BoxSendError {
         match self {
             SendError::ActorNotRunning(err) => SendError::ActorNotRunning(*err.downcast().unwrap()),
             SendError::ActorStopped => SendError::ActorStopped,
+            SendError::ActorDied(reason) => SendError::ActorDied(reason),
             SendError::MailboxFull(err) => SendError::MailboxFull(*err.downcast().unwrap()),
+            SendError::TooLarge(err) => SendError::TooLarge(*err.downcast().unwrap()),
+            SendError::Closed(err) => SendError::Closed(*err.downcast().unwrap()),
+            SendError::CircuitOpen(err) => SendError::CircuitOpen(*err.downcast().unwrap()),
             SendError::HandlerError(err) => SendError::HandlerError(*err.downcast().unwrap()),
             SendError::Timeout(err) => SendError::Timeout(err.map(|err| *err.downcast().unwrap())),
+            SendError::NoReply => SendError::NoReply,
         }
     }
 }
@@ -144,9 +293,14 @@ where
         match self {
             SendError::ActorNotRunning(_) => write!(f, "ActorNotRunning"),
             SendError::ActorStopped => write!(f, "ActorStopped"),
+            SendError::ActorDied(reason) => f.debug_tuple("ActorDied").field(reason).finish(),
             SendError::MailboxFull(_) => write!(f, "MailboxFull"),
+            SendError::TooLarge(_) => write!(f, "TooLarge"),
+            SendError::Closed(_) => write!(f, "Closed"),
+            SendError::CircuitOpen(_) => write!(f, "CircuitOpen"),
             SendError::HandlerError(err) => err.fmt(f),
             SendError::Timeout(_) => write!(f, "Timeout"),
+            SendError::NoReply => write!(f, "NoReply"),
         }
     }
 }
@@ -159,9 +313,14 @@ where
         match self {
             SendError::ActorNotRunning(_) => write!(f, "actor not running"),
             SendError::ActorStopped => write!(f, "actor stopped"),
+            SendError::ActorDied(reason) => write!(f, "actor died: {reason}"),
             SendError::MailboxFull(_) => write!(f, "mailbox full"),
+            SendError::TooLarge(_) => write!(f, "message too large"),
+            SendError::Closed(_) => write!(f, "actor closed"),
+            SendError::CircuitOpen(_) => write!(f, "circuit open"),
             SendError::HandlerError(err) => err.fmt(f),
             SendError::Timeout(_) => write!(f, "timeout"),
+            SendError::NoReply => write!(f, "no reply"),
         }
     }
 }
@@ -307,6 +466,57 @@ impl fmt::Display for RegistryError {
 
 impl error::Error for RegistryError {}
 
+/// An error that can occur when migrating an actor with [`ActorRef::migrate`](crate::actor::ActorRef::migrate).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MigrateError {
+    /// The actor isn't running.
+    ActorNotRunning,
+    /// The actor is not running in a task which can be moved, such as with
+    /// [`PreparedActor::run`](crate::actor::PreparedActor::run).
+    NotMigratable,
+}
+
+impl fmt::Display for MigrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrateError::ActorNotRunning => write!(f, "actor not running"),
+            MigrateError::NotMigratable => write!(f, "actor cannot be migrated"),
+        }
+    }
+}
+
+impl error::Error for MigrateError {}
+
+/// An error that can occur when spawning an actor with [`spawn_with_init`](crate::actor::spawn_with_init).
+#[derive(Clone, PartialEq, Eq)]
+pub enum SpawnError {
+    /// The actor failed to be constructed, with [`InitActor::init`](crate::actor::InitActor::init)
+    /// returning an error or panicking.
+    InitFailed(PanicError),
+    /// The actor's task was dropped before it finished being constructed, such as when the runtime shuts down.
+    ActorNotRunning,
+}
+
+impl fmt::Debug for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpawnError::InitFailed(_) => write!(f, "InitFailed"),
+            SpawnError::ActorNotRunning => write!(f, "ActorNotRunning"),
+        }
+    }
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpawnError::InitFailed(err) => err.fmt(f),
+            SpawnError::ActorNotRunning => write!(f, "actor not running"),
+        }
+    }
+}
+
+impl error::Error for SpawnError {}
+
 /// Error that can occur when sending a message to an actor.
 #[cfg(feature = "remote")]
 #[derive(Debug, Serialize, Deserialize)]
@@ -331,8 +541,16 @@ pub enum RemoteSendError<E> {
     BadActorType,
     /// The actors mailbox is full.
     MailboxFull,
+    /// The message is larger than the actor accepts.
+    TooLarge,
+    /// The actor has been closed, and no longer accepts messages.
+    Closed,
+    /// The circuit breaker guarding the actor is open, so the message wasn't sent.
+    CircuitOpen,
     /// Timed out waiting for a reply.
     ReplyTimeout,
+    /// The actor's message handler declined to reply.
+    NoReply,
     /// An error returned by the actor's message handler.
     HandlerError(E),
     /// Failed to serialize the message.
@@ -389,7 +607,11 @@ impl<E> RemoteSendError<E> {
             },
             RemoteSendError::BadActorType => RemoteSendError::BadActorType,
             RemoteSendError::MailboxFull => RemoteSendError::MailboxFull,
+            RemoteSendError::TooLarge => RemoteSendError::TooLarge,
+            RemoteSendError::Closed => RemoteSendError::Closed,
+            RemoteSendError::CircuitOpen => RemoteSendError::CircuitOpen,
             RemoteSendError::ReplyTimeout => RemoteSendError::ReplyTimeout,
+            RemoteSendError::NoReply => RemoteSendError::NoReply,
             RemoteSendError::HandlerError(err) => RemoteSendError::HandlerError(op(err)),
             RemoteSendError::SerializeMessage(err) => RemoteSendError::SerializeMessage(err),
             RemoteSendError::DeserializeMessage(err) => RemoteSendError::DeserializeMessage(err),
@@ -434,7 +656,11 @@ impl<E> RemoteSendError<RemoteSendError<E>> {
             },
             BadActorType | HandlerError(BadActorType) => BadActorType,
             MailboxFull | HandlerError(MailboxFull) => MailboxFull,
+            TooLarge | HandlerError(TooLarge) => TooLarge,
+            Closed | HandlerError(Closed) => Closed,
+            CircuitOpen | HandlerError(CircuitOpen) => CircuitOpen,
             ReplyTimeout | HandlerError(ReplyTimeout) => ReplyTimeout,
+            NoReply | HandlerError(NoReply) => NoReply,
             HandlerError(HandlerError(err)) => HandlerError(err),
             SerializeMessage(err) | HandlerError(SerializeMessage(err)) => SerializeMessage(err),
             DeserializeMessage(err) | HandlerError(DeserializeMessage(err)) => {
@@ -462,10 +688,14 @@ impl<M, E> From<SendError<M, E>> for RemoteSendError<E> {
     fn from(err: SendError<M, E>) -> Self {
         match err {
             SendError::ActorNotRunning(_) => RemoteSendError::ActorNotRunning,
-            SendError::ActorStopped => RemoteSendError::ActorStopped,
+            SendError::ActorStopped | SendError::ActorDied(_) => RemoteSendError::ActorStopped,
             SendError::MailboxFull(_) => RemoteSendError::MailboxFull,
+            SendError::TooLarge(_) => RemoteSendError::TooLarge,
+            SendError::Closed(_) => RemoteSendError::Closed,
+            SendError::CircuitOpen(_) => RemoteSendError::CircuitOpen,
             SendError::HandlerError(err) => RemoteSendError::HandlerError(err),
             SendError::Timeout(_) => RemoteSendError::ReplyTimeout,
+            SendError::NoReply => RemoteSendError::NoReply,
         }
     }
 }
@@ -491,7 +721,11 @@ where
             ),
             RemoteSendError::BadActorType => write!(f, "bad actor type"),
             RemoteSendError::MailboxFull => write!(f, "mailbox full"),
+            RemoteSendError::TooLarge => write!(f, "message too large"),
+            RemoteSendError::Closed => write!(f, "actor closed"),
+            RemoteSendError::CircuitOpen => write!(f, "circuit open"),
             RemoteSendError::ReplyTimeout => write!(f, "timeout"),
+            RemoteSendError::NoReply => write!(f, "no reply"),
             RemoteSendError::HandlerError(err) => err.fmt(f),
             RemoteSendError::SerializeMessage(err) => {
                 write!(f, "failed to serialize message: {err}")
@@ -523,7 +757,7 @@ where
 impl<E> error::Error for RemoteSendError<E> where E: fmt::Debug + fmt::Display {}
 
 /// Reason for an actor being stopped.
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ActorStopReason {
     /// Actor stopped normally.
     Normal,
@@ -575,10 +809,28 @@ impl fmt::Display for ActorStopReason {
     }
 }
 
+/// A hint for supervisors on whether an actor which failed should be restarted.
+///
+/// See [`Actor::restartability`] and [`PanicError::restartability`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Restartability {
+    /// The failure is temporary, such as a connection which couldn't be established,
+    /// and restarting the actor, possibly after a backoff, may succeed.
+    Transient,
+    /// The failure won't be fixed by restarting the actor, and should be escalated.
+    #[default]
+    Permanent,
+}
+
 /// A shared error that occurs when an actor panics or returns an error from a hook in the [Actor] trait.
 #[derive(Clone)]
 #[allow(missing_debug_implementations)]
-pub struct PanicError(Arc<Mutex<Box<dyn Any + Send>>>);
+pub struct PanicError {
+    err: Arc<Mutex<Box<dyn Any + Send>>>,
+    restartability: Restartability,
+    backtrace: Option<Arc<Backtrace>>,
+    history: Option<Arc<[MessageRecord]>>,
+}
 
 impl PanicError {
     /// Creates a new PanicError from a generic error.
@@ -586,12 +838,78 @@ impl PanicError {
     where
         E: Send + 'static,
     {
-        PanicError(Arc::new(Mutex::new(Box::new(err))))
+        PanicError {
+            err: Arc::new(Mutex::new(Box::new(err))),
+            restartability: Restartability::Permanent,
+            backtrace: None,
+            history: None,
+        }
     }
 
     /// Creates a new PanicError from a generic boxed error.
     pub fn new_boxed(err: Box<dyn Any + Send>) -> Self {
-        PanicError(Arc::new(Mutex::new(err)))
+        PanicError {
+            err: Arc::new(Mutex::new(err)),
+            restartability: Restartability::Permanent,
+            backtrace: None,
+            history: None,
+        }
+    }
+
+    /// Creates a new PanicError from a panic caught within an actor, attaching the backtrace captured by the panic hook.
+    pub(crate) fn new_panic(err: Box<dyn Any + Send>) -> Self {
+        let mut panic_err = PanicError::new_boxed(err);
+        panic_err.backtrace = PANIC_BACKTRACE.take().map(Arc::new);
+        panic_err
+    }
+
+    /// Creates a new PanicError from an error returned by one of `A`'s lifecycle hooks, classified with
+    /// [`Actor::restartability`].
+    pub(crate) fn new_hook_error<A: Actor>(err: BoxError) -> Self {
+        let restartability = A::restartability(&err);
+        PanicError::new(err).with_restartability(restartability)
+    }
+
+    /// Returns whether the failure is worth restarting the actor for.
+    ///
+    /// Errors returned by lifecycle hooks are classified with [`Actor::restartability`], while panics are
+    /// always [`Restartability::Permanent`].
+    pub fn restartability(&self) -> Restartability {
+        self.restartability
+    }
+
+    /// Returns the backtrace captured where the actor panicked, if any.
+    ///
+    /// Since panics within actors are caught, the backtrace normally printed by Rust's default panic hook can't be
+    /// relied on. Backtraces are captured by the hook installed with [`install_panic_hook`], and only if enabled with
+    /// the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment variables, as described in [`Backtrace::capture`].
+    ///
+    /// This returns `None` for errors returned by lifecycle hooks, rather than panics.
+    ///
+    /// [`install_panic_hook`]: crate::actor::install_panic_hook
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace.as_deref()
+    }
+
+    /// Returns the messages handled by the actor leading up to the failure, oldest first.
+    ///
+    /// The history is only recorded for actors which enable it with [`Actor::record_history`], and the message
+    /// which failed is the last entry. This is empty if history isn't recorded, or if the failure didn't come from
+    /// a message handler.
+    pub fn message_history(&self) -> &[MessageRecord] {
+        self.history.as_deref().unwrap_or_default()
+    }
+
+    /// Attaches the actor's recent message history.
+    pub(crate) fn with_history(mut self, history: Arc<[MessageRecord]>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Sets whether the failure is worth restarting the actor for.
+    pub fn with_restartability(mut self, restartability: Restartability) -> Self {
+        self.restartability = restartability;
+        self
     }
 
     /// Calls the passed closure `f` with an option containing the boxed any type downcasted into a `Cow<'static, str>`,
@@ -620,20 +938,114 @@ impl PanicError {
         T: 'static,
         F: FnOnce(&T) -> R,
     {
-        let lock = self.0.lock()?;
+        let lock = self.err.lock()?;
         Ok(lock.downcast_ref().map(f))
     }
 
+    /// Calls the passed closure `f` with the error returned by an actor's lifecycle hook downcasted into `E`,
+    /// otherwise returns `None`.
+    ///
+    /// Lifecycle hooks return a [`BoxError`], so unlike [`PanicError::with_downcast_ref`], this looks through the box
+    /// for the concrete error, allowing supervisors to branch on the kind of error which stopped an actor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::{Actor, ActorRef, PreparedActor};
+    /// use kameo::error::{ActorStopReason, BoxError};
+    /// use kameo::mailbox::unbounded::UnboundedMailbox;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum ConnectError {
+    ///     Unreachable,
+    /// }
+    /// #
+    /// # impl std::fmt::Display for ConnectError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         std::fmt::Debug::fmt(self, f)
+    /// #     }
+    /// # }
+    /// #
+    /// # impl std::error::Error for ConnectError {}
+    ///
+    /// struct Connection;
+    ///
+    /// impl Actor for Connection {
+    ///     type Mailbox = UnboundedMailbox<Self>;
+    ///
+    ///     async fn on_start(&mut self, _actor_ref: ActorRef<Self>) -> Result<(), BoxError> {
+    ///         Err(Box::new(ConnectError::Unreachable))
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let (_, reason) = PreparedActor::new().spawn(Connection).await?;
+    /// let ActorStopReason::Panicked(err) = reason else { panic!() };
+    /// let is_unreachable = err
+    ///     .with_error_downcast_ref(|err: &ConnectError| *err == ConnectError::Unreachable)
+    ///     .unwrap();
+    /// assert_eq!(is_unreachable, Some(true));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn with_error_downcast_ref<E, F, R>(
+        &self,
+        f: F,
+    ) -> Result<Option<R>, PoisonError<MutexGuard<'_, Box<dyn Any + Send>>>>
+    where
+        E: error::Error + 'static,
+        F: FnOnce(&E) -> R,
+    {
+        let lock = self.err.lock()?;
+        let err = match lock.downcast_ref::<BoxError>() {
+            Some(err) => err.downcast_ref::<E>(),
+            None => lock.downcast_ref::<E>(),
+        };
+        Ok(err.map(f))
+    }
+
     /// Returns a reference to the error as a `Box<dyn Any + Send>`.
     pub fn with<F, R>(&self, f: F) -> Result<R, PoisonError<MutexGuard<'_, Box<dyn Any + Send>>>>
     where
         F: FnOnce(&Box<dyn Any + Send>) -> R,
     {
-        let lock = self.0.lock()?;
+        let lock = self.err.lock()?;
         Ok(f(&lock))
     }
 }
 
+/// A message handled by an actor, recorded for [`PanicError::message_history`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MessageRecord {
+    /// The type name of the message.
+    pub name: &'static str,
+    /// When the actor started handling the message.
+    pub received_at: SystemTime,
+    /// How long the actor spent handling the message.
+    pub elapsed: Duration,
+}
+
+/// Panic errors are equal if they are clones of the same error.
+impl PartialEq for PanicError {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.err, &other.err)
+    }
+}
+
+impl Eq for PanicError {}
+
+thread_local! {
+    /// The backtrace of the last panic within an actor on this thread, until it's caught.
+    static PANIC_BACKTRACE: Cell<Option<Backtrace>> = const { Cell::new(None) };
+}
+
+/// Stores the backtrace of a panic within an actor, to be attached to the [`PanicError`] once it's caught.
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+pub(crate) fn set_panic_backtrace(backtrace: Backtrace) {
+    PANIC_BACKTRACE.set(Some(backtrace));
+}
+
 impl fmt::Display for PanicError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.with(|any| {
@@ -713,6 +1125,22 @@ impl fmt::Display for ActorIDFromBytesError {
 
 impl error::Error for ActorIDFromBytesError {}
 
+/// An error returned by an [`AckReceiver`](crate::message::AckReceiver) when a message was not acknowledged.
+///
+/// This occurs when the [`Ack`](crate::message::Ack) is dropped without being acknowledged, such as when the
+/// handler returns without calling [`Context::ack`](crate::message::Context::ack), the handler panics,
+/// or the actor stops before the message is handled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Nack;
+
+impl fmt::Display for Nack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "message was not acknowledged")
+    }
+}
+
+impl error::Error for Nack {}
+
 /// An infallible error type, similar to [std::convert::Infallible].
 ///
 /// Kameo provides its own Infallible type in order to implement Serialize/Deserialize for it.
@@ -720,9 +1148,8 @@ impl error::Error for ActorIDFromBytesError {}
 pub enum Infallible {}
 
 impl Clone for Infallible {
-    #[allow(clippy::non_canonical_clone_impl)]
     fn clone(&self) -> Infallible {
-        match *self {}
+        *self
     }
 }
 
@@ -753,9 +1180,8 @@ impl PartialEq for Infallible {
 impl Eq for Infallible {}
 
 impl PartialOrd for Infallible {
-    #[allow(clippy::non_canonical_partial_ord_impl)]
-    fn partial_cmp(&self, _other: &Self) -> Option<cmp::Ordering> {
-        match *self {}
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -4,6 +4,7 @@
 #![warn(rust_2018_idioms)]
 #![warn(missing_debug_implementations)]
 #![deny(unused_must_use)]
+#![forbid(unsafe_code)]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
 pub mod actor;
@@ -17,7 +18,9 @@ pub mod remote;
 pub mod reply;
 pub mod request;
 
-pub use actor::{spawn, Actor};
+#[cfg(feature = "tracing")]
+pub use actor::install_panic_hook;
+pub use actor::{export_graph, init, spawn, Actor};
 #[cfg(feature = "macros")]
 pub use kameo_macros::{messages, remote_message, Actor, RemoteActor, Reply};
 pub use reply::Reply;
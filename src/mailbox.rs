@@ -3,16 +3,21 @@
 //! An actor mailbox is a channel which stores pending messages and signals for an actor to process sequentially.
 
 pub mod bounded;
+pub mod fair;
+pub mod lifo;
+pub mod priority;
 pub mod unbounded;
 
+use std::time::{Duration, Instant};
+
 use dyn_clone::DynClone;
 use futures::{future::BoxFuture, Future};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
 use crate::{
     actor::{ActorID, ActorRef},
-    error::{ActorStopReason, SendError},
-    message::DynMessage,
+    error::{ActorStopReason, PanicError, SendError},
+    message::{DynMessage, Extensions, MessageMeta},
     reply::BoxReplySender,
     Actor,
 };
@@ -32,8 +37,10 @@ pub trait Mailbox<A: Actor>: SignalMailbox + Clone + Send + Sync {
         signal: Signal<A>,
     ) -> impl Future<Output = Result<(), mpsc::error::SendError<Signal<A>>>> + Send + '_;
     /// Tries to send a signal to the mailbox, failing if the mailbox is full.
+    #[allow(clippy::result_large_err)]
     fn try_send(&self, signal: Signal<A>) -> Result<(), mpsc::error::TrySendError<Signal<A>>>;
     /// Sends a signal to the mailbox, blocking the current thread.
+    #[allow(clippy::result_large_err)]
     fn blocking_send(&self, signal: Signal<A>) -> Result<(), mpsc::error::SendError<Signal<A>>>;
     /// Waits for the mailbox to be closed.
     fn closed(&self) -> impl Future<Output = ()> + Send + '_;
@@ -53,6 +60,21 @@ pub trait Mailbox<A: Actor>: SignalMailbox + Clone + Send + Sync {
 pub trait MailboxReceiver<A: Actor>: Send + 'static {
     /// Receives a value from the mailbox.
     fn recv(&mut self) -> impl Future<Output = Option<Signal<A>>> + Send + '_;
+    /// Returns the number of signals waiting in the mailbox.
+    ///
+    /// The default implementation returns 0, for receivers which can't tell how many signals are waiting.
+    fn len(&self) -> usize {
+        0
+    }
+    /// Returns `true` if there are no signals waiting in the mailbox.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Closes the receiving half of the mailbox, preventing any further signals from being sent.
+    ///
+    /// Signals already in the mailbox can still be received after closing. The default implementation does
+    /// nothing, so signals can still be sent until the receiver is dropped.
+    fn close(&mut self) {}
 }
 
 /// A weak mailbox which can be upraded.
@@ -77,12 +99,20 @@ pub enum Signal<A: Actor> {
         actor_ref: ActorRef<A>,
         reply: Option<BoxReplySender>,
         sent_within_actor: bool,
+        extensions: Extensions,
+        meta: MessageMeta<A>,
+        /// When the message was sent, used to measure how long it waited in the mailbox.
+        sent_at: Instant,
     },
     LinkDied {
         id: ActorID,
         reason: ActorStopReason,
     },
     Stop,
+    Flush(oneshot::Sender<()>),
+    /// Clones the actor's state for [`ActorRef::fork`], once every message sent before it has been handled.
+    Fork(Box<dyn FnOnce(&A) + Send>),
+    TaskPanicked(PanicError),
 }
 
 impl<A: Actor> Signal<A> {
@@ -95,6 +125,34 @@ impl<A: Actor> Signal<A> {
             _ => None,
         }
     }
+
+    /// Attaches the caller's reply deadline to a message, for [`Context::reply_deadline`](crate::message::Context::reply_deadline).
+    pub(crate) fn with_reply_deadline(mut self, timeout: Duration) -> Self {
+        if let Signal::Message { meta, .. } = &mut self {
+            meta.reply_deadline = Instant::now().checked_add(timeout);
+        }
+        self
+    }
+
+    /// Replies to a pending ask request with [`SendError::ActorNotRunning`], returning the message to the caller.
+    ///
+    /// This is used for messages which were queued in the mailbox, but never handled as the actor stopped.
+    /// Delegated messages are not the caller's own message, so [`SendError::ActorStopped`] is sent instead.
+    pub(crate) fn reject(self) {
+        if let Signal::Message {
+            message,
+            reply: Some(reply),
+            meta,
+            ..
+        } = self
+        {
+            if meta.delegated {
+                let _ = reply.send(Err(SendError::ActorStopped));
+            } else {
+                let _ = reply.send(Err(SendError::ActorNotRunning(message.as_any())));
+            }
+        }
+    }
 }
 
 #[doc(hidden)]
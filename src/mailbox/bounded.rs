@@ -31,7 +31,7 @@ impl<A: Actor> Mailbox<A> for BoundedMailbox<A> {
 
     #[inline]
     fn default_mailbox() -> (Self, Self::Receiver) {
-        BoundedMailbox::new(1000)
+        BoundedMailbox::new(crate::actor::default_mailbox_capacity())
     }
 
     #[inline]
@@ -101,6 +101,14 @@ impl<A: Actor> MailboxReceiver<A> for BoundedMailboxReceiver<A> {
     async fn recv(&mut self) -> Option<Signal<A>> {
         self.0.recv().await
     }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn close(&mut self) {
+        self.0.close()
+    }
 }
 
 impl<A: Actor> fmt::Debug for BoundedMailboxReceiver<A> {
@@ -0,0 +1,494 @@
+//! Fair mailbox types, where messages from different senders are processed in turn.
+//!
+//! A fair mailbox partitions pending messages by their sender, and round-robins between senders, handling one
+//! message from each before returning to the first. This prevents a single chatty producer from monopolizing a
+//! shared actor, since other senders only wait for one message from each active sender rather than for its whole
+//! backlog. Messages from the same sender are still processed in the order they were sent.
+//!
+//! # Partitioning
+//!
+//! By default, messages are partitioned by the [`ActorID`] of the actor which sent them. Messages sent from
+//! outside of an actor share a single partition. To partition by something else, such as a tenant or a
+//! connection, attach a [`FairKey`] to the message as an extension:
+//!
+//! ```
+//! use kameo::mailbox::fair::FairKey;
+//! # use kameo::message::{Context, Message};
+//!
+//! #[derive(kameo::Actor)]
+//! #[actor(mailbox = fair)]
+//! struct Database;
+//!
+//! struct Query(&'static str);
+//! #
+//! # impl Message<Query> for Database {
+//! #     type Reply = ();
+//! #     async fn handle(&mut self, msg: Query, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+//! # }
+//!
+//! # tokio_test::block_on(async {
+//! let actor_ref = kameo::spawn(Database);
+//! let tenant_id = 42;
+//! actor_ref.tell(Query("SELECT 1")).extension(FairKey(tenant_id)).await?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+//!
+//! # Memory overhead
+//!
+//! Each sender with pending messages has its own queue, costing a map entry and a queue allocation on top of the
+//! messages themselves. A sender's queue is evicted as soon as its last message is received, so idle senders use
+//! no memory, and the overhead is bounded by the number of senders with messages waiting at the same time.
+//!
+//! # System signals
+//!
+//! Signals such as link died notifications preempt pending messages. Stop requests and
+//! [`ActorRef::flush`](crate::actor::ActorRef::flush) barriers wait for every message sent before them, so
+//! [`ActorRef::stop_gracefully`](crate::actor::ActorRef::stop_gracefully) still handles all messages already queued.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use tokio::sync::mpsc;
+
+use crate::{
+    actor::ActorID,
+    error::{ActorStopReason, SendError},
+    Actor,
+};
+
+use super::{Mailbox, MailboxReceiver, Signal, SignalMailbox, WeakMailbox};
+
+/// A message extension which overrides the partition a message is queued in by a [`FairMailbox`].
+///
+/// Messages with the same key are processed in order, and take turns with messages of other keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FairKey(pub u64);
+
+/// An unbounded mailbox which takes turns processing messages from each sender.
+pub struct FairMailbox<A: Actor>(pub(crate) mpsc::UnboundedSender<Signal<A>>);
+
+impl<A: Actor> FairMailbox<A> {
+    /// Creates a new fair mailbox.
+    #[inline]
+    pub fn new() -> (Self, FairMailboxReceiver<A>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (
+            FairMailbox(tx),
+            FairMailboxReceiver {
+                rx,
+                signals: VecDeque::new(),
+                barriers: VecDeque::new(),
+                queues: HashMap::new(),
+                turns: VecDeque::new(),
+                queued: 0,
+                next_seq: 0,
+            },
+        )
+    }
+}
+
+impl<A: Actor> Mailbox<A> for FairMailbox<A> {
+    type Receiver = FairMailboxReceiver<A>;
+    type WeakMailbox = WeakFairMailbox<A>;
+
+    #[inline]
+    fn default_mailbox() -> (Self, Self::Receiver) {
+        FairMailbox::new()
+    }
+
+    #[inline]
+    async fn send(&self, signal: Signal<A>) -> Result<(), mpsc::error::SendError<Signal<A>>> {
+        self.0.send(signal)
+    }
+
+    #[inline]
+    fn try_send(&self, signal: Signal<A>) -> Result<(), mpsc::error::TrySendError<Signal<A>>> {
+        Ok(self.0.send(signal)?)
+    }
+
+    #[inline]
+    fn blocking_send(&self, signal: Signal<A>) -> Result<(), mpsc::error::SendError<Signal<A>>> {
+        self.0.send(signal)
+    }
+
+    #[inline]
+    async fn closed(&self) {
+        self.0.closed().await
+    }
+
+    #[inline]
+    fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+
+    #[inline]
+    fn downgrade(&self) -> Self::WeakMailbox {
+        WeakFairMailbox(self.0.downgrade())
+    }
+
+    #[inline]
+    fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+
+    #[inline]
+    fn weak_count(&self) -> usize {
+        self.0.weak_count()
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<A: Actor> Clone for FairMailbox<A> {
+    fn clone(&self) -> Self {
+        FairMailbox(self.0.clone())
+    }
+}
+
+impl<A: Actor> fmt::Debug for FairMailbox<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FairMailbox").field("tx", &self.0).finish()
+    }
+}
+
+/// The partition a message is queued in.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Partition {
+    Key(FairKey),
+    Sender(ActorID),
+    External,
+}
+
+/// A fair mailbox receiver.
+pub struct FairMailboxReceiver<A: Actor> {
+    rx: mpsc::UnboundedReceiver<Signal<A>>,
+    /// Signals which preempt pending messages.
+    signals: VecDeque<Signal<A>>,
+    /// Signals which wait for every message sent before them, along with their sequence number.
+    barriers: VecDeque<(u64, Signal<A>)>,
+    /// Pending messages for each partition, along with their sequence number.
+    queues: HashMap<Partition, VecDeque<(u64, Signal<A>)>>,
+    /// The order in which partitions with pending messages take their turn.
+    turns: VecDeque<Partition>,
+    queued: usize,
+    next_seq: u64,
+}
+
+impl<A: Actor> FairMailboxReceiver<A> {
+    /// Moves all signals currently in the channel into their queues.
+    fn drain(&mut self) {
+        while let Ok(signal) = self.rx.try_recv() {
+            self.push(signal);
+        }
+    }
+
+    fn push(&mut self, signal: Signal<A>) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        match signal {
+            Signal::Message {
+                ref extensions,
+                ref meta,
+                ..
+            } => {
+                let partition = match (extensions.get::<FairKey>(), meta.sender_id) {
+                    (Some(key), _) => Partition::Key(*key),
+                    (None, Some(id)) => Partition::Sender(id),
+                    (None, None) => Partition::External,
+                };
+                let queue = self.queues.entry(partition).or_default();
+                if queue.is_empty() {
+                    self.turns.push_back(partition);
+                }
+                queue.push_back((seq, signal));
+                self.queued += 1;
+            }
+            signal @ (Signal::Stop | Signal::Flush(_) | Signal::Fork(_)) => {
+                self.barriers.push_back((seq, signal))
+            }
+            signal => self.signals.push_back(signal),
+        }
+    }
+
+    /// Takes the next message in turn which was sent before `before`, evicting its queue if it's now empty.
+    fn next_message(&mut self, before: Option<u64>) -> Option<Signal<A>> {
+        for _ in 0..self.turns.len() {
+            let partition = self.turns.pop_front()?;
+            let queue = self.queues.get_mut(&partition)?;
+            let (seq, _) = queue.front()?;
+            if before.is_some_and(|before| *seq > before) {
+                self.turns.push_back(partition);
+                continue;
+            }
+
+            let (_, signal) = queue.pop_front()?;
+            if queue.is_empty() {
+                self.queues.remove(&partition);
+            } else {
+                self.turns.push_back(partition);
+            }
+            self.queued -= 1;
+            return Some(signal);
+        }
+
+        None
+    }
+}
+
+impl<A: Actor> MailboxReceiver<A> for FairMailboxReceiver<A> {
+    async fn recv(&mut self) -> Option<Signal<A>> {
+        loop {
+            self.drain();
+            if let Some(signal) = self.signals.pop_front() {
+                return Some(signal);
+            }
+            let barrier = self.barriers.front().map(|(seq, _)| *seq);
+            if let Some(message) = self.next_message(barrier) {
+                return Some(message);
+            }
+            if let Some((_, signal)) = self.barriers.pop_front() {
+                return Some(signal);
+            }
+            let signal = self.rx.recv().await?;
+            self.push(signal);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.rx.len() + self.signals.len() + self.barriers.len() + self.queued
+    }
+
+    fn close(&mut self) {
+        self.rx.close()
+    }
+}
+
+impl<A: Actor> fmt::Debug for FairMailboxReceiver<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FairMailboxReceiver")
+            .field("rx", &self.rx)
+            .field("signals", &self.signals.len())
+            .field("barriers", &self.barriers.len())
+            .field("partitions", &self.queues.len())
+            .field("messages", &self.queued)
+            .finish()
+    }
+}
+
+/// A weak fair mailbox that does not prevent the actor from being stopped.
+pub struct WeakFairMailbox<A: Actor>(mpsc::WeakUnboundedSender<Signal<A>>);
+
+impl<A: Actor> WeakMailbox for WeakFairMailbox<A> {
+    type StrongMailbox = FairMailbox<A>;
+
+    #[inline]
+    fn upgrade(&self) -> Option<Self::StrongMailbox> {
+        self.0.upgrade().map(FairMailbox)
+    }
+
+    #[inline]
+    fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+
+    #[inline]
+    fn weak_count(&self) -> usize {
+        self.0.weak_count()
+    }
+}
+
+impl<A: Actor> Clone for WeakFairMailbox<A> {
+    fn clone(&self) -> Self {
+        WeakFairMailbox(self.0.clone())
+    }
+}
+
+impl<A: Actor> fmt::Debug for WeakFairMailbox<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakFairMailbox")
+            .field("tx", &self.0)
+            .finish()
+    }
+}
+
+impl<A> SignalMailbox for FairMailbox<A>
+where
+    A: Actor,
+{
+    fn signal_startup_finished(&self) -> Result<(), SendError> {
+        self.0
+            .send(Signal::StartupFinished)
+            .map_err(|_| SendError::ActorNotRunning(()))
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.0
+                .send(Signal::LinkDied { id, reason })
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.0
+                .send(Signal::Stop)
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+}
+
+impl<A> SignalMailbox for WeakFairMailbox<A>
+where
+    A: Actor,
+{
+    fn signal_startup_finished(&self) -> Result<(), SendError> {
+        match self.upgrade() {
+            Some(mb) => mb.signal_startup_finished(),
+            None => Err(SendError::ActorNotRunning(())),
+        }
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_link_died(id, reason).await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_stop().await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::oneshot;
+
+    use crate::{
+        actor::Actor,
+        message::{Context, Message},
+        request::MessageSendSync,
+        spawn,
+    };
+
+    use super::{FairKey, FairMailbox};
+
+    #[tokio::test]
+    async fn fair_mailbox_round_robins_between_senders() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Default)]
+        struct MyActor {
+            handled: Vec<u32>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = FairMailbox<Self>;
+        }
+
+        struct Block {
+            started: oneshot::Sender<()>,
+            release: oneshot::Receiver<()>,
+        }
+
+        impl Message<Block> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                msg: Block,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                msg.started.send(()).unwrap();
+                msg.release.await.unwrap();
+            }
+        }
+
+        struct Push(u32);
+
+        impl Message<Push> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                Push(n): Push,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.handled.push(n);
+            }
+        }
+
+        struct Finish(oneshot::Sender<Vec<u32>>);
+
+        impl Message<Finish> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                Finish(tx): Finish,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                tx.send(self.handled.clone()).unwrap();
+            }
+        }
+
+        let actor_ref = spawn(MyActor::default());
+
+        // Block the actor so the following messages queue up in the mailbox
+        let (started_tx, started_rx) = oneshot::channel();
+        let (release_tx, release_rx) = oneshot::channel();
+        actor_ref
+            .tell(Block {
+                started: started_tx,
+                release: release_rx,
+            })
+            .send_sync()?;
+        started_rx.await?;
+
+        // The first sender's backlog doesn't delay the second sender's messages
+        for n in [1, 2, 3] {
+            actor_ref.tell(Push(n)).extension(FairKey(1)).await?;
+        }
+        let (finish_tx, finish_rx) = oneshot::channel();
+        actor_ref
+            .tell(Finish(finish_tx))
+            .extension(FairKey(1))
+            .await?;
+        for n in [10, 11] {
+            actor_ref.tell(Push(n)).extension(FairKey(2)).await?;
+        }
+        // Stopping gracefully still waits for every queued message
+        actor_ref.stop_gracefully().await?;
+        release_tx.send(()).unwrap();
+
+        assert_eq!(finish_rx.await?, vec![1, 10, 2, 11, 3]);
+        actor_ref.wait_for_stop().await;
+
+        Ok(())
+    }
+}
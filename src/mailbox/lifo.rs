@@ -0,0 +1,376 @@
+//! LIFO mailbox types, where the most recently sent message is processed first.
+//!
+//! A LIFO mailbox is useful for interactive workloads where stale requests are worthless,
+//! such as always rendering the newest UI state, or only serving the latest search query.
+//!
+//! # Starvation
+//!
+//! Messages are only processed in the order they were sent when the actor keeps up with its mailbox.
+//! If messages are sent faster than they can be handled, older messages may be starved indefinitely,
+//! and any ask requests waiting on them will not receive a reply until the actor catches up or stops.
+//!
+//! # System signals
+//!
+//! Signals such as link died notifications and stop requests always preempt pending messages,
+//! and are processed in the order they were sent.
+//! This means [`ActorRef::stop_gracefully`](crate::actor::ActorRef::stop_gracefully) stops the actor after the current message,
+//! and any messages still queued are rejected with [`SendError::ActorNotRunning`].
+
+use std::{collections::VecDeque, fmt};
+
+use futures::{future::BoxFuture, FutureExt};
+use tokio::sync::mpsc;
+
+use crate::{
+    actor::ActorID,
+    error::{ActorStopReason, SendError},
+    Actor,
+};
+
+use super::{Mailbox, MailboxReceiver, Signal, SignalMailbox, WeakMailbox};
+
+/// An unbounded mailbox which processes the most recently sent message first.
+pub struct LifoMailbox<A: Actor>(pub(crate) mpsc::UnboundedSender<Signal<A>>);
+
+impl<A: Actor> LifoMailbox<A> {
+    /// Creates a new LIFO mailbox.
+    #[inline]
+    pub fn new() -> (Self, LifoMailboxReceiver<A>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (
+            LifoMailbox(tx),
+            LifoMailboxReceiver {
+                rx,
+                signals: VecDeque::new(),
+                messages: Vec::new(),
+            },
+        )
+    }
+}
+
+impl<A: Actor> Mailbox<A> for LifoMailbox<A> {
+    type Receiver = LifoMailboxReceiver<A>;
+    type WeakMailbox = WeakLifoMailbox<A>;
+
+    #[inline]
+    fn default_mailbox() -> (Self, Self::Receiver) {
+        LifoMailbox::new()
+    }
+
+    #[inline]
+    async fn send(&self, signal: Signal<A>) -> Result<(), mpsc::error::SendError<Signal<A>>> {
+        self.0.send(signal)
+    }
+
+    #[inline]
+    fn try_send(&self, signal: Signal<A>) -> Result<(), mpsc::error::TrySendError<Signal<A>>> {
+        Ok(self.0.send(signal)?)
+    }
+
+    #[inline]
+    fn blocking_send(&self, signal: Signal<A>) -> Result<(), mpsc::error::SendError<Signal<A>>> {
+        self.0.send(signal)
+    }
+
+    #[inline]
+    async fn closed(&self) {
+        self.0.closed().await
+    }
+
+    #[inline]
+    fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+
+    #[inline]
+    fn downgrade(&self) -> Self::WeakMailbox {
+        WeakLifoMailbox(self.0.downgrade())
+    }
+
+    #[inline]
+    fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+
+    #[inline]
+    fn weak_count(&self) -> usize {
+        self.0.weak_count()
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<A: Actor> Clone for LifoMailbox<A> {
+    fn clone(&self) -> Self {
+        LifoMailbox(self.0.clone())
+    }
+}
+
+impl<A: Actor> fmt::Debug for LifoMailbox<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LifoMailbox").field("tx", &self.0).finish()
+    }
+}
+
+/// A LIFO mailbox receiver.
+pub struct LifoMailboxReceiver<A: Actor> {
+    rx: mpsc::UnboundedReceiver<Signal<A>>,
+    signals: VecDeque<Signal<A>>,
+    messages: Vec<Signal<A>>,
+}
+
+impl<A: Actor> LifoMailboxReceiver<A> {
+    /// Moves all signals currently in the channel into the signal queue and message stack.
+    fn drain(&mut self) {
+        while let Ok(signal) = self.rx.try_recv() {
+            self.push(signal);
+        }
+    }
+
+    fn push(&mut self, signal: Signal<A>) {
+        match signal {
+            signal @ Signal::Message { .. } => self.messages.push(signal),
+            // Flushes and forks wait for every message already on the stack
+            signal @ (Signal::Flush(_) | Signal::Fork(_)) => self.messages.insert(0, signal),
+            signal => self.signals.push_back(signal),
+        }
+    }
+}
+
+impl<A: Actor> MailboxReceiver<A> for LifoMailboxReceiver<A> {
+    async fn recv(&mut self) -> Option<Signal<A>> {
+        loop {
+            self.drain();
+            if let Some(signal) = self.signals.pop_front() {
+                return Some(signal);
+            }
+            if let Some(message) = self.messages.pop() {
+                return Some(message);
+            }
+            let signal = self.rx.recv().await?;
+            self.push(signal);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.rx.len() + self.signals.len() + self.messages.len()
+    }
+
+    fn close(&mut self) {
+        self.rx.close()
+    }
+}
+
+impl<A: Actor> fmt::Debug for LifoMailboxReceiver<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LifoMailboxReceiver")
+            .field("rx", &self.rx)
+            .field("signals", &self.signals.len())
+            .field("messages", &self.messages.len())
+            .finish()
+    }
+}
+
+/// A weak LIFO mailbox that does not prevent the actor from being stopped.
+pub struct WeakLifoMailbox<A: Actor>(mpsc::WeakUnboundedSender<Signal<A>>);
+
+impl<A: Actor> WeakMailbox for WeakLifoMailbox<A> {
+    type StrongMailbox = LifoMailbox<A>;
+
+    #[inline]
+    fn upgrade(&self) -> Option<Self::StrongMailbox> {
+        self.0.upgrade().map(LifoMailbox)
+    }
+
+    #[inline]
+    fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+
+    #[inline]
+    fn weak_count(&self) -> usize {
+        self.0.weak_count()
+    }
+}
+
+impl<A: Actor> Clone for WeakLifoMailbox<A> {
+    fn clone(&self) -> Self {
+        WeakLifoMailbox(self.0.clone())
+    }
+}
+
+impl<A: Actor> fmt::Debug for WeakLifoMailbox<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakLifoMailbox")
+            .field("tx", &self.0)
+            .finish()
+    }
+}
+
+impl<A> SignalMailbox for LifoMailbox<A>
+where
+    A: Actor,
+{
+    fn signal_startup_finished(&self) -> Result<(), SendError> {
+        self.0
+            .send(Signal::StartupFinished)
+            .map_err(|_| SendError::ActorNotRunning(()))
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.0
+                .send(Signal::LinkDied { id, reason })
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.0
+                .send(Signal::Stop)
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+}
+
+impl<A> SignalMailbox for WeakLifoMailbox<A>
+where
+    A: Actor,
+{
+    fn signal_startup_finished(&self) -> Result<(), SendError> {
+        match self.upgrade() {
+            Some(mb) => mb.signal_startup_finished(),
+            None => Err(SendError::ActorNotRunning(())),
+        }
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_link_died(id, reason).await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_stop().await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::oneshot;
+
+    use crate::{
+        actor::Actor,
+        message::{Context, Message},
+        request::{MessageSend, MessageSendSync, TryMessageSend},
+        spawn,
+    };
+
+    use super::LifoMailbox;
+
+    #[tokio::test]
+    async fn lifo_mailbox_handles_newest_message_first() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Default)]
+        struct MyActor {
+            handled: Vec<u32>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = LifoMailbox<Self>;
+        }
+
+        struct Block {
+            started: oneshot::Sender<()>,
+            release: oneshot::Receiver<()>,
+        }
+
+        impl Message<Block> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                msg: Block,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                msg.started.send(()).unwrap();
+                msg.release.await.unwrap();
+            }
+        }
+
+        struct Push(u32);
+
+        impl Message<Push> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                Push(n): Push,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.handled.push(n);
+            }
+        }
+
+        struct Finish(oneshot::Sender<Vec<u32>>);
+
+        impl Message<Finish> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                Finish(tx): Finish,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                tx.send(self.handled.clone()).unwrap();
+            }
+        }
+
+        let actor_ref = spawn(MyActor::default());
+
+        // Block the actor so the following messages queue up in the mailbox
+        let (started_tx, started_rx) = oneshot::channel();
+        let (release_tx, release_rx) = oneshot::channel();
+        actor_ref
+            .tell(Block {
+                started: started_tx,
+                release: release_rx,
+            })
+            .send_sync()?;
+        started_rx.await?;
+
+        let (finish_tx, finish_rx) = oneshot::channel();
+        actor_ref.tell(Finish(finish_tx)).send_sync()?;
+        actor_ref.tell(Push(1)).send_sync()?;
+        actor_ref.tell(Push(2)).send().await?;
+        actor_ref.tell(Push(3)).try_send().await?;
+        release_tx.send(()).unwrap();
+
+        assert_eq!(finish_rx.await?, vec![3, 2, 1]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,474 @@
+//! Priority mailbox types, where the message with the highest priority is processed first.
+//!
+//! Each message's priority is returned by [`Message::priority`], and a priority mailbox always processes the pending
+//! message with the highest priority next. This suits workload-aware actors, such as handling health checks and
+//! cancellations ahead of queued bulk work.
+//!
+//! # Example
+//!
+//! ```
+//! use kameo::message::{Context, Message};
+//!
+//! #[derive(kameo::Actor)]
+//! #[actor(mailbox = priority)]
+//! struct Scheduler;
+//!
+//! struct Job {
+//!     urgency: i64,
+//! }
+//!
+//! impl Message<Job> for Scheduler {
+//!     type Reply = ();
+//!
+//!     async fn handle(&mut self, job: Job, _: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+//!
+//!     fn priority(job: &Job) -> i64 {
+//!         job.urgency
+//!     }
+//! }
+//! ```
+//!
+//! # Ordering
+//!
+//! Messages are no longer processed in the order they were sent, even from the same sender. Messages with equal
+//! priorities are processed in the order they were sent, so they can't starve each other. However messages with a
+//! low priority may be starved indefinitely while messages with a higher priority keep arriving, and any ask
+//! requests waiting on them will not receive a reply until the actor catches up or stops.
+//!
+//! # System signals
+//!
+//! Signals such as link died notifications preempt pending messages. Stop requests and
+//! [`ActorRef::flush`](crate::actor::ActorRef::flush) barriers wait for every message sent before them, regardless
+//! of priority, and messages sent after a barrier wait for the barrier, so
+//! [`ActorRef::stop_gracefully`](crate::actor::ActorRef::stop_gracefully) still handles all messages already queued.
+//!
+//! [`Message::priority`]: crate::message::Message::priority
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, VecDeque},
+    fmt,
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use tokio::sync::mpsc;
+
+use crate::{
+    actor::ActorID,
+    error::{ActorStopReason, SendError},
+    Actor,
+};
+
+use super::{Mailbox, MailboxReceiver, Signal, SignalMailbox, WeakMailbox};
+
+/// An unbounded mailbox which processes the message with the highest priority first.
+pub struct PriorityMailbox<A: Actor>(pub(crate) mpsc::UnboundedSender<Signal<A>>);
+
+impl<A: Actor> PriorityMailbox<A> {
+    /// Creates a new priority mailbox.
+    #[inline]
+    pub fn new() -> (Self, PriorityMailboxReceiver<A>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (
+            PriorityMailbox(tx),
+            PriorityMailboxReceiver {
+                rx,
+                signals: VecDeque::new(),
+                messages: BinaryHeap::new(),
+                blocked: VecDeque::new(),
+                next_seq: 0,
+            },
+        )
+    }
+}
+
+impl<A: Actor> Mailbox<A> for PriorityMailbox<A> {
+    type Receiver = PriorityMailboxReceiver<A>;
+    type WeakMailbox = WeakPriorityMailbox<A>;
+
+    #[inline]
+    fn default_mailbox() -> (Self, Self::Receiver) {
+        PriorityMailbox::new()
+    }
+
+    #[inline]
+    async fn send(&self, signal: Signal<A>) -> Result<(), mpsc::error::SendError<Signal<A>>> {
+        self.0.send(signal)
+    }
+
+    #[inline]
+    fn try_send(&self, signal: Signal<A>) -> Result<(), mpsc::error::TrySendError<Signal<A>>> {
+        Ok(self.0.send(signal)?)
+    }
+
+    #[inline]
+    fn blocking_send(&self, signal: Signal<A>) -> Result<(), mpsc::error::SendError<Signal<A>>> {
+        self.0.send(signal)
+    }
+
+    #[inline]
+    async fn closed(&self) {
+        self.0.closed().await
+    }
+
+    #[inline]
+    fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+
+    #[inline]
+    fn downgrade(&self) -> Self::WeakMailbox {
+        WeakPriorityMailbox(self.0.downgrade())
+    }
+
+    #[inline]
+    fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+
+    #[inline]
+    fn weak_count(&self) -> usize {
+        self.0.weak_count()
+    }
+
+    #[inline]
+    fn capacity(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<A: Actor> Clone for PriorityMailbox<A> {
+    fn clone(&self) -> Self {
+        PriorityMailbox(self.0.clone())
+    }
+}
+
+impl<A: Actor> fmt::Debug for PriorityMailbox<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PriorityMailbox")
+            .field("tx", &self.0)
+            .finish()
+    }
+}
+
+/// A pending message, ordered by its priority and then by the order it was sent in.
+struct Pending<A: Actor> {
+    priority: i64,
+    seq: u64,
+    signal: Signal<A>,
+}
+
+impl<A: Actor> PartialEq for Pending<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<A: Actor> Eq for Pending<A> {}
+
+impl<A: Actor> PartialOrd for Pending<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: Actor> Ord for Pending<A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Earlier messages come first among equal priorities
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+/// A priority mailbox receiver.
+pub struct PriorityMailboxReceiver<A: Actor> {
+    rx: mpsc::UnboundedReceiver<Signal<A>>,
+    /// Signals which preempt pending messages.
+    signals: VecDeque<Signal<A>>,
+    /// Pending messages sent before the next barrier.
+    messages: BinaryHeap<Pending<A>>,
+    /// Barriers, and the messages sent after the first barrier, in the order they were sent.
+    blocked: VecDeque<Signal<A>>,
+    next_seq: u64,
+}
+
+impl<A: Actor> PriorityMailboxReceiver<A> {
+    /// Moves all signals currently in the channel into their queues.
+    fn drain(&mut self) {
+        while let Ok(signal) = self.rx.try_recv() {
+            self.push(signal);
+        }
+    }
+
+    fn push(&mut self, signal: Signal<A>) {
+        match signal {
+            signal @ Signal::Message { .. } if self.blocked.is_empty() => self.push_message(signal),
+            signal @ (Signal::Message { .. }
+            | Signal::Stop
+            | Signal::Flush(_)
+            | Signal::Fork(_)) => self.blocked.push_back(signal),
+            signal => self.signals.push_back(signal),
+        }
+    }
+
+    fn push_message(&mut self, signal: Signal<A>) {
+        let priority = match &signal {
+            Signal::Message { message, .. } => message.priority(),
+            _ => 0,
+        };
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.messages.push(Pending {
+            priority,
+            seq,
+            signal,
+        });
+    }
+
+    /// Takes the next barrier, releasing the messages sent between it and the following barrier.
+    fn next_barrier(&mut self) -> Option<Signal<A>> {
+        let barrier = self.blocked.pop_front()?;
+        while let Some(Signal::Message { .. }) = self.blocked.front() {
+            if let Some(signal) = self.blocked.pop_front() {
+                self.push_message(signal);
+            }
+        }
+        Some(barrier)
+    }
+}
+
+impl<A: Actor> MailboxReceiver<A> for PriorityMailboxReceiver<A> {
+    async fn recv(&mut self) -> Option<Signal<A>> {
+        loop {
+            self.drain();
+            if let Some(signal) = self.signals.pop_front() {
+                return Some(signal);
+            }
+            if let Some(Pending { signal, .. }) = self.messages.pop() {
+                return Some(signal);
+            }
+            if let Some(barrier) = self.next_barrier() {
+                return Some(barrier);
+            }
+            let signal = self.rx.recv().await?;
+            self.push(signal);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.rx.len() + self.signals.len() + self.messages.len() + self.blocked.len()
+    }
+
+    fn close(&mut self) {
+        self.rx.close()
+    }
+}
+
+impl<A: Actor> fmt::Debug for PriorityMailboxReceiver<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PriorityMailboxReceiver")
+            .field("rx", &self.rx)
+            .field("signals", &self.signals.len())
+            .field("messages", &self.messages.len())
+            .field("blocked", &self.blocked.len())
+            .finish()
+    }
+}
+
+/// A weak priority mailbox that does not prevent the actor from being stopped.
+pub struct WeakPriorityMailbox<A: Actor>(mpsc::WeakUnboundedSender<Signal<A>>);
+
+impl<A: Actor> WeakMailbox for WeakPriorityMailbox<A> {
+    type StrongMailbox = PriorityMailbox<A>;
+
+    #[inline]
+    fn upgrade(&self) -> Option<Self::StrongMailbox> {
+        self.0.upgrade().map(PriorityMailbox)
+    }
+
+    #[inline]
+    fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+
+    #[inline]
+    fn weak_count(&self) -> usize {
+        self.0.weak_count()
+    }
+}
+
+impl<A: Actor> Clone for WeakPriorityMailbox<A> {
+    fn clone(&self) -> Self {
+        WeakPriorityMailbox(self.0.clone())
+    }
+}
+
+impl<A: Actor> fmt::Debug for WeakPriorityMailbox<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakPriorityMailbox")
+            .field("tx", &self.0)
+            .finish()
+    }
+}
+
+impl<A> SignalMailbox for PriorityMailbox<A>
+where
+    A: Actor,
+{
+    fn signal_startup_finished(&self) -> Result<(), SendError> {
+        self.0
+            .send(Signal::StartupFinished)
+            .map_err(|_| SendError::ActorNotRunning(()))
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.0
+                .send(Signal::LinkDied { id, reason })
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            self.0
+                .send(Signal::Stop)
+                .map_err(|_| SendError::ActorNotRunning(()))
+        }
+        .boxed()
+    }
+}
+
+impl<A> SignalMailbox for WeakPriorityMailbox<A>
+where
+    A: Actor,
+{
+    fn signal_startup_finished(&self) -> Result<(), SendError> {
+        match self.upgrade() {
+            Some(mb) => mb.signal_startup_finished(),
+            None => Err(SendError::ActorNotRunning(())),
+        }
+    }
+
+    fn signal_link_died(
+        &self,
+        id: ActorID,
+        reason: ActorStopReason,
+    ) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_link_died(id, reason).await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+
+    fn signal_stop(&self) -> BoxFuture<'_, Result<(), SendError>> {
+        async move {
+            match self.upgrade() {
+                Some(mb) => mb.signal_stop().await,
+                None => Err(SendError::ActorNotRunning(())),
+            }
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        actor::Actor,
+        message::{Context, Message},
+        request::MessageSend,
+        spawn,
+    };
+
+    use super::PriorityMailbox;
+
+    #[tokio::test]
+    async fn priority_mailbox_handles_highest_priority_first(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Default)]
+        struct MyActor {
+            handled: Vec<&'static str>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = PriorityMailbox<Self>;
+        }
+
+        struct Job {
+            name: &'static str,
+            priority: i64,
+        }
+
+        impl Message<Job> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                job: Job,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                if job.name == "block" {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+                self.handled.push(job.name);
+            }
+
+            fn priority(job: &Job) -> i64 {
+                job.priority
+            }
+        }
+
+        struct Handled;
+
+        impl Message<Handled> for MyActor {
+            type Reply = Vec<&'static str>;
+
+            async fn handle(
+                &mut self,
+                _: Handled,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.handled.clone()
+            }
+        }
+
+        let actor_ref = spawn(MyActor::default());
+        let job = |name, priority| Job { name, priority };
+
+        // Block the actor while the other jobs are queued
+        actor_ref.tell(job("block", 0)).await?;
+        actor_ref.wait_startup().await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        actor_ref.tell(job("low", -1)).await?;
+        actor_ref.tell(job("first", 1)).await?;
+        actor_ref.tell(job("high", 5)).await?;
+        actor_ref.tell(job("second", 1)).await?;
+        // Jobs sent after a flush wait for every job sent before it
+        let (flushed, told) = tokio::join!(
+            actor_ref.flush(),
+            actor_ref.tell(job("after flush", 10)).send()
+        );
+        flushed?;
+        told?;
+        actor_ref.flush().await?;
+
+        assert_eq!(
+            actor_ref.ask(Handled).await?,
+            ["block", "high", "first", "second", "low", "after flush"]
+        );
+
+        Ok(())
+    }
+}
@@ -101,6 +101,14 @@ impl<A: Actor> MailboxReceiver<A> for UnboundedMailboxReceiver<A> {
     async fn recv(&mut self) -> Option<Signal<A>> {
         self.0.recv().await
     }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn close(&mut self) {
+        self.0.close()
+    }
 }
 
 impl<A: Actor> fmt::Debug for UnboundedMailboxReceiver<A> {
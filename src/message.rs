@@ -8,26 +8,46 @@
 //! reference to the current actor and a way to reply to messages. This enables actors to perform a wide range of
 //! actions in response to received messages, from altering their own state to querying other actors.
 //!
-//! The module distinguishes between two kinds of communication: messages, which are intended to modify an actor's
-//! state and might lead to side effects, and queries, which are read-only requests for information from an actor.
-//! This distinction helps in clearly separating commands from queries, aligning with the CQRS
-//! (Command Query Responsibility Segregation) principle and enhancing the clarity and maintainability of actor
-//! interactions. It also provides some performance benefits in that sequential queries can be processed concurrently.
+//! Messages are processed one at a time, in the order they are received, with exclusive access to the actor's state.
+//! Read-only requests are sent as regular messages, so a request for information will never run concurrently with a
+//! message which modifies state, and can never observe a partially applied change.
 
-use std::{any, fmt};
+use std::{
+    any,
+    collections::HashMap,
+    fmt,
+    marker::PhantomData,
+    mem,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::{atomic::Ordering, Arc, Mutex, PoisonError, Weak},
+    task::{self, Poll},
+    time::{Duration, Instant},
+};
 
-use futures::{future::BoxFuture, Future, FutureExt};
+use futures::{
+    future::{self, AbortHandle, Abortable, BoxFuture},
+    Future, FutureExt,
+};
+use tokio::{sync::oneshot, task_local};
 
 use crate::{
-    actor::ActorRef,
-    error::SendError,
+    actor::{ActorID, ActorRef, WeakActorRef, CURRENT_ACTOR_ID},
+    error::{ActorStopReason, BoxSendError, Nack, PanicError, SendError},
+    mailbox::{Mailbox, Signal},
     reply::{BoxReplySender, DelegatedReply, ForwardedReply, Reply, ReplySender},
-    request::{AskRequest, LocalAskRequest, MessageSend, WithoutRequestTimeout},
+    request::{
+        AskRequest, LocalAskRequest, MessageSend, RequestScope, WithRequestTimeout,
+        WithoutRequestTimeout,
+    },
     Actor,
 };
 
 pub(crate) type BoxDebug = Box<dyn fmt::Debug + Send + 'static>;
-pub(crate) type BoxReply = Box<dyn any::Any + Send>;
+
+task_local! {
+    static CURRENT_AMBIENT: Ambient;
+}
 
 /// A message that can modify an actors state.
 ///
@@ -44,6 +64,235 @@ pub trait Message<T: Send + 'static>: Actor {
         msg: T,
         ctx: Context<'_, Self, Self::Reply>,
     ) -> impl Future<Output = Self::Reply> + Send;
+
+    /// Returns the approximate size of the message in bytes, which is checked against
+    /// [`Actor::max_message_bytes`] before the message is enqueued.
+    ///
+    /// The size is advisory and reported by the message itself, typically through its [`MessageSize`] implementation.
+    ///
+    /// # Default Implementation
+    /// By default, messages have a size of `0`, so they are never rejected.
+    #[allow(unused_variables)]
+    fn message_size(msg: &T) -> usize {
+        0
+    }
+
+    /// Returns the priority of the message, used by a [`PriorityMailbox`](crate::mailbox::priority::PriorityMailbox)
+    /// to process messages with a higher priority first.
+    ///
+    /// Other mailboxes ignore the priority.
+    ///
+    /// # Default Implementation
+    /// By default, messages have a priority of `0`.
+    #[allow(unused_variables)]
+    fn priority(msg: &T) -> i64 {
+        0
+    }
+
+    /// Validates the message before it's handled.
+    ///
+    /// Messages which fail validation never reach [`handle`](Message::handle). For ask requests, the error is sent
+    /// back to the caller as a [`SendError::HandlerError`], just as if the handler had returned it. For tell requests,
+    /// where there's no caller to receive it, the error is passed to [`Actor::on_async_error`].
+    ///
+    /// Validation runs on the actor with read-only access to its state, so it can check messages against the current
+    /// state, such as rejecting a withdrawal larger than the balance.
+    ///
+    /// # Default Implementation
+    /// By default, every message is valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor, Default)]
+    /// struct Account {
+    ///     balance: u64,
+    /// }
+    ///
+    /// struct Withdraw(u64);
+    ///
+    /// impl Message<Withdraw> for Account {
+    ///     type Reply = Result<u64, String>;
+    ///
+    ///     fn validate(&self, Withdraw(amount): &Withdraw) -> Result<(), String> {
+    ///         if *amount > self.balance {
+    ///             return Err(format!("insufficient balance for withdrawal of {amount}"));
+    ///         }
+    ///         Ok(())
+    ///     }
+    ///
+    ///     async fn handle(&mut self, Withdraw(amount): Withdraw, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.balance -= amount;
+    ///         Ok(self.balance)
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(Account { balance: 10 });
+    /// assert!(actor_ref.ask(Withdraw(20)).await.is_err());
+    /// assert_eq!(actor_ref.ask(Withdraw(5)).await?, 5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[allow(unused_variables)]
+    fn validate(&self, msg: &T) -> Result<(), <Self::Reply as Reply>::Error> {
+        Ok(())
+    }
+
+    /// The default timeout for waiting for a reply to this message, or `None` to wait forever.
+    ///
+    /// This is applied to [`ask`](crate::actor::ActorRef::ask) requests which don't set a
+    /// [reply timeout](crate::request::AskRequest::reply_timeout) themselves, so the timeout can be declared once with
+    /// the message rather than repeated at every call site. A reply timeout set on the request always takes
+    /// precedence. Requests which time out fail with [`SendError::Timeout`], and
+    /// the handler can read the deadline with [`Context::reply_deadline`].
+    ///
+    /// The timeout isn't applied to blocking requests, such as
+    /// [`blocking_send`](crate::request::BlockingMessageSend::blocking_send), which always wait forever.
+    ///
+    /// # Default Implementation
+    /// By default, this returns `None`, waiting forever for a reply.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::message::{Context, Message};
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Search;
+    ///
+    /// struct Query(String);
+    ///
+    /// impl Message<Query> for Search {
+    ///     type Reply = Vec<String>;
+    ///
+    ///     async fn handle(&mut self, msg: Query, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         vec![msg.0]
+    ///     }
+    ///
+    ///     fn default_timeout() -> Option<Duration> {
+    ///         Some(Duration::from_millis(200))
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(Search);
+    /// // Times out after 200ms
+    /// actor_ref.ask(Query("kameo".to_string())).await?;
+    /// // Times out after 1s
+    /// actor_ref
+    ///     .ask(Query("kameo".to_string()))
+    ///     .reply_timeout(Duration::from_secs(1))
+    ///     .await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    fn default_timeout() -> Option<Duration> {
+        None
+    }
+}
+
+/// The approximate size of a message in bytes, used to reject oversized messages before they're enqueued.
+///
+/// An actor's mailbox is bounded by the number of messages rather than their size, so a single huge message can
+/// exceed a memory budget. Implementing [`Message::message_size`] with a `MessageSize` lets an actor reject messages
+/// larger than [`Actor::max_message_bytes`] with [`SendError::TooLarge`].
+///
+/// Sizes are reported by the message, and are only as accurate as its implementation. Typically only the heap
+/// allocated payload is counted.
+///
+/// # Example
+///
+/// ```
+/// use kameo::message::{Context, Message, MessageSize};
+/// use kameo::Actor;
+///
+/// struct Upload {
+///     name: String,
+///     data: Vec<u8>,
+/// }
+///
+/// impl MessageSize for Upload {
+///     fn message_size(&self) -> usize {
+///         self.name.message_size() + self.data.message_size()
+///     }
+/// }
+///
+/// struct Storage;
+///
+/// impl Actor for Storage {
+///     type Mailbox = kameo::mailbox::bounded::BoundedMailbox<Self>;
+///
+///     fn max_message_bytes() -> Option<usize> {
+///         Some(1024 * 1024)
+///     }
+/// }
+///
+/// impl Message<Upload> for Storage {
+///     type Reply = ();
+///
+///     async fn handle(&mut self, msg: Upload, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         // ...
+///     }
+///
+///     fn message_size(msg: &Upload) -> usize {
+///         msg.message_size()
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::spawn(Storage);
+/// let upload = Upload { name: "huge.bin".to_string(), data: vec![0; 2 * 1024 * 1024] };
+/// let err = actor_ref.tell(upload).await.unwrap_err();
+/// assert!(err.is_too_large());
+/// # });
+/// ```
+pub trait MessageSize {
+    /// Returns the approximate size of the message in bytes.
+    fn message_size(&self) -> usize;
+}
+
+impl MessageSize for str {
+    fn message_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl MessageSize for String {
+    fn message_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> MessageSize for [T] {
+    fn message_size(&self) -> usize {
+        mem::size_of_val(self)
+    }
+}
+
+impl<T> MessageSize for Vec<T> {
+    fn message_size(&self) -> usize {
+        mem::size_of_val(self.as_slice())
+    }
+}
+
+impl<T: MessageSize + ?Sized> MessageSize for Box<T> {
+    fn message_size(&self) -> usize {
+        (**self).message_size()
+    }
+}
+
+/// Returns whether a message is larger than the actor accepts, according to [`Actor::max_message_bytes`].
+pub(crate) fn is_too_large<A, M>(msg: &M) -> bool
+where
+    A: Message<M>,
+    M: Send + 'static,
+{
+    A::max_message_bytes().is_some_and(|max| <A as Message<M>>::message_size(msg) > max)
 }
 
 /// A type for handling streams attached to an actor.
@@ -64,14 +313,21 @@ pub enum StreamMessage<T, S, F> {
 }
 
 /// A context provided to message handlers providing access
-/// to the current actor ref, and reply channel.
+/// to the current actor ref, reply channel, and extensions.
 #[derive(Debug)]
 pub struct Context<'r, A: Actor, R: ?Sized>
 where
     R: Reply,
 {
     actor_ref: ActorRef<A>,
-    reply: &'r mut Option<ReplySender<R::Value>>,
+    reply: &'r mut Option<BoxReplySender>,
+    extensions: &'r mut Extensions,
+    meta: &'r MessageMeta<A>,
+    locals: &'r mut ActorLocals,
+    replied_early: &'r mut Option<RepliedEarly>,
+    mailbox_len: usize,
+    started_at: Instant,
+    phantom: PhantomData<R>,
 }
 
 impl<'r, A, R> Context<'r, A, R>
@@ -81,9 +337,24 @@ where
 {
     pub(crate) fn new(
         actor_ref: ActorRef<A>,
-        reply: &'r mut Option<ReplySender<R::Value>>,
+        reply: &'r mut Option<BoxReplySender>,
+        extensions: &'r mut Extensions,
+        meta: &'r MessageMeta<A>,
+        locals: &'r mut ActorLocals,
+        replied_early: &'r mut Option<RepliedEarly>,
+        mailbox_len: usize,
     ) -> Self {
-        Context { actor_ref, reply }
+        Context {
+            actor_ref,
+            reply,
+            extensions,
+            meta,
+            locals,
+            replied_early,
+            mailbox_len,
+            started_at: Instant::now(),
+            phantom: PhantomData,
+        }
     }
 
     /// Returns the current actor's ref, allowing messages to be sent to itself.
@@ -91,6 +362,331 @@ where
         self.actor_ref.clone()
     }
 
+    /// Returns the number of messages and signals which were waiting in the mailbox when this message was received.
+    ///
+    /// This can be used by handlers to detect when the actor is falling behind, and shed load by returning
+    /// a cheaper, degraded result.
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// struct Search(String);
+    ///
+    /// impl Message<Search> for MyActor {
+    ///     type Reply = Vec<String>;
+    ///
+    ///     async fn handle(&mut self, msg: Search, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         if ctx.mailbox_len() > 100 {
+    ///             // Overloaded, skip the expensive search
+    ///             return vec![];
+    ///         }
+    ///         vec![msg.0]
+    ///     }
+    /// }
+    /// ```
+    pub fn mailbox_len(&self) -> usize {
+        self.mailbox_len
+    }
+
+    /// Returns the instant the actor started handling the current message.
+    pub fn started_at(&self) -> Instant {
+        self.started_at
+    }
+
+    /// Returns the time elapsed since the actor started handling the current message.
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Returns the instant after which the caller stops waiting for a reply, if the message was sent with a reply timeout.
+    ///
+    /// The deadline is advisory. Kameo doesn't cancel the handler once it passes, but any reply sent afterwards is
+    /// discarded, so a handler can check the deadline to skip work which would be wasted.
+    ///
+    /// This is `None` for tell requests, and for ask requests sent without a reply timeout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Instant;
+    ///
+    /// use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// struct Search(String);
+    ///
+    /// impl Message<Search> for MyActor {
+    ///     type Reply = Option<Vec<String>>;
+    ///
+    ///     async fn handle(&mut self, msg: Search, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         if ctx.reply_deadline().is_some_and(|deadline| deadline <= Instant::now()) {
+    ///             return None;
+    ///         }
+    ///         Some(vec![msg.0])
+    ///     }
+    /// }
+    /// ```
+    pub fn reply_deadline(&self) -> Option<Instant> {
+        self.meta.reply_deadline
+    }
+
+    /// Returns `true` if the caller has stopped waiting for the reply, such as by dropping the reply future.
+    ///
+    /// The reply would be discarded, so long running handlers can check this to skip the rest of their work. Messages
+    /// sent with [`tell`](crate::actor::ActorRef::tell), and handlers which have already replied or taken the
+    /// [`reply_sender`](Context::reply_sender), are never abandoned.
+    ///
+    /// Actors can also abort their handlers automatically, with
+    /// [`Actor::abort_abandoned_requests`].
+    pub fn is_reply_abandoned(&self) -> bool {
+        self.reply.as_ref().is_some_and(|tx| tx.is_closed())
+    }
+
+    /// Waits until the caller stops waiting for the reply.
+    ///
+    /// This is useful with [`tokio::select!`] to stop waiting on other work once the reply would be discarded. If the
+    /// reply can never be abandoned, such as for a message sent with [`tell`](crate::actor::ActorRef::tell), this
+    /// never completes. See [`Context::is_reply_abandoned`].
+    pub async fn reply_abandoned(&mut self) {
+        match self.reply.as_mut() {
+            Some(tx) => tx.closed().await,
+            None => future::pending().await,
+        }
+    }
+
+    /// Sends a message to another actor and waits for a reply, within the time remaining for the current message.
+    ///
+    /// If the current message has a [reply deadline](Context::reply_deadline), the request inherits the remaining
+    /// budget as its reply timeout, or the message's [default timeout](Message::default_timeout) if that's shorter.
+    /// The other actor sees the same deadline, and any requests it sends with `Context::ask` inherit it in turn, so a
+    /// whole tree of requests stops waiting at the point the original caller gave up. If the deadline has already
+    /// passed, the message isn't sent, and [`SendError::Timeout`] is returned with the message.
+    ///
+    /// Without a deadline, this is the same as [`ActorRef::ask`]. To opt out of inheriting the deadline, send the
+    /// request with [`ActorRef::ask`] directly.
+    ///
+    /// Deadlines are measured with [`Instant`], the monotonic clock of the current process, so they aren't affected
+    /// by changes to the system time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::ActorRef;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct Database;
+    /// #
+    /// # struct Query;
+    /// #
+    /// # impl Message<Query> for Database {
+    /// #     type Reply = u32;
+    /// #     async fn handle(&mut self, _: Query, _: Context<'_, Self, Self::Reply>) -> Self::Reply { 42 }
+    /// # }
+    /// #
+    /// #[derive(kameo::Actor)]
+    /// struct Api {
+    ///     db: ActorRef<Database>,
+    /// }
+    ///
+    /// struct Request;
+    ///
+    /// impl Message<Request> for Api {
+    ///     type Reply = Result<u32, kameo::error::SendError<Query>>;
+    ///
+    ///     async fn handle(&mut self, _: Request, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         // Gives up once the caller of `Request` stops waiting
+    ///         ctx.ask(&self.db, Query).await
+    ///     }
+    /// }
+    /// ```
+    pub async fn ask<B, M>(
+        &self,
+        actor_ref: &ActorRef<B>,
+        msg: M,
+    ) -> Result<<B::Reply as Reply>::Ok, SendError<M, <B::Reply as Reply>::Error>>
+    where
+        B: Message<M>,
+        M: Send + 'static,
+        for<'a> AskRequest<
+            LocalAskRequest<'a, B, B::Mailbox>,
+            B::Mailbox,
+            M,
+            WithoutRequestTimeout,
+            WithoutRequestTimeout,
+        >: MessageSend<
+            Ok = <B::Reply as Reply>::Ok,
+            Error = SendError<M, <B::Reply as Reply>::Error>,
+        >,
+        for<'a> AskRequest<
+            LocalAskRequest<'a, B, B::Mailbox>,
+            B::Mailbox,
+            M,
+            WithoutRequestTimeout,
+            WithRequestTimeout,
+        >: MessageSend<
+            Ok = <B::Reply as Reply>::Ok,
+            Error = SendError<M, <B::Reply as Reply>::Error>,
+        >,
+    {
+        let Some(deadline) = self.reply_deadline() else {
+            return actor_ref.ask(msg).send().await;
+        };
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(SendError::Timeout(Some(msg)));
+        }
+        let timeout = <B as Message<M>>::default_timeout()
+            .map_or(remaining, |default| default.min(remaining));
+        actor_ref.ask(msg).reply_timeout(timeout).send().await
+    }
+
+    /// Returns the id of the actor which sent the current message, if it was sent from within an actor.
+    ///
+    /// The sender is the actor whose task the message was sent from, such as from one of its message handlers or
+    /// lifecycle hooks. This can be used for simple authorization, such as only accepting a message from a
+    /// supervisor, or for attributing messages in logs.
+    ///
+    /// This is `None` for messages sent from outside of an actor, including from tasks spawned by an actor, and for
+    /// messages sent from remote actors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::ActorID;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor { supervisor: ActorID }
+    /// #
+    /// struct Reconfigure;
+    ///
+    /// impl Message<Reconfigure> for MyActor {
+    ///     type Reply = Result<(), &'static str>;
+    ///
+    ///     async fn handle(&mut self, _msg: Reconfigure, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         if ctx.sender_id() != Some(self.supervisor) {
+    ///             return Err("only the supervisor may reconfigure this actor");
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    /// ```
+    pub fn sender_id(&self) -> Option<ActorID> {
+        self.meta.sender_id
+    }
+
+    /// Returns the extensions attached to the message currently being handled.
+    ///
+    /// Message extensions are set by the sender using `extension` on a local ask or tell request,
+    /// and are dropped once the handler returns. They're useful for passing request scoped context,
+    /// such as authentication details, alongside a message without adding fields to the message itself.
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct UserId(u64);
+    ///
+    /// struct Whoami;
+    ///
+    /// impl Message<Whoami> for MyActor {
+    ///     type Reply = Option<u64>;
+    ///
+    ///     async fn handle(&mut self, _: Whoami, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         ctx.extensions().get::<UserId>().map(|id| id.0)
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// let id = actor_ref.ask(Whoami).extension(UserId(7)).await?;
+    /// assert_eq!(id, Some(7));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn extensions(&self) -> &Extensions {
+        self.extensions
+    }
+
+    /// Returns a value from the [`Ambient`] context the message was sent with, if present.
+    ///
+    /// Messages sent by the handler carry the same ambient context along. See [`Ambient`] for more information.
+    pub fn ambient<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.meta.ambient.as_ref()?.get()
+    }
+
+    /// Returns a mutable reference to the extensions attached to the message currently being handled.
+    pub fn extensions_mut(&mut self) -> &mut Extensions {
+        self.extensions
+    }
+
+    /// Returns the extensions stored alongside the actor's state.
+    ///
+    /// Actor extensions start out empty when the actor is spawned, and live for as long as the actor is running.
+    /// Unlike [`Context::extensions`], values inserted here are visible to every message handled afterwards.
+    pub fn actor_extensions(&self) -> &Extensions {
+        &self.locals.extensions
+    }
+
+    /// Returns a mutable reference to the extensions stored alongside the actor's state.
+    pub fn actor_extensions_mut(&mut self) -> &mut Extensions {
+        &mut self.locals.extensions
+    }
+
+    /// Acknowledges the message currently being handled, if an [`Ack`] was attached by the sender.
+    ///
+    /// Acknowledgements are separate from replies, allowing a handler to signal that a message has been durably
+    /// processed, such as after a database commit, independently of when or whether a reply is sent.
+    /// If the handler returns without acknowledging the message, the sender's [`AckReceiver`] resolves with [`Nack`].
+    ///
+    /// Returns `false` if no `Ack` was attached to the message, or it was already acknowledged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Ack, Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor;
+    /// #
+    /// struct Save(String);
+    ///
+    /// impl Message<Save> for MyActor {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, msg: Save, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         // Commit to the database...
+    ///         ctx.ack();
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// let (ack, acked) = Ack::new();
+    /// actor_ref.tell(Save("hello".to_string())).extension(ack).await?;
+    /// acked.await?; // Safe to remove the message from the upstream queue
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    ///
+    /// [`Nack`]: crate::error::Nack
+    pub fn ack(&mut self) -> bool {
+        self.extensions
+            .remove::<Ack>()
+            .map(|ack| ack.ack())
+            .unwrap_or(false)
+    }
+
     /// Extracts the reply sender, providing a mechanism for delegated responses and an optional reply sender.
     ///
     /// This method is designed for scenarios where the response to a message is not immediate and needs to be
@@ -140,43 +736,393 @@ where
     /// back to the requester. Failure to do so could result in the requester waiting indefinitely for a response.
     #[must_use = "the reply must be sent to the ReplySender"]
     pub fn reply_sender(&mut self) -> (DelegatedReply<R::Value>, Option<ReplySender<R::Value>>) {
-        (DelegatedReply::new(), self.reply.take())
+        (
+            DelegatedReply::new(),
+            self.reply.take().map(ReplySender::new),
+        )
     }
 
     /// Sends a reply to the caller early, returning a `DelegatedReply`.
     ///
     /// This is a shortcut for creating a `DelegatedReply` in cases where you didn't need access to the `ReplySender`.
     pub fn reply(&mut self, reply: R::Value) -> DelegatedReply<R::Value> {
-        if let Some(reply_sender) = self.reply.take() {
-            reply_sender.send(reply);
+        if let Some(tx) = self.reply.take() {
+            ReplySender::new(tx).send(reply);
         }
         DelegatedReply::new()
     }
 
-    /// Forwards the message to another actor, returning a [ForwardedReply].
+    /// Sends a reply to the caller immediately, letting the handler continue with work the caller doesn't wait for.
     ///
-    /// The message will be sent handled by another actor without blocking the current actor.
-    pub fn forward<B, M, R2, E>(
-        &mut self,
-        actor_ref: ActorRef<B>,
-        message: M,
-    ) -> ForwardedReply<R::Ok, M, E>
-    where
-        B: Message<M, Reply = R2>,
-        M: Unpin + Send + Sync + 'static,
-        R: Reply<Error = SendError<M, E>, Value = Result<<R as Reply>::Ok, SendError<M, E>>>,
-        R2: Reply<Ok = R::Ok, Error = E, Value = Result<R::Ok, E>>,
-        E: fmt::Debug + Unpin + Send + Sync + 'static,
-        R::Ok: Unpin,
-        for<'a> AskRequest<
-            LocalAskRequest<'a, B, B::Mailbox>,
-            B::Mailbox,
-            M,
-            WithoutRequestTimeout,
-            WithoutRequestTimeout,
-        >: MessageSend<Ok = R::Ok, Error = SendError<M, E>>,
-    {
-        let (delegated_reply, reply_sender) = self.reply_sender();
+    /// Unlike [`reply`](Context::reply), this works with any reply type, and the value later returned by the handler
+    /// is ignored, so it can return anything, such as a default value. This reduces the caller's latency for handlers
+    /// with trailing work, like cleaning up or sending follow-up messages.
+    ///
+    /// The reply can only be sent once, so calling this again has no effect. If the handler panics after replying,
+    /// the caller has already received its reply, and the panic is handled by [`Actor::on_panic`] as usual.
+    ///
+    /// If the message was sent with [`tell`](ActorRef::tell), there's no caller to reply to, and an error passed to
+    /// this method is handled by [`Actor::on_async_error`] in place of the handler's return value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct Cache { entries: Vec<u32> }
+    /// #
+    /// struct Insert(u32);
+    ///
+    /// impl Message<Insert> for Cache {
+    ///     type Reply = usize;
+    ///
+    ///     async fn handle(&mut self, Insert(n): Insert, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         self.entries.push(n);
+    ///         ctx.reply_now(self.entries.len());
+    ///
+    ///         // The caller already has its reply
+    ///         self.entries.sort_unstable();
+    ///         0 // Ignored
+    ///     }
+    /// }
+    /// ```
+    pub fn reply_now(&mut self, value: R::Value) {
+        if self.replied_early.is_some() {
+            return;
+        }
+        let err = match self.reply.take() {
+            Some(tx) => {
+                ReplySender::new(tx).send(value);
+                None
+            }
+            None => value.into_boxed_err(),
+        };
+        *self.replied_early = Some(RepliedEarly(Mutex::new(err)));
+    }
+
+    /// Declines to reply to the caller, returning a `DelegatedReply`.
+    ///
+    /// This is useful for handlers which sometimes have nothing to reply with. If the message was sent with `ask`,
+    /// the caller receives [`SendError::NoReply`] rather than waiting indefinitely.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    /// use kameo::reply::DelegatedReply;
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct MyActor { cache: std::collections::HashMap<u32, String> }
+    /// #
+    /// struct Lookup(u32);
+    ///
+    /// impl Message<Lookup> for MyActor {
+    ///     type Reply = DelegatedReply<String>;
+    ///
+    ///     async fn handle(&mut self, Lookup(key): Lookup, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         match self.cache.get(&key) {
+    ///             Some(value) => ctx.reply(value.clone()),
+    ///             None => ctx.decline(),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn decline(&mut self) -> DelegatedReply<R::Value> {
+        if let Some(tx) = self.reply.take() {
+            ReplySender::<R::Value>::new(tx).decline();
+        }
+        DelegatedReply::new()
+    }
+
+    /// Stops the actor with the given reason once the current message has been handled.
+    ///
+    /// Unlike panicking, the handler finishes normally and its reply is still sent to the caller before the actor stops.
+    /// No further messages are handled, and [`Actor::on_stop`] is called with `reason`.
+    ///
+    /// This is useful for actors which reach a terminal state, such as a connection actor whose peer has closed.
+    /// If called more than once while handling a message, the last reason is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::error::ActorStopReason;
+    /// use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct Connection;
+    /// #
+    /// struct PeerClosed;
+    ///
+    /// impl Message<PeerClosed> for Connection {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _msg: PeerClosed, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         ctx.stop(ActorStopReason::Normal);
+    ///     }
+    /// }
+    /// ```
+    pub fn stop(&mut self, reason: ActorStopReason) {
+        self.locals.stop_request = Some(reason);
+    }
+
+    /// Yields to the runtime, then returns `true` if the actor has been asked to stop.
+    ///
+    /// The actor has been asked to stop once [`ActorRef::stop_gracefully`] or [`ActorRef::close`] has been called,
+    /// or the handler has called [`Context::stop`]. Long running handlers, such as CPU heavy loops which rarely
+    /// await, can call this between chunks of work to let other tasks on the same worker thread run, and to finish
+    /// early when the actor is shutting down rather than delaying [`Actor::on_stop`].
+    ///
+    /// This only helps handlers which call it. A handler which never awaits still holds its worker thread until it
+    /// returns, and returning early is up to the handler, since the actor handles any messages queued before the stop
+    /// signal either way.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct Hasher;
+    /// #
+    /// struct HashChunks(Vec<Vec<u8>>);
+    ///
+    /// impl Message<HashChunks> for Hasher {
+    ///     type Reply = Vec<u64>;
+    ///
+    ///     async fn handle(&mut self, msg: HashChunks, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         let mut hashes = Vec::new();
+    ///         for chunk in msg.0 {
+    ///             hashes.push(chunk.iter().map(|b| *b as u64).sum());
+    ///             if ctx.yield_if_should_stop().await {
+    ///                 break;
+    ///             }
+    ///         }
+    ///         hashes
+    ///     }
+    /// }
+    /// ```
+    pub async fn yield_if_should_stop(&self) -> bool {
+        tokio::task::yield_now().await;
+        self.actor_ref.info.stop_requested.load(Ordering::Acquire)
+            || self.locals.stop_request.is_some()
+    }
+
+    /// Spawns a background task which is aborted when the actor stops.
+    ///
+    /// The task is given a [`WeakActorRef`] to send results back to the actor without keeping it alive. Tasks
+    /// which are still running when the actor stops are aborted before [`Actor::on_stop`] is called, so background
+    /// work such as polling or cleanup never outlives the actor. The returned handle can abort the task sooner.
+    ///
+    /// A panic in the task doesn't affect the actor. Use [`Context::spawn_linked_task`] to handle it as a panic
+    /// in the actor instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kameo::message::{Context, Message};
+    ///
+    /// # #[derive(kameo::Actor)]
+    /// # struct Poller;
+    /// #
+    /// # impl Message<Poll> for Poller {
+    /// #     type Reply = ();
+    /// #     async fn handle(&mut self, _msg: Poll, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { }
+    /// # }
+    /// #
+    /// struct StartPolling;
+    /// struct Poll;
+    ///
+    /// impl Message<StartPolling> for Poller {
+    ///     type Reply = ();
+    ///
+    ///     async fn handle(&mut self, _msg: StartPolling, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         ctx.spawn_task(|actor_ref| async move {
+    ///             loop {
+    ///                 tokio::time::sleep(Duration::from_secs(1)).await;
+    ///                 let Some(actor_ref) = actor_ref.upgrade() else {
+    ///                     break;
+    ///                 };
+    ///                 let _ = actor_ref.tell(Poll).await;
+    ///             }
+    ///         });
+    ///     }
+    /// }
+    /// ```
+    pub fn spawn_task<F, Fut>(&mut self, f: F) -> AbortHandle
+    where
+        F: FnOnce(WeakActorRef<A>) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (task, handle) = self.track_task(f(self.actor_ref.downgrade()));
+        self.actor_ref.info.spawn(task);
+        handle
+    }
+
+    /// Creates a [`RequestScope`] for sending requests to other actors concurrently.
+    ///
+    /// Requests in the scope which haven't completed are cancelled when it's dropped, so a handler which returns
+    /// early or panics leaves no requests running. See [`RequestScope`] for more information.
+    pub fn scope(&self) -> RequestScope {
+        RequestScope::new()
+    }
+
+    /// Spawns a background task which is aborted when the actor stops, and whose panics are handled by the actor.
+    ///
+    /// This is the same as [`Context::spawn_task`], except that if the task panics, [`Actor::on_panic`] is called
+    /// with the panic as though a message handler had panicked, which stops the actor by default.
+    pub fn spawn_linked_task<F, Fut>(&mut self, f: F) -> AbortHandle
+    where
+        F: FnOnce(WeakActorRef<A>) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let actor_ref = self.actor_ref.downgrade();
+        let task = AssertUnwindSafe(f(actor_ref.clone())).catch_unwind();
+        let (task, handle) = self.track_task(async move {
+            if let Err(err) = task.await {
+                if let Some(actor_ref) = actor_ref.upgrade() {
+                    let _ = actor_ref
+                        .mailbox()
+                        .send(Signal::TaskPanicked(PanicError::new_panic(err)))
+                        .await;
+                }
+            }
+        });
+        self.actor_ref.info.spawn(task);
+        handle
+    }
+
+    /// Makes a background task abortable, keeping its handle until it finishes so it's aborted when the actor stops.
+    fn track_task<Fut>(&mut self, task: Fut) -> (BoxFuture<'static, ()>, AbortHandle)
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (handle, registration) = AbortHandle::new_pair();
+        let running = Arc::new(());
+        let tasks = &mut self.locals.spawned_tasks;
+        tasks.retain(|(_, running)| running.strong_count() > 0);
+        tasks.push((handle.clone(), Arc::downgrade(&running)));
+        let task = async move {
+            let _running = running;
+            let _ = Abortable::new(task, registration).await;
+        };
+        (task.boxed(), handle)
+    }
+
+    /// Delegates the reply to another actor, sending it a message whose reply goes straight to the original caller.
+    ///
+    /// Rather than waiting for the other actor's reply and sending it back, the caller's reply channel is passed
+    /// along with `message`, so the other actor's handler replies to the caller directly. It may itself delegate
+    /// further with `delegate_to`, forming a chain of delegations of any length, where one reply is delivered to the
+    /// original caller once an actor in the chain finally replies.
+    ///
+    /// If the chain breaks before a reply is sent, such as when an actor in the chain isn't running or stops before
+    /// handling the message, the caller receives [`SendError::ActorStopped`]. The caller's reply deadline, if any, is
+    /// carried along the chain.
+    ///
+    /// This waits for capacity in the other actor's mailbox. For messages sent with [`tell`](ActorRef::tell),
+    /// `message` is sent without expecting a reply.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kameo::actor::ActorRef;
+    /// use kameo::message::{Context, Message};
+    /// use kameo::reply::DelegatedReply;
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Gateway {
+    ///     backend: ActorRef<Backend>,
+    /// }
+    ///
+    /// #[derive(kameo::Actor)]
+    /// struct Backend;
+    ///
+    /// struct Lookup(u32);
+    ///
+    /// impl Message<Lookup> for Gateway {
+    ///     type Reply = DelegatedReply<String>;
+    ///
+    ///     async fn handle(&mut self, msg: Lookup, mut ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         ctx.delegate_to(&self.backend, msg).await
+    ///     }
+    /// }
+    ///
+    /// impl Message<Lookup> for Backend {
+    ///     type Reply = String;
+    ///
+    ///     async fn handle(&mut self, Lookup(id): Lookup, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+    ///         format!("user-{id}")
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let backend = kameo::spawn(Backend);
+    /// let gateway = kameo::spawn(Gateway { backend });
+    /// assert_eq!(gateway.ask(Lookup(7)).await?, "user-7");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn delegate_to<B, M>(
+        &mut self,
+        actor_ref: &ActorRef<B>,
+        message: M,
+    ) -> DelegatedReply<R::Value>
+    where
+        B: Message<M>,
+        B::Reply: Reply<Ok = R::Ok, Error = R::Error>,
+        M: Send + 'static,
+    {
+        let reply = self.reply.take();
+        let mut meta = MessageMeta::for_send();
+        meta.reply_deadline = self.meta.reply_deadline;
+        meta.delegated = reply.is_some();
+        let signal = Signal::Message {
+            message: Box::new(message),
+            actor_ref: actor_ref.clone(),
+            reply,
+            sent_within_actor: actor_ref.is_current(),
+            sent_at: Instant::now(),
+            extensions: Extensions::new(),
+            meta,
+        };
+        if actor_ref.is_closed() {
+            signal.reject();
+        } else if let Err(tokio::sync::mpsc::error::SendError(signal)) =
+            actor_ref.mailbox().send(signal).await
+        {
+            signal.reject();
+        }
+
+        DelegatedReply::new()
+    }
+
+    /// Forwards the message to another actor, returning a [ForwardedReply].
+    ///
+    /// The message will be sent handled by another actor without blocking the current actor.
+    pub fn forward<B, M, R2, E>(
+        &mut self,
+        actor_ref: ActorRef<B>,
+        message: M,
+    ) -> ForwardedReply<R::Ok, M, E>
+    where
+        B: Message<M, Reply = R2>,
+        M: Unpin + Send + Sync + 'static,
+        R: Reply<Error = SendError<M, E>, Value = Result<<R as Reply>::Ok, SendError<M, E>>>,
+        R2: Reply<Ok = R::Ok, Error = E, Value = Result<R::Ok, E>>,
+        E: fmt::Debug + Unpin + Send + Sync + 'static,
+        R::Ok: Unpin,
+        for<'a> AskRequest<
+            LocalAskRequest<'a, B, B::Mailbox>,
+            B::Mailbox,
+            M,
+            WithoutRequestTimeout,
+            WithoutRequestTimeout,
+        >: MessageSend<Ok = R::Ok, Error = SendError<M, E>>,
+    {
+        let (delegated_reply, reply_sender) = self.reply_sender();
         tokio::spawn(async move {
             let reply = MessageSend::send(actor_ref.ask(message)).await;
             if let Some(reply_sender) = reply_sender {
@@ -188,6 +1134,371 @@ where
     }
 }
 
+/// A handle used to acknowledge that a message has been processed, separately from its reply.
+///
+/// An `Ack` is attached to a message as an extension, and acknowledged by the handler with [`Context::ack`].
+/// Dropping an `Ack` without acknowledging it is treated as a negative acknowledgement, causing the
+/// corresponding [`AckReceiver`] to resolve with [`Nack`].
+///
+/// The handler may take ownership of the `Ack` with [`Extensions::remove`] to acknowledge it after returning,
+/// for example once a delegated reply has completed.
+#[derive(Debug)]
+pub struct Ack {
+    tx: oneshot::Sender<()>,
+}
+
+impl Ack {
+    /// Creates a new `Ack`, along with the receiver which resolves once it is acknowledged.
+    pub fn new() -> (Ack, AckReceiver) {
+        let (tx, rx) = oneshot::channel();
+        (Ack { tx }, AckReceiver { rx })
+    }
+
+    /// Acknowledges the message, returning `false` if the receiver was dropped.
+    pub fn ack(self) -> bool {
+        self.tx.send(()).is_ok()
+    }
+}
+
+/// A future which resolves once the corresponding [`Ack`] is acknowledged,
+/// or with [`Nack`] if it is dropped without being acknowledged.
+#[derive(Debug)]
+pub struct AckReceiver {
+    rx: oneshot::Receiver<()>,
+}
+
+impl Future for AckReceiver {
+    type Output = Result<(), Nack>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.rx)
+            .poll(cx)
+            .map(|res| res.map_err(|_| Nack))
+    }
+}
+
+type CloneMessageFn<A> = Box<dyn Fn() -> Box<dyn DynMessage<A>> + Send>;
+
+/// A copy of a message kept by the actor, so it can be redelivered if its handler panics.
+pub(crate) struct Redelivery<A: Actor> {
+    pub(crate) remaining: u32,
+    message: std::sync::Mutex<CloneMessageFn<A>>,
+}
+
+impl<A: Actor> Redelivery<A> {
+    pub(crate) fn new<M>(msg: &M, max_redeliveries: u32) -> Self
+    where
+        A: Message<M>,
+        M: Clone + Send + 'static,
+    {
+        let msg = msg.clone();
+        Redelivery {
+            remaining: max_redeliveries,
+            message: std::sync::Mutex::new(Box::new(move || Box::new(msg.clone()))),
+        }
+    }
+
+    /// Returns a new copy of the message.
+    pub(crate) fn message(&self) -> Box<dyn DynMessage<A>> {
+        (self.message.lock().unwrap_or_else(|err| err.into_inner()))()
+    }
+}
+
+/// Marks a message which was replied to with [`Context::reply_now`], along with the error to report if it had no caller.
+#[derive(Debug)]
+pub(crate) struct RepliedEarly(Mutex<Option<BoxDebug>>);
+
+/// Data attached to a message by the runtime, kept apart from the [`Extensions`] set by the sender.
+#[doc(hidden)]
+pub struct MessageMeta<A: Actor> {
+    /// The id of the actor which sent the message.
+    pub(crate) sender_id: Option<ActorID>,
+    /// The ambient context the message was sent with.
+    pub(crate) ambient: Option<Ambient>,
+    /// The tracing span which was active when the message was sent.
+    #[cfg(feature = "tracing")]
+    pub(crate) sender_span: Option<Box<tracing::Span>>,
+    /// The instant after which the caller of an ask request stops waiting for a reply.
+    pub(crate) reply_deadline: Option<Instant>,
+    /// Whether the reply is sent to the caller of another message, which delegated it.
+    pub(crate) delegated: bool,
+    /// A copy of the message, if it's redelivered when its handler panics.
+    pub(crate) redelivery: Option<Box<Redelivery<A>>>,
+}
+
+impl<A: Actor> MessageMeta<A> {
+    /// Creates the metadata for a message being sent, capturing the sender's id, ambient context and tracing span.
+    pub(crate) fn for_send() -> Self {
+        let ambient = Ambient::current();
+        MessageMeta {
+            sender_id: CURRENT_ACTOR_ID.try_with(Clone::clone).ok(),
+            ambient: (!ambient.is_empty()).then_some(ambient),
+            #[cfg(feature = "tracing")]
+            sender_span: Some(tracing::Span::current())
+                .filter(|span| !span.is_none())
+                .map(Box::new),
+            ..MessageMeta::default()
+        }
+    }
+}
+
+impl<A: Actor> Default for MessageMeta<A> {
+    fn default() -> Self {
+        MessageMeta {
+            sender_id: None,
+            ambient: None,
+            #[cfg(feature = "tracing")]
+            sender_span: None,
+            reply_deadline: None,
+            delegated: false,
+            redelivery: None,
+        }
+    }
+}
+
+impl<A: Actor> fmt::Debug for MessageMeta<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageMeta")
+            .field("sender_id", &self.sender_id)
+            .field("reply_deadline", &self.reply_deadline)
+            .field("delegated", &self.delegated)
+            .finish_non_exhaustive()
+    }
+}
+
+/// State kept alongside the actor's own state, which its message handlers access through their [`Context`].
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct ActorLocals {
+    /// The extensions returned by [`Context::actor_extensions`].
+    pub(crate) extensions: Extensions,
+    /// A request from a message handler to stop the actor once the message has been handled.
+    pub(crate) stop_request: Option<ActorStopReason>,
+    /// Background tasks spawned by the actor's message handlers, which are aborted when the actor stops.
+    ///
+    /// Each task holds a strong reference to its marker until it finishes.
+    pub(crate) spawned_tasks: Vec<(AbortHandle, Weak<()>)>,
+}
+
+/// Creates the span a message handler runs in, linked to the span which was active when the message was sent.
+///
+/// For ask requests, the sender is waiting on the reply, so the handler span is a child of the sender's span.
+/// For tell requests, the sender's span may have already closed by the time the message is handled,
+/// so the handler span instead follows from it.
+///
+/// The span is created at the level returned by [`Actor::log_level`].
+#[cfg(feature = "tracing")]
+fn handler_span<T>(
+    actor_name: &str,
+    level: tracing::Level,
+    sender_span: tracing::Span,
+    is_ask: bool,
+) -> tracing::Span {
+    // Span levels must be known at compile time, so a span is created for each level
+    macro_rules! span {
+        ($($args:tt)*) => {
+            match level {
+                tracing::Level::TRACE => tracing::trace_span!($($args)*),
+                tracing::Level::DEBUG => tracing::debug_span!($($args)*),
+                tracing::Level::INFO => tracing::info_span!($($args)*),
+                tracing::Level::WARN => tracing::warn_span!($($args)*),
+                tracing::Level::ERROR => tracing::error_span!($($args)*),
+            }
+        };
+    }
+
+    let span = if is_ask {
+        span!(
+            parent: &sender_span,
+            "handle_message",
+            actor = actor_name,
+            message = any::type_name::<T>(),
+        )
+    } else {
+        let span = span!(
+            parent: None,
+            "handle_message",
+            actor = actor_name,
+            message = any::type_name::<T>(),
+        );
+        span.follows_from(&sender_span);
+        span
+    };
+
+    if span.is_disabled() {
+        sender_span
+    } else {
+        span
+    }
+}
+
+/// A type map of values, keyed by their type.
+///
+/// Extensions are used to attach arbitrary data to a message or an actor, and are accessible to message handlers
+/// through [`Context::extensions`] and [`Context::actor_extensions`].
+#[derive(Default)]
+pub struct Extensions {
+    map: HashMap<any::TypeId, Box<dyn any::Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Creates an empty `Extensions`.
+    pub fn new() -> Self {
+        Extensions::default()
+    }
+
+    /// Inserts a value, returning the previous value of the same type if one was present.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, val: T) -> Option<T> {
+        self.map
+            .insert(any::TypeId::of::<T>(), Box::new(val))
+            .and_then(|prev| prev.downcast().ok().map(|prev| *prev))
+    }
+
+    /// Returns a reference to a value of type `T`, if present.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.map
+            .get(&any::TypeId::of::<T>())
+            .and_then(|val| val.downcast_ref())
+    }
+
+    /// Returns a mutable reference to a value of type `T`, if present.
+    pub fn get_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut T> {
+        self.map
+            .get_mut(&any::TypeId::of::<T>())
+            .and_then(|val| val.downcast_mut())
+    }
+
+    /// Removes a value of type `T`, returning it if present.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> Option<T> {
+        self.map
+            .remove(&any::TypeId::of::<T>())
+            .and_then(|val| val.downcast().ok().map(|val| *val))
+    }
+
+    /// Returns `true` if a value of type `T` is present.
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.map.contains_key(&any::TypeId::of::<T>())
+    }
+
+    /// Removes all values.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the number of values stored.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if no values are stored.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.map.len())
+            .finish()
+    }
+}
+
+/// Values which are propagated along with every message sent, such as a tenant or request id.
+///
+/// An ambient context is set for a future with [`Ambient::scope`], usually where a request enters the system. Every
+/// ask and tell request sent from within the future captures the current ambient context, and the handler of the
+/// message runs with it restored, so messages sent by the handler carry it on to the next actor without any manual
+/// plumbing. Handlers can read values with [`Context::ambient`], and code which isn't a handler, such as logging or
+/// metrics, can read them with [`Ambient::current`].
+///
+/// The context is captured when a request is created, and is immutable once captured, so a handler which wants to
+/// change a value for downstream messages can build a new context from [`Ambient::current`] and scope its sends
+/// with it. Tell requests behave the same as ask requests, even though the sender may have moved on by the time the
+/// message is handled. Tasks spawned with `tokio::spawn` don't inherit the context, and it isn't sent to remote
+/// actors.
+///
+/// # Example
+///
+/// ```
+/// use kameo::message::{Ambient, Context, Message};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct TenantId(u64);
+///
+/// #[derive(kameo::Actor)]
+/// struct Orders;
+///
+/// struct PlaceOrder;
+///
+/// impl Message<PlaceOrder> for Orders {
+///     type Reply = Option<TenantId>;
+///
+///     async fn handle(&mut self, _: PlaceOrder, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         ctx.ambient::<TenantId>().copied()
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let orders = kameo::spawn(Orders);
+/// let tenant = Ambient::new()
+///     .with(TenantId(42))
+///     .scope(async { orders.ask(PlaceOrder).await })
+///     .await?;
+/// assert_eq!(tenant, Some(TenantId(42)));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct Ambient {
+    map: Arc<HashMap<any::TypeId, Arc<dyn any::Any + Send + Sync>>>,
+}
+
+impl Ambient {
+    /// Creates an empty ambient context.
+    pub fn new() -> Self {
+        Ambient::default()
+    }
+
+    /// Returns the ambient context of the current task, or an empty context if none is set.
+    ///
+    /// Within a message handler, this is the context captured when the message was sent.
+    pub fn current() -> Self {
+        CURRENT_AMBIENT.try_with(Clone::clone).unwrap_or_default()
+    }
+
+    /// Adds a value to the context, replacing any previous value of the same type.
+    pub fn with<T: Send + Sync + 'static>(mut self, val: T) -> Self {
+        Arc::make_mut(&mut self.map).insert(any::TypeId::of::<T>(), Arc::new(val));
+        self
+    }
+
+    /// Returns a reference to a value of type `T`, if present.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.map
+            .get(&any::TypeId::of::<T>())
+            .and_then(|val| val.downcast_ref())
+    }
+
+    /// Returns `true` if the context has no values.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Runs a future with this as the current ambient context, so requests sent from it carry the context along.
+    pub async fn scope<F: Future>(self, f: F) -> F::Output {
+        CURRENT_AMBIENT.scope(self, f).await
+    }
+}
+
+impl fmt::Debug for Ambient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ambient")
+            .field("len", &self.map.len())
+            .finish()
+    }
+}
+
 /// An object safe message which can be handled by an actor `A`.
 ///
 /// This trait is implemented for all types which implement [`Message`], and is typically used for advanced cases such
@@ -197,16 +1508,38 @@ where
     Self: Send,
     A: Actor,
 {
-    /// Handles the dyn message with the provided actor state, ref, and reply sender.
-    fn handle_dyn(
+    /// Handles the dyn message with the provided actor state, ref, reply sender, extensions, metadata, actor locals,
+    /// and mailbox length.
+    ///
+    /// The reply sender is borrowed rather than owned, so that if the handler panics or is aborted before replying,
+    /// the caller can still be notified of the reason the actor stopped.
+    #[allow(clippy::too_many_arguments)]
+    fn handle_dyn<'a>(
         self: Box<Self>,
-        state: &mut A,
+        state: &'a mut A,
         actor_ref: ActorRef<A>,
-        tx: Option<BoxReplySender>,
-    ) -> BoxFuture<'_, Option<BoxDebug>>;
+        reply: &'a mut Option<BoxReplySender>,
+        extensions: Extensions,
+        meta: MessageMeta<A>,
+        locals: &'a mut ActorLocals,
+        mailbox_len: usize,
+    ) -> BoxFuture<'a, Option<BoxDebug>>;
+
+    /// Returns the type name of the message.
+    fn message_name(&self) -> &'static str;
+
+    /// Returns the type id of the message.
+    fn message_type(&self) -> any::TypeId;
+
+    /// Returns the priority of the message.
+    ///
+    /// See [`Message::priority`].
+    fn priority(&self) -> i64 {
+        0
+    }
 
-    /// Casts the type to a `Box<dyn Any>`.
-    fn as_any(self: Box<Self>) -> Box<dyn any::Any>;
+    /// Casts the type to a `Box<dyn Any + Send>`.
+    fn as_any(self: Box<Self>) -> Box<dyn any::Any + Send>;
 }
 
 impl<A, T> DynMessage<A> for T
@@ -214,28 +1547,149 @@ where
     A: Actor + Message<T>,
     T: Send + 'static,
 {
-    fn handle_dyn(
+    fn handle_dyn<'a>(
         self: Box<Self>,
-        state: &mut A,
+        state: &'a mut A,
         actor_ref: ActorRef<A>,
-        tx: Option<BoxReplySender>,
-    ) -> BoxFuture<'_, Option<BoxDebug>> {
-        async move {
-            let mut reply_sender = tx.map(ReplySender::new);
-            let ctx: Context<'_, A, <A as Message<T>>::Reply> =
-                Context::new(actor_ref, &mut reply_sender);
-            let reply = Message::handle(state, *self, ctx).await;
-            if let Some(tx) = reply_sender.take() {
-                tx.send(reply.into_value());
+        reply: &'a mut Option<BoxReplySender>,
+        mut extensions: Extensions,
+        #[cfg_attr(not(feature = "tracing"), allow(unused_mut))] mut meta: MessageMeta<A>,
+        locals: &'a mut ActorLocals,
+        mailbox_len: usize,
+    ) -> BoxFuture<'a, Option<BoxDebug>> {
+        #[cfg(feature = "tracing")]
+        let span = meta.sender_span.take().map(|sender_span| {
+            handler_span::<T>(
+                actor_ref.name(),
+                A::log_level(),
+                *sender_span,
+                reply.is_some(),
+            )
+        });
+        let ambient = meta.ambient.clone();
+
+        let fut = async move {
+            if let Err(err) = <A as Message<T>>::validate(state, &self) {
+                // The message was rejected before reaching the handler
+                return match reply.take() {
+                    Some(tx) => {
+                        let _ = tx.send(Err(BoxSendError::HandlerError(Box::new(err))));
+                        None
+                    }
+                    None => {
+                        Some(<<A as Message<T>>::Reply as Reply>::into_boxed_validation_err(err))
+                    }
+                };
+            }
+
+            let mut replied_early = None;
+            let ctx: Context<'_, A, <A as Message<T>>::Reply> = Context::new(
+                actor_ref,
+                reply,
+                &mut extensions,
+                &meta,
+                locals,
+                &mut replied_early,
+                mailbox_len,
+            );
+            let value = Message::handle(state, *self, ctx).await;
+            if let Some(tx) = reply.take() {
+                ReplySender::new(tx).send(value.into_value());
+                None
+            } else if !<<A as Message<T>>::Reply as Reply>::CAN_ERROR {
                 None
+            } else if let Some(RepliedEarly(err)) = replied_early {
+                // The handler already replied, so its return value is ignored
+                err.into_inner().unwrap_or_else(PoisonError::into_inner)
             } else {
-                reply.into_boxed_err()
+                value.into_boxed_err()
             }
+        };
+
+        let fut = match ambient {
+            Some(ambient) => ambient.scope(fut).left_future(),
+            None => fut.right_future(),
+        };
+
+        #[cfg(feature = "tracing")]
+        if let Some(span) = span {
+            return tracing::Instrument::instrument(fut, span).boxed();
         }
-        .boxed()
+
+        fut.boxed()
+    }
+
+    fn message_name(&self) -> &'static str {
+        any::type_name::<T>()
+    }
+
+    fn message_type(&self) -> any::TypeId {
+        any::TypeId::of::<T>()
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn any::Any> {
+    fn priority(&self) -> i64 {
+        <A as Message<T>>::priority(self)
+    }
+
+    fn as_any(self: Box<Self>) -> Box<dyn any::Any + Send> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        actor::Actor, error::Nack, mailbox::unbounded::UnboundedMailbox, request::MessageSend,
+        spawn,
+    };
+
+    use super::{Ack, Context, Message};
+
+    #[tokio::test]
+    async fn ack_settles_once() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Process {
+            ack: bool,
+        }
+
+        impl Message<Process> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                msg: Process,
+                mut ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                if msg.ack {
+                    assert!(ctx.ack());
+                    assert!(!ctx.ack());
+                }
+            }
+        }
+
+        let actor_ref = spawn(MyActor);
+
+        let (ack, acked) = Ack::new();
+        actor_ref
+            .tell(Process { ack: true })
+            .extension(ack)
+            .send()
+            .await?;
+        assert_eq!(acked.await, Ok(()));
+
+        let (ack, acked) = Ack::new();
+        actor_ref
+            .tell(Process { ack: false })
+            .extension(ack)
+            .send()
+            .await?;
+        assert_eq!(acked.await, Err(Nack));
+
+        Ok(())
+    }
+}
@@ -37,6 +37,8 @@
 //! ## Types in the Module
 //!
 //! - [`ActorSwarm`]: The core struct for managing the distributed swarm of nodes and coordinating actor registration and messaging.
+//! - [`Cluster`]: A handle for observing nodes joining, leaving, and becoming unreachable.
+//! - [`Compression`]: Configures compression of large message payloads sent between nodes.
 //! - [`SwarmFuture`]: A future that holds the response from the actor swarm.
 //! - [`RemoteActor`]: A trait for identifying remote actors via a unique ID.
 //! - [`RemoteMessage`]: A trait for identifying remote messages via a unique ID.
@@ -69,8 +71,12 @@ use crate::{
 
 #[doc(hidden)]
 pub mod _internal;
+mod cluster;
+mod compression;
 mod swarm;
 
+pub use cluster::*;
+pub use compression::*;
 pub use swarm::*;
 
 pub(crate) static REMOTE_REGISTRY: Lazy<Mutex<HashMap<ActorID, RemoteRegistryActorRef>>> =
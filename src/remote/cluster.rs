@@ -0,0 +1,412 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    pin::Pin,
+    sync::{Mutex, PoisonError},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{stream::BoxStream, Stream, StreamExt};
+use libp2p::PeerId;
+use once_cell::sync::Lazy;
+use tokio::sync::broadcast;
+
+use super::{ActorSwarm, SwarmCommand};
+
+static CLUSTER: Lazy<Cluster> = Lazy::new(Cluster::new);
+
+/// A change in the membership of the cluster, received from [`Cluster::events`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClusterEvent {
+    /// A node connected to this node.
+    NodeJoined(PeerId),
+    /// A node missed [`ClusterConfig::failure_threshold`] heartbeats in a row.
+    ///
+    /// The node is still connected, and may become reachable again, but requests sent to it are unlikely to
+    /// complete until it does.
+    NodeUnreachable(PeerId),
+    /// A node which was unreachable answered a heartbeat again.
+    NodeReachable(PeerId),
+    /// A node's last connection to this node was closed.
+    NodeLeft(PeerId),
+}
+
+/// Configures the failure detector started with [`Cluster::start_failure_detector`].
+///
+/// The failure detector sends a heartbeat to every connected node each [`heartbeat_interval`], and marks a node as
+/// unreachable once it has missed [`failure_threshold`] heartbeats in a row. A node is detected as unreachable
+/// after roughly `heartbeat_interval * failure_threshold`, so lower values detect failures sooner, at the cost of
+/// more traffic and more false positives on slow networks.
+///
+/// [`heartbeat_interval`]: ClusterConfig::heartbeat_interval
+/// [`failure_threshold`]: ClusterConfig::failure_threshold
+#[derive(Clone, Copy, Debug)]
+pub struct ClusterConfig {
+    heartbeat_interval: Duration,
+    failure_threshold: u32,
+}
+
+impl ClusterConfig {
+    /// Creates a new configuration, with a heartbeat interval of 1 second and a failure threshold of 3.
+    pub fn new() -> Self {
+        ClusterConfig::default()
+    }
+
+    /// Sets how often heartbeats are sent to each node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval is zero.
+    pub fn heartbeat_interval(mut self, interval: Duration) -> Self {
+        assert!(
+            !interval.is_zero(),
+            "heartbeat interval must be greater than zero"
+        );
+        self.heartbeat_interval = interval;
+        self
+    }
+
+    /// Sets how many heartbeats in a row a node can miss before it's considered unreachable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the threshold is zero.
+    pub fn failure_threshold(mut self, threshold: u32) -> Self {
+        assert!(threshold > 0, "failure threshold must be greater than zero");
+        self.failure_threshold = threshold;
+        self
+    }
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        ClusterConfig {
+            heartbeat_interval: Duration::from_secs(1),
+            failure_threshold: 3,
+        }
+    }
+}
+
+/// A handle for observing the nodes in the cluster.
+///
+/// Nodes join the cluster when they connect to this node, and leave it when their last connection is closed.
+/// Connections alone can't tell a crashed or partitioned node from a slow one, so the cluster can also run a failure
+/// detector, started with [`start_failure_detector`](Cluster::start_failure_detector), which sends heartbeats to
+/// each node and reports nodes which stop answering as unreachable.
+///
+/// Membership changes are published as [`ClusterEvent`]s, so supervisors can react to them, such as by respawning
+/// actors which lived on a node that left.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use kameo::remote::{ActorSwarm, ClusterConfig, ClusterEvent};
+///
+/// # tokio_test::block_on(async {
+/// let cluster = ActorSwarm::bootstrap()?.cluster();
+/// cluster.start_failure_detector(ClusterConfig::new());
+///
+/// let mut events = cluster.events();
+/// while let Some(event) = events.next().await {
+///     match event {
+///         ClusterEvent::NodeJoined(peer_id) => println!("{peer_id} joined"),
+///         ClusterEvent::NodeUnreachable(peer_id) => println!("{peer_id} is unreachable"),
+///         ClusterEvent::NodeReachable(peer_id) => println!("{peer_id} is reachable again"),
+///         ClusterEvent::NodeLeft(peer_id) => println!("{peer_id} left"),
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct Cluster {
+    events: broadcast::Sender<ClusterEvent>,
+    members: Mutex<HashMap<PeerId, Member>>,
+    config: Mutex<Option<ClusterConfig>>,
+}
+
+#[derive(Debug)]
+struct Member {
+    /// Whether the last heartbeat sent to the node is still waiting for an answer.
+    pending: bool,
+    /// The number of heartbeats in a row the node has missed.
+    missed: u32,
+    reachable: bool,
+}
+
+impl Cluster {
+    fn new() -> Self {
+        Cluster {
+            events: broadcast::channel(256).0,
+            members: Mutex::new(HashMap::new()),
+            config: Mutex::new(None),
+        }
+    }
+
+    /// Returns the cluster of the bootstrapped [`ActorSwarm`], or `None` if the swarm hasn't been bootstrapped.
+    pub fn get() -> Option<&'static Cluster> {
+        ActorSwarm::get().map(ActorSwarm::cluster)
+    }
+
+    pub(crate) fn global() -> &'static Cluster {
+        &CLUSTER
+    }
+
+    /// Returns a stream of membership changes.
+    ///
+    /// The stream only receives changes which happen after it's created, so use [`members`](Cluster::members) to
+    /// find the nodes which are already connected. A subscriber which falls too far behind skips the oldest
+    /// events it missed.
+    pub fn events(&self) -> ClusterEvents {
+        let rx = self.events.subscribe();
+        ClusterEvents(
+            futures::stream::unfold(rx, |mut rx| async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(event) => return Some((event, rx)),
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            })
+            .boxed(),
+        )
+    }
+
+    /// Returns the nodes currently connected to this node, including any which are unreachable.
+    pub fn members(&self) -> Vec<PeerId> {
+        self.lock_members().keys().copied().collect()
+    }
+
+    /// Returns `true` if the node is connected and hasn't been detected as unreachable.
+    pub fn is_reachable(&self, peer_id: &PeerId) -> bool {
+        self.lock_members()
+            .get(peer_id)
+            .is_some_and(|member| member.reachable)
+    }
+
+    /// Starts the failure detector, sending heartbeats to every node in the cluster.
+    ///
+    /// Only the first call has any effect, and later calls return `false`. Every node in the cluster should run a
+    /// version of kameo which answers heartbeats, otherwise they'll be reported as unreachable.
+    pub fn start_failure_detector(&'static self, config: ClusterConfig) -> bool {
+        let Some(swarm) = ActorSwarm::get() else {
+            return false;
+        };
+        {
+            let mut current = self.config.lock().unwrap_or_else(PoisonError::into_inner);
+            if current.is_some() {
+                return false;
+            }
+            *current = Some(config);
+        }
+
+        let swarm_tx = swarm.sender().clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.heartbeat_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                swarm_tx.send(SwarmCommand::Heartbeat);
+            }
+        });
+
+        true
+    }
+
+    /// Records a new connection to a node.
+    pub(crate) fn node_joined(&self, peer_id: PeerId) {
+        let mut members = self.lock_members();
+        if members.contains_key(&peer_id) {
+            return;
+        }
+        members.insert(
+            peer_id,
+            Member {
+                pending: false,
+                missed: 0,
+                reachable: true,
+            },
+        );
+        let _ = self.events.send(ClusterEvent::NodeJoined(peer_id));
+    }
+
+    /// Records the last connection to a node being closed.
+    pub(crate) fn node_left(&self, peer_id: PeerId) {
+        if self.lock_members().remove(&peer_id).is_some() {
+            let _ = self.events.send(ClusterEvent::NodeLeft(peer_id));
+        }
+    }
+
+    /// Starts a round of heartbeats, returning the nodes to send them to.
+    ///
+    /// Nodes which haven't answered the previous round have missed a heartbeat.
+    pub(crate) fn heartbeat_round(&self) -> Vec<PeerId> {
+        let Some(config) = *self.config.lock().unwrap_or_else(PoisonError::into_inner) else {
+            return vec![];
+        };
+
+        let mut members = self.lock_members();
+        for (peer_id, member) in members.iter_mut() {
+            if member.pending {
+                member.missed = member.missed.saturating_add(1);
+                if member.reachable && member.missed >= config.failure_threshold {
+                    member.reachable = false;
+                    let _ = self.events.send(ClusterEvent::NodeUnreachable(*peer_id));
+                }
+            }
+            member.pending = true;
+        }
+        members.keys().copied().collect()
+    }
+
+    /// Records a node answering a heartbeat.
+    pub(crate) fn heartbeat_answered(&self, peer_id: &PeerId) {
+        let mut members = self.lock_members();
+        let Some(member) = members.get_mut(peer_id) else {
+            return;
+        };
+        member.pending = false;
+        member.missed = 0;
+        if !member.reachable {
+            member.reachable = true;
+            let _ = self.events.send(ClusterEvent::NodeReachable(*peer_id));
+        }
+    }
+
+    fn lock_members(&self) -> std::sync::MutexGuard<'_, HashMap<PeerId, Member>> {
+        self.members.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl fmt::Debug for Cluster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cluster")
+            .field("members", &*self.lock_members())
+            .field(
+                "config",
+                &*self.config.lock().unwrap_or_else(PoisonError::into_inner),
+            )
+            .finish()
+    }
+}
+
+/// A stream of [`ClusterEvent`]s, returned by [`Cluster::events`].
+pub struct ClusterEvents(BoxStream<'static, ClusterEvent>);
+
+impl Stream for ClusterEvents {
+    type Item = ClusterEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_next_unpin(cx)
+    }
+}
+
+impl fmt::Debug for ClusterEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClusterEvents").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{FutureExt, StreamExt};
+    use libp2p::PeerId;
+
+    use super::{Cluster, ClusterConfig, ClusterEvent, ClusterEvents};
+
+    fn cluster(failure_threshold: u32) -> Cluster {
+        let cluster = Cluster::new();
+        *cluster.config.lock().unwrap() =
+            Some(ClusterConfig::new().failure_threshold(failure_threshold));
+        cluster
+    }
+
+    fn drain(events: &mut ClusterEvents) -> Vec<ClusterEvent> {
+        let mut drained = Vec::new();
+        while let Some(Some(event)) = events.next().now_or_never() {
+            drained.push(event);
+        }
+        drained
+    }
+
+    #[test]
+    fn cluster_tracks_joined_and_left_nodes() {
+        let cluster = cluster(3);
+        let mut events = cluster.events();
+        let peer_id = PeerId::random();
+
+        cluster.node_joined(peer_id);
+        cluster.node_joined(peer_id);
+        assert_eq!(cluster.members(), [peer_id]);
+        assert!(cluster.is_reachable(&peer_id));
+
+        cluster.node_left(peer_id);
+        cluster.node_left(peer_id);
+        assert!(cluster.members().is_empty());
+        assert!(!cluster.is_reachable(&peer_id));
+        assert_eq!(
+            drain(&mut events),
+            [
+                ClusterEvent::NodeJoined(peer_id),
+                ClusterEvent::NodeLeft(peer_id)
+            ]
+        );
+    }
+
+    #[test]
+    fn cluster_detects_unreachable_nodes() {
+        let cluster = cluster(2);
+        let peer_id = PeerId::random();
+        let other_peer_id = PeerId::random();
+        cluster.node_joined(peer_id);
+        cluster.node_joined(other_peer_id);
+        let mut events = cluster.events();
+
+        // Only the node which stops answering heartbeats is unreachable
+        for _ in 0..3 {
+            let mut round = cluster.heartbeat_round();
+            round.sort();
+            let mut members = vec![peer_id, other_peer_id];
+            members.sort();
+            assert_eq!(round, members);
+            cluster.heartbeat_answered(&other_peer_id);
+        }
+        assert!(!cluster.is_reachable(&peer_id));
+        assert!(cluster.is_reachable(&other_peer_id));
+        assert_eq!(cluster.members().len(), 2);
+
+        // Missing more heartbeats doesn't report the node again
+        cluster.heartbeat_round();
+        assert_eq!(drain(&mut events), [ClusterEvent::NodeUnreachable(peer_id)]);
+
+        cluster.heartbeat_answered(&peer_id);
+        cluster.heartbeat_answered(&peer_id);
+        assert!(cluster.is_reachable(&peer_id));
+        assert_eq!(drain(&mut events), [ClusterEvent::NodeReachable(peer_id)]);
+
+        // Answering resets the missed heartbeats
+        cluster.heartbeat_round();
+        cluster.heartbeat_answered(&peer_id);
+        cluster.heartbeat_round();
+        cluster.heartbeat_round();
+        assert!(cluster.is_reachable(&peer_id));
+    }
+
+    #[test]
+    fn cluster_without_failure_detector_sends_no_heartbeats() {
+        let cluster = Cluster::new();
+        let peer_id = PeerId::random();
+        cluster.node_joined(peer_id);
+
+        for _ in 0..5 {
+            assert!(cluster.heartbeat_round().is_empty());
+        }
+        assert!(cluster.is_reachable(&peer_id));
+
+        // Answers from unknown nodes are ignored
+        cluster.heartbeat_answered(&PeerId::random());
+        assert_eq!(cluster.members(), [peer_id]);
+    }
+}
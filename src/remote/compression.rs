@@ -0,0 +1,316 @@
+use std::{borrow::Cow, fmt, io, sync::Arc};
+
+use once_cell::sync::OnceCell;
+
+static COMPRESSION: OnceCell<Compression> = OnceCell::new();
+
+/// A compression algorithm for remote message payloads, used by [`Compression`].
+///
+/// Kameo doesn't bundle any algorithms, so an implementation usually wraps a compression crate such as `zstd` or
+/// `lz4_flex`. Nodes only compress messages for peers which support an algorithm with the same
+/// [`name`](Compressor::name), so every implementation of an algorithm must produce data the others can decompress.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::io;
+///
+/// use kameo::remote::Compressor;
+///
+/// struct Zstd;
+///
+/// impl Compressor for Zstd {
+///     fn name(&self) -> &'static str {
+///         "zstd"
+///     }
+///
+///     fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+///         zstd::encode_all(data, 3)
+///     }
+///
+///     fn decompress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+///         zstd::decode_all(data)
+///     }
+/// }
+/// ```
+pub trait Compressor: Send + Sync + 'static {
+    /// The name the algorithm is negotiated with peers by, such as `"zstd"`.
+    fn name(&self) -> &'static str;
+
+    /// Compresses a message payload.
+    fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>>;
+
+    /// Decompresses a message payload received from a peer.
+    ///
+    /// Payloads come from other nodes, so implementations should limit how large the decompressed data can grow.
+    fn decompress(&self, data: &[u8]) -> io::Result<Vec<u8>>;
+}
+
+/// Configures compression of remote message payloads, enabled with
+/// [`ActorSwarm::enable_compression`](super::ActorSwarm::enable_compression).
+///
+/// Payloads of at least [`threshold`](Compression::threshold) bytes are compressed before being sent to another
+/// node, and decompressed by the receiving node before the message is handled. Smaller payloads are sent as is,
+/// since compressing them rarely saves enough to be worth the time, and so are payloads which don't get smaller.
+///
+/// # Negotiation
+///
+/// Both ends of a connection must agree on an algorithm before any message is compressed. When a node connects to
+/// another, each node tells the other which algorithms it supports, and then compresses the messages it sends with
+/// the first of its own algorithms which the other node also supports. Until then, or if there's no such algorithm,
+/// messages are sent uncompressed.
+///
+/// Nodes which haven't enabled compression advertise no algorithms, and nodes running an older version of kameo
+/// don't answer at all, so in both cases messages to and from them are never compressed. Each message is marked
+/// with the algorithm it was compressed with, so a node can always decompress what it receives.
+#[derive(Clone)]
+pub struct Compression {
+    algorithms: Vec<Arc<dyn Compressor>>,
+    threshold: usize,
+}
+
+impl Compression {
+    /// Creates a new configuration with a preferred algorithm, compressing payloads of at least 8 KiB.
+    pub fn new(algorithm: impl Compressor) -> Self {
+        Compression {
+            algorithms: vec![Arc::new(algorithm)],
+            threshold: 8 * 1024,
+        }
+    }
+
+    /// Adds an algorithm to use with peers which don't support any of the algorithms added before it.
+    pub fn fallback(mut self, algorithm: impl Compressor) -> Self {
+        self.algorithms.push(Arc::new(algorithm));
+        self
+    }
+
+    /// Sets the size in bytes a payload must reach to be compressed.
+    pub fn threshold(mut self, bytes: usize) -> Self {
+        self.threshold = bytes;
+        self
+    }
+
+    /// Enables compression for the process, returning `false` if it was already enabled.
+    pub(crate) fn enable(self) -> bool {
+        COMPRESSION.set(self).is_ok()
+    }
+
+    /// Returns the enabled compression configuration, if any.
+    pub(crate) fn get() -> Option<&'static Compression> {
+        COMPRESSION.get()
+    }
+
+    /// Returns the names of the supported algorithms, in order of preference.
+    pub(crate) fn advertised() -> Vec<Cow<'static, str>> {
+        Compression::get()
+            .map(|compression| {
+                compression
+                    .algorithms
+                    .iter()
+                    .map(|algorithm| Cow::Borrowed(algorithm.name()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Picks the algorithm to compress messages to a peer with, given the algorithms the peer supports.
+    pub(crate) fn negotiate(supported: &[Cow<'static, str>]) -> Option<&'static str> {
+        Compression::get()?.negotiate_with(supported)
+    }
+
+    /// Compresses a payload with the negotiated algorithm, if it's large enough and gets smaller.
+    ///
+    /// Returns the payload, and the name of the algorithm if it was compressed.
+    pub(crate) fn compress(
+        algorithm: Option<&'static str>,
+        payload: Vec<u8>,
+    ) -> (Vec<u8>, Option<Cow<'static, str>>) {
+        match (Compression::get(), algorithm) {
+            (Some(compression), Some(algorithm)) => compression.compress_with(algorithm, payload),
+            _ => (payload, None),
+        }
+    }
+
+    /// Decompresses a received payload, if it was compressed.
+    pub(crate) fn decompress(algorithm: Option<&str>, payload: Vec<u8>) -> Result<Vec<u8>, String> {
+        let Some(name) = algorithm else {
+            return Ok(payload);
+        };
+        match Compression::get() {
+            Some(compression) => compression.decompress_with(name, payload),
+            None => Err(format!("unsupported compression algorithm {name:?}")),
+        }
+    }
+
+    fn negotiate_with(&self, supported: &[Cow<'static, str>]) -> Option<&'static str> {
+        self.algorithms
+            .iter()
+            .map(|algorithm| algorithm.name())
+            .find(|name| supported.iter().any(|supported| supported == name))
+    }
+
+    fn compress_with(&self, name: &str, payload: Vec<u8>) -> (Vec<u8>, Option<Cow<'static, str>>) {
+        let Some(algorithm) = self.find(name) else {
+            return (payload, None);
+        };
+        if payload.len() < self.threshold {
+            return (payload, None);
+        }
+        match algorithm.compress(&payload) {
+            Ok(compressed) if compressed.len() < payload.len() => {
+                (compressed, Some(Cow::Borrowed(algorithm.name())))
+            }
+            _ => (payload, None),
+        }
+    }
+
+    fn decompress_with(&self, name: &str, payload: Vec<u8>) -> Result<Vec<u8>, String> {
+        let algorithm = self
+            .find(name)
+            .ok_or_else(|| format!("unsupported compression algorithm {name:?}"))?;
+        algorithm
+            .decompress(&payload)
+            .map_err(|err| format!("failed to decompress payload with {name}: {err}"))
+    }
+
+    fn find(&self, name: &str) -> Option<&dyn Compressor> {
+        self.algorithms
+            .iter()
+            .find(|algorithm| algorithm.name() == name)
+            .map(AsRef::as_ref)
+    }
+}
+
+impl fmt::Debug for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Compression")
+            .field(
+                "algorithms",
+                &self
+                    .algorithms
+                    .iter()
+                    .map(|algorithm| algorithm.name())
+                    .collect::<Vec<_>>(),
+            )
+            .field("threshold", &self.threshold)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{borrow::Cow, io, iter};
+
+    use super::{Compression, Compressor};
+
+    /// Run-length encodes payloads as pairs of a count and a byte.
+    pub(crate) struct Rle;
+
+    impl Compressor for Rle {
+        fn name(&self) -> &'static str {
+            "rle"
+        }
+
+        fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+            let mut compressed = Vec::new();
+            for run in data.chunk_by(|a, b| a == b) {
+                for run in run.chunks(u8::MAX as usize) {
+                    compressed.extend([run.len() as u8, run[0]]);
+                }
+            }
+            Ok(compressed)
+        }
+
+        fn decompress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+            if !data.len().is_multiple_of(2) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "truncated run"));
+            }
+            Ok(data
+                .chunks(2)
+                .flat_map(|run| iter::repeat_n(run[1], run[0] as usize))
+                .collect())
+        }
+    }
+
+    /// Leaves payloads as they are.
+    struct Identity;
+
+    impl Compressor for Identity {
+        fn name(&self) -> &'static str {
+            "identity"
+        }
+
+        fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+            Ok(data.to_vec())
+        }
+
+        fn decompress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+            Ok(data.to_vec())
+        }
+    }
+
+    #[test]
+    fn compression_negotiates_first_shared_algorithm() {
+        let compression = Compression::new(Rle).fallback(Identity);
+        let supported = |names: &[&'static str]| -> Vec<Cow<'static, str>> {
+            names.iter().copied().map(Cow::Borrowed).collect()
+        };
+
+        // Our own order of preference wins over the peer's
+        assert_eq!(
+            compression.negotiate_with(&supported(&["identity", "rle"])),
+            Some("rle")
+        );
+        assert_eq!(
+            compression.negotiate_with(&supported(&["zstd", "identity"])),
+            Some("identity")
+        );
+        assert_eq!(compression.negotiate_with(&supported(&["zstd"])), None);
+        assert_eq!(compression.negotiate_with(&[]), None);
+    }
+
+    #[test]
+    fn compression_only_compresses_payloads_over_threshold() {
+        let compression = Compression::new(Rle).threshold(16);
+
+        let (payload, algorithm) = compression.compress_with("rle", vec![0; 15]);
+        assert_eq!((payload, algorithm), (vec![0; 15], None));
+
+        let (payload, algorithm) = compression.compress_with("rle", vec![0; 16]);
+        assert_eq!(payload, vec![16, 0]);
+        assert_eq!(algorithm.as_deref(), Some("rle"));
+        assert_eq!(compression.decompress_with("rle", payload), Ok(vec![0; 16]));
+    }
+
+    #[test]
+    fn compression_sends_payloads_which_dont_shrink_as_is() {
+        let compression = Compression::new(Rle).fallback(Identity).threshold(0);
+        let distinct: Vec<u8> = (0..64).collect();
+
+        // Run-length encoding doubles the size of bytes which don't repeat
+        let (payload, algorithm) = compression.compress_with("rle", distinct.clone());
+        assert_eq!((payload, algorithm), (distinct.clone(), None));
+
+        // Payloads which stay the same size aren't worth marking as compressed either
+        let (payload, algorithm) = compression.compress_with("identity", distinct.clone());
+        assert_eq!((payload, algorithm), (distinct.clone(), None));
+
+        // Algorithms which weren't configured are never used
+        let (payload, algorithm) = compression.compress_with("zstd", distinct.clone());
+        assert_eq!((payload, algorithm), (distinct, None));
+    }
+
+    #[test]
+    fn compression_reports_decompression_errors() {
+        let compression = Compression::new(Rle);
+
+        assert_eq!(
+            compression.decompress_with("rle", vec![2, 7, 1]),
+            Err("failed to decompress payload with rle: truncated run".to_string())
+        );
+        assert_eq!(
+            compression.decompress_with("zstd", vec![2, 7]),
+            Err("unsupported compression algorithm \"zstd\"".to_string())
+        );
+    }
+}
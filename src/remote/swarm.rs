@@ -1,5 +1,5 @@
 use core::task;
-use std::{borrow::Cow, collections::HashMap, io, pin, time::Duration};
+use std::{borrow::Cow, collections::HashMap, io, num::NonZeroU32, pin, time::Duration};
 
 use futures::{ready, stream::FuturesUnordered, Future, FutureExt};
 use libp2p::{
@@ -29,7 +29,7 @@ use crate::{
     remote, Actor,
 };
 
-use super::{RemoteActor, RemoteRegistryActorRef, REMOTE_REGISTRY};
+use super::{Cluster, Compression, RemoteActor, RemoteRegistryActorRef, REMOTE_REGISTRY};
 
 static ACTOR_SWARM: OnceCell<ActorSwarm> = OnceCell::new();
 
@@ -218,6 +218,28 @@ impl ActorSwarm {
             .send_with_reply(|reply| SwarmCommand::ListenOn { addr, reply })
     }
 
+    pub(crate) fn sender(&self) -> &SwarmSender {
+        &self.swarm_tx
+    }
+
+    /// Returns the cluster of nodes connected to this node, for observing membership changes.
+    ///
+    /// See [`Cluster`] for more information.
+    pub fn cluster(&self) -> &'static Cluster {
+        Cluster::global()
+    }
+
+    /// Enables compression of large message payloads sent to other nodes.
+    ///
+    /// Compression is negotiated with each node when it connects, so this should be called before listening or
+    /// dialing, and connections established before it's called are never compressed. Only the first call has any
+    /// effect, and later calls return `false`.
+    ///
+    /// See [`Compression`] for more information.
+    pub fn enable_compression(&self, compression: Compression) -> bool {
+        compression.enable()
+    }
+
     /// Retrieves a reference to the current `ActorSwarm` if it has been bootstrapped.
     ///
     /// This function is useful for getting access to the swarm after initialization without
@@ -475,6 +497,10 @@ pub struct ActorSwarmHandler {
         HashMap<kad::QueryId, oneshot::Sender<Result<kad::PeerRecord, kad::GetRecordError>>>,
     put_queries: HashMap<kad::QueryId, oneshot::Sender<kad::PutRecordResult>>,
     requests: HashMap<OutboundRequestId, oneshot::Sender<SwarmResponse>>,
+    heartbeats: HashMap<OutboundRequestId, PeerId>,
+    hellos: HashMap<OutboundRequestId, PeerId>,
+    /// The compression algorithm negotiated with each peer.
+    compression: HashMap<PeerId, &'static str>,
 }
 
 impl ActorSwarmHandler {
@@ -485,6 +511,9 @@ impl ActorSwarmHandler {
             get_queries: HashMap::new(),
             put_queries: HashMap::new(),
             requests: HashMap::new(),
+            heartbeats: HashMap::new(),
+            hellos: HashMap::new(),
+            compression: HashMap::new(),
         }
     }
 
@@ -494,6 +523,9 @@ impl ActorSwarmHandler {
                 Some(cmd) = self.cmd_rx.recv() => self.handle_command(swarm, cmd),
                 Some(event) = swarm.next() => {
                     match event {
+                        SwarmEvent::ConnectionEstablished { peer_id, connection_id, endpoint, num_established, .. } => {
+                            self.handle_event(swarm, ActorSwarmEvent::ConnectionEstablished { peer_id, connection_id, endpoint, num_established });
+                        }
                         SwarmEvent::ConnectionClosed { peer_id, connection_id, endpoint, num_established, cause } => {
                             self.handle_event(swarm, ActorSwarmEvent::ConnectionClosed { peer_id, connection_id, endpoint, num_established, cause });
                         }
@@ -594,12 +626,15 @@ impl ActorSwarmHandler {
                         let _ = reply.send(SwarmResponse::Ask(result));
                     });
                 } else {
+                    let (payload, compression) =
+                        Compression::compress(self.compression.get(&peer_id).copied(), payload);
                     let req_id = swarm.behaviour_mut().ask(
                         &peer_id,
                         actor_id,
                         actor_remote_id,
                         message_remote_id,
                         payload,
+                        compression,
                         mailbox_timeout,
                         reply_timeout,
                         immediate,
@@ -631,12 +666,15 @@ impl ActorSwarmHandler {
                         let _ = reply.send(SwarmResponse::Tell(result));
                     });
                 } else {
+                    let (payload, compression) =
+                        Compression::compress(self.compression.get(&peer_id).copied(), payload);
                     let req_id = swarm.behaviour_mut().tell(
                         &peer_id,
                         actor_id,
                         actor_remote_id,
                         message_remote_id,
                         payload,
+                        compression,
                         mailbox_timeout,
                         immediate,
                     );
@@ -736,19 +774,46 @@ impl ActorSwarmHandler {
                     .behaviour_mut()
                     .send_signal_link_died_response(channel, result);
             }
+            SwarmCommand::Heartbeat => {
+                for peer_id in Cluster::global().heartbeat_round() {
+                    let req_id = swarm.behaviour_mut().heartbeat(&peer_id);
+                    self.heartbeats.insert(req_id, peer_id);
+                }
+            }
         }
     }
 
     /// Handles a swarm event.
     ///
-    /// ConnectionClosed, Mdns, Kademlia, and RequestResponse events should be handled always.
+    /// ConnectionEstablished, ConnectionClosed, Mdns, Kademlia, and RequestResponse events should be handled always.
     pub fn handle_event<B: SwarmBehaviour>(
         &mut self,
         swarm: &mut Swarm<B>,
         event: ActorSwarmEvent,
     ) {
         match event {
-            ActorSwarmEvent::ConnectionClosed { peer_id, .. } => {
+            ActorSwarmEvent::ConnectionEstablished {
+                peer_id,
+                num_established,
+                ..
+            } if num_established.get() == 1 => {
+                Cluster::global().node_joined(peer_id);
+                if Compression::get().is_some() {
+                    let req_id = swarm
+                        .behaviour_mut()
+                        .hello(&peer_id, Compression::advertised());
+                    self.hellos.insert(req_id, peer_id);
+                }
+            }
+            ActorSwarmEvent::ConnectionClosed {
+                peer_id,
+                num_established,
+                ..
+            } => {
+                if num_established == 0 {
+                    Cluster::global().node_left(peer_id);
+                    self.compression.remove(&peer_id);
+                }
                 tokio::spawn(async move {
                     let mut futures = FuturesUnordered::new();
                     for RemoteRegistryActorRef {
@@ -818,6 +883,7 @@ impl ActorSwarmHandler {
             },
             ActorSwarmEvent::Behaviour(ActorSwarmBehaviourEvent::RequestResponse(
                 request_response::Event::Message {
+                    peer,
                     message:
                         request_response::Message::Request {
                             request, channel, ..
@@ -830,12 +896,22 @@ impl ActorSwarmHandler {
                     actor_remote_id,
                     message_remote_id,
                     payload,
+                    compression,
                     mailbox_timeout,
                     reply_timeout,
                     immediate,
                 } => {
                     let tx = self.cmd_tx.clone();
                     tokio::spawn(async move {
+                        let payload = match Compression::decompress(compression.as_deref(), payload)
+                        {
+                            Ok(payload) => payload,
+                            Err(err) => {
+                                let result = Err(RemoteSendError::DeserializeMessage(err));
+                                tx.send(SwarmCommand::SendAskResponse { result, channel });
+                                return;
+                            }
+                        };
                         let result = remote::ask(
                             actor_id,
                             actor_remote_id,
@@ -854,11 +930,21 @@ impl ActorSwarmHandler {
                     actor_remote_id,
                     message_remote_id,
                     payload,
+                    compression,
                     mailbox_timeout,
                     immediate,
                 } => {
                     let tx = self.cmd_tx.clone();
                     tokio::spawn(async move {
+                        let payload = match Compression::decompress(compression.as_deref(), payload)
+                        {
+                            Ok(payload) => payload,
+                            Err(err) => {
+                                let result = Err(RemoteSendError::DeserializeMessage(err));
+                                tx.send(SwarmCommand::SendTellResponse { result, channel });
+                                return;
+                            }
+                        };
                         let result = remote::tell(
                             actor_id,
                             actor_remote_id,
@@ -918,6 +1004,17 @@ impl ActorSwarmHandler {
                         tx.send(SwarmCommand::SendSignalLinkDiedResponse { result, channel });
                     });
                 }
+                SwarmRequest::Heartbeat => {
+                    let _ = swarm.behaviour_mut().send_heartbeat_response(channel);
+                }
+                SwarmRequest::Hello { compression } => {
+                    if let Some(algorithm) = Compression::negotiate(&compression) {
+                        self.compression.insert(peer, algorithm);
+                    }
+                    let _ = swarm
+                        .behaviour_mut()
+                        .send_hello_response(channel, Compression::advertised());
+                }
             },
             ActorSwarmEvent::Behaviour(ActorSwarmBehaviourEvent::RequestResponse(
                 request_response::Event::Message {
@@ -929,7 +1026,15 @@ impl ActorSwarmHandler {
                     ..
                 },
             )) => {
-                if let Some(tx) = self.requests.remove(&request_id) {
+                if let Some(peer_id) = self.heartbeats.remove(&request_id) {
+                    Cluster::global().heartbeat_answered(&peer_id);
+                } else if let Some(peer_id) = self.hellos.remove(&request_id) {
+                    if let SwarmResponse::Hello { compression } = response {
+                        if let Some(algorithm) = Compression::negotiate(&compression) {
+                            self.compression.insert(peer_id, algorithm);
+                        }
+                    }
+                } else if let Some(tx) = self.requests.remove(&request_id) {
                     let _ = tx.send(response);
                 }
             }
@@ -938,6 +1043,10 @@ impl ActorSwarmHandler {
                     request_id, error, ..
                 },
             )) => {
+                // A failed heartbeat is counted as missed in the next round
+                self.heartbeats.remove(&request_id);
+                // Peers which can't answer a hello, such as those running an older version, are never compressed
+                self.hellos.remove(&request_id);
                 if let Some(tx) = self.requests.remove(&request_id) {
                     let err = match error {
                         OutboundFailure::DialFailure => RemoteSendError::DialFailure,
@@ -1140,6 +1249,8 @@ pub enum SwarmCommand {
         /// Response channel.
         channel: ResponseChannel<SwarmResponse>,
     },
+    /// Sends a heartbeat to every node in the cluster.
+    Heartbeat,
 }
 
 /// An actor registration record.
@@ -1194,6 +1305,9 @@ pub enum SwarmRequest {
         message_remote_id: Cow<'static, str>,
         /// The payload data to be sent with the request.
         payload: Vec<u8>,
+        /// The algorithm the payload was compressed with, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        compression: Option<Cow<'static, str>>,
         /// Optional timeout duration for the mailbox to receive the request.
         mailbox_timeout: Option<Duration>,
         /// Optional timeout duration to wait for a reply to the request.
@@ -1213,6 +1327,9 @@ pub enum SwarmRequest {
         message_remote_id: Cow<'static, str>,
         /// The payload data to be sent with the message.
         payload: Vec<u8>,
+        /// The algorithm the payload was compressed with, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        compression: Option<Cow<'static, str>>,
         /// Optional timeout duration for the mailbox to receive the message.
         mailbox_timeout: Option<Duration>,
         /// Indicates whether the message should be sent immediately.
@@ -1249,6 +1366,13 @@ pub enum SwarmRequest {
         /// The reason the actor died.
         stop_reason: ActorStopReason,
     },
+    /// A heartbeat from the cluster's failure detector.
+    Heartbeat,
+    /// Advertises the compression algorithms the node supports, sent when a node connects.
+    Hello {
+        /// The supported compression algorithms, in order of preference.
+        compression: Vec<Cow<'static, str>>,
+    },
 }
 
 /// Represents different types of responses that can be sent within the swarm.
@@ -1273,6 +1397,15 @@ pub enum SwarmResponse {
     /// Represents the response to a link died signal.
     SignalLinkDied(Result<(), RemoteSendError<Infallible>>),
 
+    /// Represents the response to a heartbeat.
+    Heartbeat,
+
+    /// Represents the response to a hello, advertising the compression algorithms the node supports.
+    Hello {
+        /// The supported compression algorithms, in order of preference.
+        compression: Vec<Cow<'static, str>>,
+    },
+
     /// Represents a failure that occurred while attempting to send an outbound request.
     ///
     /// Contains the error that caused the outbound request to fail.
@@ -1292,6 +1425,7 @@ pub trait SwarmBehaviour: NetworkBehaviour {
         actor_remote_id: Cow<'static, str>,
         message_remote_id: Cow<'static, str>,
         payload: Vec<u8>,
+        compression: Option<Cow<'static, str>>,
         mailbox_timeout: Option<Duration>,
         reply_timeout: Option<Duration>,
         immediate: bool,
@@ -1308,6 +1442,7 @@ pub trait SwarmBehaviour: NetworkBehaviour {
         actor_remote_id: Cow<'static, str>,
         message_remote_id: Cow<'static, str>,
         payload: Vec<u8>,
+        compression: Option<Cow<'static, str>>,
         mailbox_timeout: Option<Duration>,
         immediate: bool,
     ) -> OutboundRequestId;
@@ -1338,6 +1473,12 @@ pub trait SwarmBehaviour: NetworkBehaviour {
         stop_reason: ActorStopReason,
     ) -> OutboundRequestId;
 
+    /// Sends a heartbeat to a peer, for the cluster's failure detector.
+    fn heartbeat(&mut self, peer: &PeerId) -> OutboundRequestId;
+
+    /// Advertises the compression algorithms this node supports to a peer which just connected.
+    fn hello(&mut self, peer: &PeerId, compression: Vec<Cow<'static, str>>) -> OutboundRequestId;
+
     /// Sends a response to a previously received `ask` request.
     ///
     /// This method handles the result of processing an `ask` request and sends back the appropriate response.
@@ -1383,6 +1524,19 @@ pub trait SwarmBehaviour: NetworkBehaviour {
         result: Result<(), RemoteSendError<Infallible>>,
     ) -> Result<(), SwarmResponse>;
 
+    /// Answers a previously received heartbeat.
+    fn send_heartbeat_response(
+        &mut self,
+        channel: ResponseChannel<SwarmResponse>,
+    ) -> Result<(), SwarmResponse>;
+
+    /// Answers a previously received hello with the compression algorithms this node supports.
+    fn send_hello_response(
+        &mut self,
+        channel: ResponseChannel<SwarmResponse>,
+        compression: Vec<Cow<'static, str>>,
+    ) -> Result<(), SwarmResponse>;
+
     /// Adds a network address for a peer to the Kademlia routing table.
     ///
     /// This method updates the routing information for a peer by adding a new address.
@@ -1442,6 +1596,17 @@ pub use behaviour::*;
 /// An actor swarm event.
 #[derive(Debug)]
 pub enum ActorSwarmEvent {
+    /// A connection to the given peer has been opened.
+    ConnectionEstablished {
+        /// Identity of the peer that we have connected to.
+        peer_id: PeerId,
+        /// Identifier of the connection.
+        connection_id: ConnectionId,
+        /// Endpoint of the connection that has been opened.
+        endpoint: ConnectedPoint,
+        /// Number of established connections to this peer, including the one that has just been opened.
+        num_established: NonZeroU32,
+    },
     /// A connection with the given peer has been closed, possibly as a result of an error.
     ConnectionClosed {
         /// Identity of the peer that we have connected to.
@@ -1487,6 +1652,7 @@ impl SwarmBehaviour for ActorSwarmBehaviour {
         actor_remote_id: Cow<'static, str>,
         message_remote_id: Cow<'static, str>,
         payload: Vec<u8>,
+        compression: Option<Cow<'static, str>>,
         mailbox_timeout: Option<Duration>,
         reply_timeout: Option<Duration>,
         immediate: bool,
@@ -1498,6 +1664,7 @@ impl SwarmBehaviour for ActorSwarmBehaviour {
                 actor_remote_id,
                 message_remote_id,
                 payload,
+                compression,
                 mailbox_timeout,
                 reply_timeout,
                 immediate,
@@ -1512,6 +1679,7 @@ impl SwarmBehaviour for ActorSwarmBehaviour {
         actor_remote_id: Cow<'static, str>,
         message_remote_id: Cow<'static, str>,
         payload: Vec<u8>,
+        compression: Option<Cow<'static, str>>,
         mailbox_timeout: Option<Duration>,
         immediate: bool,
     ) -> OutboundRequestId {
@@ -1522,6 +1690,7 @@ impl SwarmBehaviour for ActorSwarmBehaviour {
                 actor_remote_id,
                 message_remote_id,
                 payload,
+                compression,
                 mailbox_timeout,
                 immediate,
             },
@@ -1580,6 +1749,16 @@ impl SwarmBehaviour for ActorSwarmBehaviour {
         )
     }
 
+    fn heartbeat(&mut self, peer: &PeerId) -> OutboundRequestId {
+        self.request_response
+            .send_request(peer, SwarmRequest::Heartbeat)
+    }
+
+    fn hello(&mut self, peer: &PeerId, compression: Vec<Cow<'static, str>>) -> OutboundRequestId {
+        self.request_response
+            .send_request(peer, SwarmRequest::Hello { compression })
+    }
+
     fn send_ask_response(
         &mut self,
         channel: ResponseChannel<SwarmResponse>,
@@ -1625,6 +1804,23 @@ impl SwarmBehaviour for ActorSwarmBehaviour {
             .send_response(channel, SwarmResponse::SignalLinkDied(result))
     }
 
+    fn send_heartbeat_response(
+        &mut self,
+        channel: ResponseChannel<SwarmResponse>,
+    ) -> Result<(), SwarmResponse> {
+        self.request_response
+            .send_response(channel, SwarmResponse::Heartbeat)
+    }
+
+    fn send_hello_response(
+        &mut self,
+        channel: ResponseChannel<SwarmResponse>,
+        compression: Vec<Cow<'static, str>>,
+    ) -> Result<(), SwarmResponse> {
+        self.request_response
+            .send_response(channel, SwarmResponse::Hello { compression })
+    }
+
     fn kademlia_add_address(&mut self, peer: &PeerId, address: Multiaddr) -> kad::RoutingUpdate {
         self.kademlia.add_address(peer, address)
     }
@@ -1671,3 +1867,91 @@ impl<T> Future for SwarmFuture<T> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, marker::PhantomData};
+
+    use libp2p::{
+        request_response::{self, Codec},
+        PeerId, StreamProtocol,
+    };
+
+    use crate::{
+        actor::ActorID,
+        remote::{compression::tests::Rle, Compression},
+    };
+
+    use super::{SwarmRequest, SwarmResponse};
+
+    /// Returns the codec requests are sent to other nodes with.
+    fn codec<C>(_behaviour: PhantomData<request_response::Behaviour<C>>) -> C
+    where
+        C: Codec + Clone + Default + Send + 'static,
+    {
+        C::default()
+    }
+
+    fn tell(payload: Vec<u8>, compression: Option<Cow<'static, str>>) -> SwarmRequest {
+        SwarmRequest::Tell {
+            actor_id: ActorID::new_with_peer_id(1, PeerId::random()),
+            actor_remote_id: Cow::Borrowed("MyActor"),
+            message_remote_id: Cow::Borrowed("MyMessage"),
+            payload,
+            compression,
+            mailbox_timeout: None,
+            immediate: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn compressed_requests_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let mut codec =
+            codec(PhantomData::<request_response::cbor::Behaviour<SwarmRequest, SwarmResponse>>);
+        let protocol = StreamProtocol::new("/kameo/1");
+
+        assert!(Compression::new(Rle).threshold(16).enable());
+        let algorithm = Compression::negotiate(&Compression::advertised());
+        assert_eq!(algorithm, Some("rle"));
+
+        // The payload is compressed before being sent, and decompressed by the receiving node
+        let payload = vec![7; 1024];
+        let (compressed, compression) = Compression::compress(algorithm, payload.clone());
+        assert!(compressed.len() < payload.len());
+        let mut bytes = Vec::new();
+        codec
+            .write_request(&protocol, &mut bytes, tell(compressed, compression))
+            .await?;
+        let SwarmRequest::Tell {
+            payload: received,
+            compression,
+            ..
+        } = codec.read_request(&protocol, &mut bytes.as_slice()).await?
+        else {
+            panic!("expected a tell request");
+        };
+        assert_eq!(compression.as_deref(), Some("rle"));
+        assert_eq!(
+            Compression::decompress(compression.as_deref(), received)?,
+            payload
+        );
+
+        // Uncompressed requests leave out the algorithm, so older nodes can still read them
+        let mut bytes = Vec::new();
+        codec
+            .write_request(&protocol, &mut bytes, tell(vec![7; 8], None))
+            .await?;
+        let SwarmRequest::Tell {
+            payload: received,
+            compression,
+            ..
+        } = codec.read_request(&protocol, &mut bytes.as_slice()).await?
+        else {
+            panic!("expected a tell request");
+        };
+        assert_eq!(compression, None);
+        assert_eq!(Compression::decompress(None, received)?, vec![7; 8]);
+
+        Ok(())
+    }
+}
@@ -14,14 +14,16 @@
 //! - The `DelegatedReply` type signifies that the actual reply will be managed by another part of the system,
 //!   supporting asynchronous and decoupled communication workflows.
 //! - Importantly, when messages are sent asynchronously with [`tell`](crate::actor::ActorRef::tell) and an error is returned by the actor
-//!   without a direct means for the caller to handle it (due to the absence of a reply expectation), the error is treated
-//!   as a panic within the actor. This behavior will trigger the actor's [`on_panic`](crate::actor::Actor::on_panic) hook, which may result in the actor
-//!   being restarted or stopped based on the [Actor](crate::Actor) implementation (which stops the actor by default).
+//!   without a direct means for the caller to handle it (due to the absence of a reply expectation), the error is passed
+//!   to the actor's [`on_async_error`](crate::actor::Actor::on_async_error) hook. By default this treats the error as a panic,
+//!   triggering the actor's [`on_panic`](crate::actor::Actor::on_panic) hook, which may result in the actor
+//!   being restarted or stopped based on the [Actor] implementation (which stops the actor by default).
 //!
 //! The `Reply` trait, by encompassing a broad range of types and defining specific behaviors for error handling,
 //! ensures that actors can manage their communication responsibilities efficiently and effectively.
 
 use std::{
+    any,
     borrow::Cow,
     collections::{HashMap, HashSet},
     fmt,
@@ -41,18 +43,340 @@ use std::{
     thread::Thread,
 };
 
-use futures::Future;
-use tokio::sync::oneshot;
+use futures::{
+    future::{self, BoxFuture},
+    Future, FutureExt,
+};
+use tokio::sync::{mpsc, oneshot};
 
 use crate::{
+    actor::ActorRef,
     error::{BoxSendError, SendError},
-    message::{BoxDebug, BoxReply},
+    message::{BoxDebug, Message},
+    request::{MessageSend, TryMessageSendSync},
+    Actor,
 };
 
+/// A type erased reply, sent through a [`BoxReplySender`].
+pub type BoxReplyResult = Result<BoxReply, BoxSendError>;
+
+/// A type erased reply value, which is downcast back to the reply type by the requester.
+///
+/// Small primitive replies, such as integers, floats, `bool` and `char`, are stored inline, so replying with them
+/// doesn't allocate. Other replies are boxed.
+pub struct BoxReply(BoxReplyKind);
+
+macro_rules! inline_replies {
+    ($($variant:ident($ty:ty)),* $(,)?) => {
+        enum BoxReplyKind {
+            Boxed(Box<dyn any::Any + Send>),
+            $( $variant($ty), )*
+        }
+
+        impl BoxReply {
+            /// Type erases a reply value, storing it inline if it's a small primitive.
+            pub fn new<T: Send + 'static>(value: T) -> Self {
+                let mut value = Some(value);
+                let any: &mut dyn any::Any = &mut value;
+                $(
+                    if let Some(value) = any.downcast_mut::<Option<$ty>>() {
+                        return BoxReply(BoxReplyKind::$variant(value.take().unwrap()));
+                    }
+                )*
+                BoxReply(BoxReplyKind::Boxed(Box::new(value.unwrap())))
+            }
+
+            /// Attempts to downcast the reply to a concrete type, returning it back if it's a different type.
+            pub fn downcast<T: 'static>(self) -> Result<T, Self> {
+                match self.0 {
+                    BoxReplyKind::Boxed(value) => value
+                        .downcast()
+                        .map(|value| *value)
+                        .map_err(|value| BoxReply(BoxReplyKind::Boxed(value))),
+                    $(
+                        BoxReplyKind::$variant(value) => {
+                            let mut value = Some(value);
+                            let any: &mut dyn any::Any = &mut value;
+                            match any.downcast_mut::<Option<T>>() {
+                                Some(value) => Ok(value.take().unwrap()),
+                                None => Err(BoxReply(BoxReplyKind::$variant(value.unwrap()))),
+                            }
+                        }
+                    )*
+                }
+            }
+        }
+    };
+}
+
+inline_replies! {
+    Bool(bool),
+    Char(char),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    Usize(usize),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Isize(isize),
+    F32(f32),
+    F64(f64),
+}
+
+impl fmt::Debug for BoxReply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxReply").finish_non_exhaustive()
+    }
+}
+
 /// A boxed reply sender which will be downcasted to the correct type when receiving a reply.
 ///
 /// This is reserved for advanced use cases, and misuse of this can result in panics.
-pub type BoxReplySender = oneshot::Sender<Result<BoxReply, BoxSendError>>;
+///
+/// Since an actor's mailbox carries messages of many types, replies are type erased as a [`BoxReply`] before being
+/// sent back to the requester. Zero-sized replies such as `()` and small primitives such as integers are not
+/// allocated, while other replies incur a single allocation per message.
+///
+/// Replies are sent through a `oneshot` channel by default, which is what [`ask`](crate::actor::ActorRef::ask)
+/// requests wait on. Other transports can be supplied with a [`ReplyTransport`], which is boxed, costing one more
+/// allocation per message.
+pub struct BoxReplySender(ReplySenderKind);
+
+enum ReplySenderKind {
+    Oneshot(oneshot::Sender<BoxReplyResult>),
+    Transport(Box<dyn ReplyTransport<BoxReplyResult>>),
+}
+
+impl BoxReplySender {
+    /// Creates a reply sender which sends the reply through a custom transport.
+    pub fn new(transport: impl ReplyTransport<BoxReplyResult>) -> Self {
+        BoxReplySender(ReplySenderKind::Transport(Box::new(transport)))
+    }
+
+    /// Creates a reply sender which downcasts the reply before sending it through a typed transport.
+    ///
+    /// The types must match the reply and message of the request, otherwise sending the reply will panic.
+    pub(crate) fn typed<T, O, M, E>(transport: T) -> Self
+    where
+        T: ReplyTransport<Result<O, SendError<M, E>>>,
+        O: Send + 'static,
+        M: Send + 'static,
+        E: Send + 'static,
+    {
+        BoxReplySender::new(Downcast {
+            transport,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Sends the reply, consuming the sender.
+    ///
+    /// Returns the reply if it's known not to have been delivered, which is only the case for the default `oneshot`
+    /// transport when the requester stopped waiting.
+    pub fn send(self, reply: BoxReplyResult) -> Result<(), BoxReplyResult> {
+        match self.0 {
+            ReplySenderKind::Oneshot(tx) => tx.send(reply),
+            ReplySenderKind::Transport(transport) => {
+                transport.send(reply);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns `true` if the receiving end of the transport has stopped waiting for the reply.
+    pub fn is_closed(&self) -> bool {
+        match &self.0 {
+            ReplySenderKind::Oneshot(tx) => tx.is_closed(),
+            ReplySenderKind::Transport(transport) => transport.is_closed(),
+        }
+    }
+
+    /// Waits until the receiving end of the transport stops waiting for the reply.
+    pub async fn closed(&mut self) {
+        match &mut self.0 {
+            ReplySenderKind::Oneshot(tx) => tx.closed().await,
+            ReplySenderKind::Transport(transport) => transport.closed().await,
+        }
+    }
+}
+
+impl From<oneshot::Sender<BoxReplyResult>> for BoxReplySender {
+    fn from(tx: oneshot::Sender<BoxReplyResult>) -> Self {
+        BoxReplySender(ReplySenderKind::Oneshot(tx))
+    }
+}
+
+impl fmt::Debug for BoxReplySender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            ReplySenderKind::Oneshot(tx) => f.debug_tuple("BoxReplySender").field(tx).finish(),
+            ReplySenderKind::Transport(_) => f
+                .debug_tuple("BoxReplySender")
+                .field(&format_args!("<transport>"))
+                .finish(),
+        }
+    }
+}
+
+/// A transport which delivers the reply to a message.
+///
+/// Replies are sent through a `oneshot` channel by default, but a [`tell`](crate::actor::ActorRef::tell) request
+/// can carry a transport of its own with [`TellRequest::reply_to`](crate::request::TellRequest::reply_to), such
+/// as an `mpsc` channel collecting the replies to many messages into a single stream, or another actor which
+/// receives the reply as a message.
+///
+/// The reply is a `Result<R::Ok, SendError<M, R::Error>>`, the same as the result of an
+/// [`ask`](crate::actor::ActorRef::ask) request, where `R` is the message's reply type and `M` is the message.
+///
+/// Transports are implemented for `oneshot` and unbounded `mpsc` senders, and for [`ActorRef`]s of actors which
+/// handle the reply as a message.
+///
+/// # Contract
+///
+/// - [`send`](ReplyTransport::send) is called at most once. It's called from the actor's task, so it must not block,
+///   and should hand the reply off rather than doing any work with it.
+/// - The reply may be an error, such as [`SendError::ActorNotRunning`] returning the message if the actor stopped
+///   before handling it, or [`SendError::HandlerError`] if the handler failed.
+/// - If the transport is dropped without `send` being called, no reply was produced, such as if the actor was
+///   killed while handling the message.
+/// - [`is_closed`](ReplyTransport::is_closed) and [`closed`](ReplyTransport::closed) report when the receiving end
+///   has gone away, which lets handlers detect [abandoned requests](crate::message::Context::is_reply_abandoned).
+///   Transports which can't tell should keep the defaults, which never close.
+///
+/// # Example
+///
+/// ```
+/// use kameo::message::{Context, Message};
+/// use tokio::sync::mpsc;
+///
+/// # #[derive(kameo::Actor)]
+/// # struct Doubler;
+/// #
+/// impl Message<u32> for Doubler {
+///     type Reply = u32;
+///
+///     async fn handle(&mut self, n: u32, _: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         n * 2
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let actor_ref = kameo::spawn(Doubler);
+/// let (tx, mut rx) = mpsc::unbounded_channel();
+/// for n in 1..=3 {
+///     actor_ref.tell(n).reply_to(tx.clone()).await?;
+/// }
+///
+/// assert_eq!(rx.recv().await.unwrap()?, 2);
+/// assert_eq!(rx.recv().await.unwrap()?, 4);
+/// assert_eq!(rx.recv().await.unwrap()?, 6);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+///
+/// [`ActorRef`]: crate::actor::ActorRef
+pub trait ReplyTransport<R>: Send + Sync + 'static {
+    /// Delivers the reply.
+    fn send(self: Box<Self>, reply: R);
+
+    /// Returns `true` if the receiving end has stopped waiting for the reply.
+    fn is_closed(&self) -> bool {
+        false
+    }
+
+    /// Waits until the receiving end stops waiting for the reply.
+    fn closed(&mut self) -> BoxFuture<'_, ()> {
+        future::pending().boxed()
+    }
+}
+
+impl<R: Send + 'static> ReplyTransport<R> for oneshot::Sender<R> {
+    fn send(self: Box<Self>, reply: R) {
+        let _ = (*self).send(reply);
+    }
+
+    fn is_closed(&self) -> bool {
+        oneshot::Sender::is_closed(self)
+    }
+
+    fn closed(&mut self) -> BoxFuture<'_, ()> {
+        oneshot::Sender::closed(self).boxed()
+    }
+}
+
+impl<R: Send + 'static> ReplyTransport<R> for mpsc::UnboundedSender<R> {
+    fn send(self: Box<Self>, reply: R) {
+        let _ = mpsc::UnboundedSender::send(&self, reply);
+    }
+
+    fn is_closed(&self) -> bool {
+        mpsc::UnboundedSender::is_closed(self)
+    }
+
+    fn closed(&mut self) -> BoxFuture<'_, ()> {
+        mpsc::UnboundedSender::closed(self).boxed()
+    }
+}
+
+impl<A, R> ReplyTransport<R> for ActorRef<A>
+where
+    A: Actor + Message<R>,
+    R: Send + 'static,
+{
+    fn send(self: Box<Self>, reply: R) {
+        if let Err(SendError::MailboxFull(reply)) = self.tell(reply).try_send_sync() {
+            // Sending can't block the actor, so the reply waits for capacity in a task of its own
+            let info = self.info.clone();
+            info.spawn(
+                async move {
+                    let _ = self.tell(reply).send().await;
+                }
+                .boxed(),
+            );
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        !self.is_alive()
+    }
+
+    fn closed(&mut self) -> BoxFuture<'_, ()> {
+        self.wait_for_stop().boxed()
+    }
+}
+
+/// Downcasts a type erased reply for a typed transport.
+struct Downcast<T, O, M, E> {
+    transport: T,
+    phantom: PhantomData<fn(O, M, E)>,
+}
+
+impl<T, O, M, E> ReplyTransport<BoxReplyResult> for Downcast<T, O, M, E>
+where
+    T: ReplyTransport<Result<O, SendError<M, E>>>,
+    O: Send + 'static,
+    M: Send + 'static,
+    E: Send + 'static,
+{
+    fn send(self: Box<Self>, reply: BoxReplyResult) {
+        let reply = match reply {
+            Ok(val) => Ok(val.downcast().unwrap()),
+            Err(err) => Err(err.downcast()),
+        };
+        Box::new(self.transport).send(reply);
+    }
+
+    fn is_closed(&self) -> bool {
+        self.transport.is_closed()
+    }
+
+    fn closed(&mut self) -> BoxFuture<'_, ()> {
+        self.transport.closed()
+    }
+}
 
 /// A deligated reply that has been forwarded to another actor.
 pub type ForwardedReply<T, M, E = ()> = DelegatedReply<Result<T, SendError<M, E>>>;
@@ -65,6 +389,10 @@ pub type ForwardedReply<T, M, E = ()> = DelegatedReply<Result<T, SendError<M, E>
 /// This is implemented for all many std lib types, and can be implemented on custom types manually or with the derive
 /// macro.
 ///
+/// Replies are type erased into a `Box<dyn Any + Send>` and sent back to the caller, which may be running on another
+/// thread. For this reason, a reply and each of its associated types must be `Send + 'static`, and types which
+/// aren't, such as `Rc<T>`, are rejected at compile time rather than when the reply is sent.
+///
 /// # Example
 ///
 /// ```
@@ -73,6 +401,41 @@ pub type ForwardedReply<T, M, E = ()> = DelegatedReply<Result<T, SendError<M, E>
 /// #[derive(Reply)]
 /// pub struct Foo { }
 /// ```
+///
+/// A reply which can't be sent between threads fails to compile:
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+///
+/// use kameo::Reply;
+///
+/// #[derive(Reply)]
+/// pub struct Foo(Rc<()>);
+/// ```
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+///
+/// use kameo::message::{Context, Message};
+///
+/// # #[derive(kameo::Actor)]
+/// # struct MyActor;
+/// #
+/// struct Get;
+///
+/// impl Message<Get> for MyActor {
+///     type Reply = Rc<()>;
+///
+///     async fn handle(&mut self, _msg: Get, _ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         Rc::new(())
+///     }
+/// }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a message reply",
+    label = "the reply type of a message must implement `Reply`",
+    note = "implement `Reply` for `{Self}`, for example with `#[derive(Reply)]`"
+)]
 pub trait Reply: Send + 'static {
     /// The success type in the reply.
     type Ok: Send + 'static;
@@ -83,12 +446,31 @@ pub trait Reply: Send + 'static {
     /// In almost all cases this will be `Self`. The only exception is the `DelegatedReply` type.
     type Value: Reply;
 
+    /// Whether the reply can ever be an error.
+    ///
+    /// Replies which can't error, such as those with an [`Infallible`](crate::error::Infallible) error type,
+    /// should set this to `false`, allowing the actor to skip checking a told message's reply for errors.
+    /// If this is `false`, [`into_boxed_err`](Reply::into_boxed_err) is never called on replies to told messages,
+    /// so an error would be silently ignored rather than passed to
+    /// [`Actor::on_async_error`].
+    const CAN_ERROR: bool = true;
+
     /// Converts a reply to a `Result`.
     fn to_result(self) -> Result<Self::Ok, Self::Error>;
 
     /// Converts the reply into a `Box<fmt::Debug + Send + Sync + 'static>` if it's an Err, otherwise `None`.
     fn into_boxed_err(self) -> Option<BoxDebug>;
 
+    /// Converts an error which rejected a told message in [`Message::validate`] into a
+    /// `Box<fmt::Debug + Send + Sync + 'static>`, to be passed to [`Actor::on_async_error`].
+    ///
+    /// Since the error type isn't required to implement `Debug`, the error is described by its type name by default.
+    /// Replies whose error type implements `Debug`, such as `Result`, box the error itself.
+    fn into_boxed_validation_err(err: Self::Error) -> BoxDebug {
+        let _ = err;
+        Box::new(ErrorTypeName(any::type_name::<Self::Error>()))
+    }
+
     /// Converts the type to Self::Reply.
     ///
     /// In almost all cases, this will simply return itself.
@@ -131,11 +513,24 @@ where
         None
     }
 
+    fn into_boxed_validation_err(err: Self::Error) -> BoxDebug {
+        R::into_boxed_validation_err(err)
+    }
+
     fn into_value(self) -> Self::Value {
         unimplemented!("a DeligatedReply cannot be converted to a value and is only a marker type")
     }
 }
 
+/// Describes an error which doesn't implement `Debug` by its type name.
+struct ErrorTypeName(&'static str);
+
+impl fmt::Debug for ErrorTypeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
 /// A mechanism for sending replies back to the original requester in a message exchange.
 ///
 /// `ReplySender` encapsulates the functionality to send a response back to whereever
@@ -198,10 +593,18 @@ impl<R> ReplySender<R> {
         let _ = self.tx.send(
             reply
                 .to_result()
-                .map(|value| Box::new(value) as BoxReply)
+                .map(BoxReply::new)
                 .map_err(|err| BoxSendError::HandlerError(Box::new(err))),
         );
     }
+
+    /// Declines to reply, consuming the `ReplySender`.
+    ///
+    /// The requester receives [`SendError::NoReply`] instead of waiting for a reply
+    /// that will never arrive.
+    pub fn decline(self) {
+        let _ = self.tx.send(Err(BoxSendError::NoReply));
+    }
 }
 
 impl<R: ?Sized> fmt::Debug for ReplySender<R> {
@@ -213,6 +616,72 @@ impl<R: ?Sized> fmt::Debug for ReplySender<R> {
     }
 }
 
+/// The reply sender of a message whose handler panicked, passed to [`Actor::on_panic_reply`].
+///
+/// The caller of the message is still waiting for a reply, and receives the value sent with
+/// [`send`](StrandedReply::send) instead of an error. Dropping the `StrandedReply` leaves the caller with
+/// [`SendError::ActorStopped`], as if the hook wasn't implemented.
+#[must_use = "the caller receives an error if no reply is sent"]
+pub struct StrandedReply<A> {
+    tx: BoxReplySender,
+    message: any::TypeId,
+    message_name: &'static str,
+    phantom: PhantomData<fn() -> A>,
+}
+
+impl<A: Actor> StrandedReply<A> {
+    pub(crate) fn new(
+        tx: BoxReplySender,
+        message: any::TypeId,
+        message_name: &'static str,
+    ) -> Self {
+        StrandedReply {
+            tx,
+            message,
+            message_name,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the type name of the message whose handler panicked.
+    pub fn message_name(&self) -> &'static str {
+        self.message_name
+    }
+
+    /// Returns `true` if the handler which panicked was handling a message of type `M`.
+    pub fn is<M: 'static>(&self) -> bool {
+        self.message == any::TypeId::of::<M>()
+    }
+
+    /// Sends a fallback reply to the caller, as if the handler for message `M` had returned it.
+    ///
+    /// Returns the `StrandedReply` back if the handler which panicked wasn't handling a message of type `M`.
+    pub fn send<M>(self, reply: <A as Message<M>>::Reply) -> Result<(), Self>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+    {
+        if !self.is::<M>() {
+            return Err(self);
+        }
+        ReplySender::new(self.tx).send(reply.into_value());
+        Ok(())
+    }
+
+    /// Declines to reply, so the caller receives [`SendError::NoReply`] instead of an error about the panic.
+    pub fn decline(self) {
+        let _ = self.tx.send(Err(BoxSendError::NoReply));
+    }
+}
+
+impl<A> fmt::Debug for StrandedReply<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StrandedReply")
+            .field("message_name", &self.message_name)
+            .finish()
+    }
+}
+
 impl<T, E> Reply for Result<T, E>
 where
     T: Send + 'static,
@@ -230,6 +699,10 @@ where
         self.map_err(|err| Box::new(err) as BoxDebug).err()
     }
 
+    fn into_boxed_validation_err(err: Self::Error) -> BoxDebug {
+        Box::new(err)
+    }
+
     #[inline]
     fn into_value(self) -> Self::Value {
         self
@@ -265,6 +738,8 @@ macro_rules! impl_infallible_reply {
             type Error = $crate::error::Infallible;
             type Value = Self;
 
+            const CAN_ERROR: bool = false;
+
             fn to_result(self) -> Result<Self, $crate::error::Infallible> {
                 Ok(self)
             }
@@ -390,3 +865,32 @@ impl_infallible_reply!([
     {A: 'static + Send, B: 'static + Send, C: 'static + Send, D: 'static + Send, E: 'static + Send, F: 'static + Send, G: 'static + Send, H: 'static + Send, I: 'static + Send, J: 'static + Send, K: 'static + Send, L: 'static + Send, M: 'static + Send, N: 'static + Send, O: 'static + Send, P: 'static + Send, Q: 'static + Send, R: 'static + Send, S: 'static + Send, T: 'static + Send, U: 'static + Send, V: 'static + Send, W: 'static + Send, X: 'static + Send, Y: 'static + Send} (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y),
     {A: 'static + Send, B: 'static + Send, C: 'static + Send, D: 'static + Send, E: 'static + Send, F: 'static + Send, G: 'static + Send, H: 'static + Send, I: 'static + Send, J: 'static + Send, K: 'static + Send, L: 'static + Send, M: 'static + Send, N: 'static + Send, O: 'static + Send, P: 'static + Send, Q: 'static + Send, R: 'static + Send, S: 'static + Send, T: 'static + Send, U: 'static + Send, V: 'static + Send, W: 'static + Send, X: 'static + Send, Y: 'static + Send, Z: 'static + Send} (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z),
 ]);
+
+#[cfg(test)]
+mod tests {
+    use super::BoxReply;
+
+    #[test]
+    fn box_reply_downcasts_inline_replies() {
+        let reply = BoxReply::new(7u32);
+        // A mismatched type returns the reply so it can be tried again
+        let reply = reply.downcast::<u64>().unwrap_err();
+        let reply = reply.downcast::<i32>().unwrap_err();
+        assert_eq!(reply.downcast::<u32>().unwrap(), 7);
+
+        assert!(BoxReply::new(true).downcast::<bool>().unwrap());
+        assert_eq!(BoxReply::new('k').downcast::<char>().unwrap(), 'k');
+        assert_eq!(BoxReply::new(-1.5f64).downcast::<f64>().unwrap(), -1.5);
+    }
+
+    #[test]
+    fn box_reply_downcasts_boxed_replies() {
+        let reply = BoxReply::new("hello".to_string());
+        let reply = reply.downcast::<u32>().unwrap_err();
+        assert_eq!(reply.downcast::<String>().unwrap(), "hello");
+
+        let reply = BoxReply::new(Some(7u32));
+        assert_eq!(reply.downcast::<Option<u32>>().unwrap(), Some(7));
+        assert_eq!(BoxReply::new(()).downcast::<()>().unwrap(), ());
+    }
+}
@@ -9,10 +9,14 @@
 //! - **Tell (bounded)**: refers to sending a message using [`tell`] on an actor with a [`BoundedMailbox`].
 //! - **Tell (unbounded)**: refers to sending a message using [`tell`] on an actor with an [`UnboundedMailbox`].
 //!
+//! Actors with a [`LifoMailbox`] or a [`FairMailbox`] support the same methods as actors with an [`UnboundedMailbox`].
+//!
 //! [`tell`]: method@crate::actor::ActorRef::tell
 //! [`ask`]: method@crate::actor::ActorRef::ask
 //! [`BoundedMailbox`]: crate::mailbox::bounded::BoundedMailbox
 //! [`UnboundedMailbox`]: crate::mailbox::unbounded::UnboundedMailbox
+//! [`LifoMailbox`]: crate::mailbox::lifo::LifoMailbox
+//! [`FairMailbox`]: crate::mailbox::fair::FairMailbox
 //!
 //! **Legend**
 //!
@@ -47,6 +51,7 @@ use std::time::Duration;
 use futures::Future;
 
 mod ask;
+mod scope;
 mod tell;
 
 #[cfg(feature = "remote")]
@@ -55,7 +60,8 @@ pub use ask::RemoteAskRequest;
 #[cfg(feature = "remote")]
 pub use tell::RemoteTellRequest;
 
-pub use ask::{AskRequest, LocalAskRequest};
+pub use ask::{AskRequest, LocalAskRequest, ReplyFuture};
+pub use scope::{RequestScope, ScopedReply};
 pub use tell::{LocalTellRequest, TellRequest};
 
 use crate::{error::SendError, reply::ReplySender, Reply};
@@ -1,6 +1,16 @@
 use futures::{future::BoxFuture, FutureExt};
-use std::{future::IntoFuture, marker::PhantomData, time::Duration};
-use tokio::{sync::oneshot, time::timeout};
+use std::{
+    future::{Future, IntoFuture},
+    marker::PhantomData,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::oneshot,
+    time::{sleep, timeout, Sleep},
+};
 
 #[cfg(feature = "remote")]
 use crate::remote::{RemoteActor, RemoteMessage, SwarmCommand, SwarmResponse};
@@ -8,9 +18,12 @@ use crate::remote::{RemoteActor, RemoteMessage, SwarmCommand, SwarmResponse};
 use crate::{
     actor,
     error::{self, SendError},
-    mailbox::{bounded::BoundedMailbox, unbounded::UnboundedMailbox, Mailbox, Signal},
-    message::{BoxReply, Message},
-    reply::ReplySender,
+    mailbox::{
+        bounded::BoundedMailbox, fair::FairMailbox, lifo::LifoMailbox, unbounded::UnboundedMailbox,
+        Mailbox, Signal,
+    },
+    message::{Extensions, Message, MessageMeta},
+    reply::{BoxReply, ReplySender},
     Actor, Reply,
 };
 
@@ -38,7 +51,9 @@ where
 {
     mailbox: &'a Mb,
     signal: Signal<A>,
+    closed: &'a AtomicBool,
     rx: oneshot::Receiver<Result<BoxReply, error::BoxSendError>>,
+    too_large: bool,
 }
 
 /// A request to a remote actor.
@@ -74,6 +89,7 @@ where
         M: Send + 'static,
     {
         let (reply, rx) = oneshot::channel();
+        let too_large = crate::message::is_too_large::<A, M>(&msg);
 
         AskRequest {
             location: LocalAskRequest {
@@ -81,10 +97,15 @@ where
                 signal: Signal::Message {
                     message: Box::new(msg),
                     actor_ref: actor_ref.clone(),
-                    reply: Some(reply),
+                    reply: Some(reply.into()),
                     sent_within_actor: actor_ref.is_current(),
+                    extensions: Extensions::new(),
+                    meta: MessageMeta::for_send(),
+                    sent_at: Instant::now(),
                 },
                 rx,
+                closed: &actor_ref.info.closed,
+                too_large,
             },
             mailbox_timeout: WithoutRequestTimeout,
             reply_timeout: WithoutRequestTimeout,
@@ -99,6 +120,35 @@ impl<A, M, Tm, Tr> AskRequest<LocalAskRequest<'_, A, A::Mailbox>, A::Mailbox, M,
 where
     A: Actor,
 {
+    /// Attaches an extension to the message, accessible to the handler through [`Context::extensions`].
+    ///
+    /// [`Context::extensions`]: crate::message::Context::extensions
+    pub fn extension<V: Send + Sync + 'static>(mut self, val: V) -> Self {
+        if let Signal::Message { extensions, .. } = &mut self.location.signal {
+            extensions.insert(val);
+        }
+        self
+    }
+
+    /// Rejects the message with [`SendError::Closed`](error::SendError::Closed) if the actor has been closed, or
+    /// [`SendError::TooLarge`](error::SendError::TooLarge) if it's larger than the actor accepts.
+    fn check_accepted<E>(self) -> Result<Self, error::SendError<M, E>>
+    where
+        M: 'static,
+    {
+        if self.location.closed.load(Ordering::Acquire) {
+            return Err(error::SendError::Closed(
+                self.location.signal.downcast_message().unwrap(),
+            ));
+        }
+        if self.location.too_large {
+            return Err(error::SendError::TooLarge(
+                self.location.signal.downcast_message().unwrap(),
+            ));
+        }
+        Ok(self)
+    }
+
     #[cfg(all(debug_assertions, feature = "tracing"))]
     fn warn_deadlock(&self, msg: &'static str) {
         use tracing::warn;
@@ -113,6 +163,186 @@ where
     fn warn_deadlock(&self, _msg: &'static str) {}
 }
 
+impl<'a, A, M, Tr>
+    AskRequest<LocalAskRequest<'a, A, A::Mailbox>, A::Mailbox, M, WithoutRequestTimeout, Tr>
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+    Tr: Into<MaybeRequestTimeout>,
+{
+    /// Sends the message, waiting only until it has been enqueued in the actor's mailbox, and returns a
+    /// [`ReplyFuture`] which resolves to the actor's reply.
+    ///
+    /// Unlike awaiting the request, which waits for the reply, this separates failing to enqueue the message,
+    /// such as when the actor isn't running, from the outcome of handling it. Bounded mailboxes still apply
+    /// backpressure, and the message is returned in the error if it couldn't be enqueued.
+    ///
+    /// If a [reply timeout](AskRequest::reply_timeout) is set, it starts once the message has been enqueued.
+    ///
+    /// # Cancellation
+    ///
+    /// Dropping the [`ReplyFuture`] doesn't cancel the message, which is still handled by the actor,
+    /// but its reply is discarded. Actors can abort the handler instead with
+    /// [`Actor::abort_abandoned_requests`](crate::Actor::abort_abandoned_requests).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kameo::Actor;
+    /// # use kameo::message::{Context, Message};
+    /// #
+    /// # #[derive(Actor)]
+    /// # struct MyActor;
+    /// #
+    /// # struct Msg;
+    /// #
+    /// # impl Message<Msg> for MyActor {
+    /// #     type Reply = u32;
+    /// #     async fn handle(&mut self, msg: Msg, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply { 10 }
+    /// # }
+    /// #
+    /// # tokio_test::block_on(async {
+    /// let actor_ref = kameo::spawn(MyActor);
+    /// let reply = actor_ref.ask(Msg).send_returning_handle().await?;
+    /// // The message is in the actor's mailbox
+    /// assert_eq!(reply.await?, 10);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn send_returning_handle(
+        self,
+    ) -> Result<ReplyFuture<M, A::Reply>, error::SendError<M, <A::Reply as Reply>::Error>> {
+        let req = self.check_accepted()?;
+        let reply_timeout = match req.reply_timeout.into() {
+            MaybeRequestTimeout::NoTimeout => <A as Message<M>>::default_timeout().into(),
+            reply_timeout => reply_timeout,
+        };
+        let signal = match reply_timeout {
+            MaybeRequestTimeout::Timeout(duration) => {
+                req.location.signal.with_reply_deadline(duration)
+            }
+            MaybeRequestTimeout::NoTimeout => req.location.signal,
+        };
+        req.location.mailbox.send(signal).await?;
+        Ok(ReplyFuture::new(req.location.rx, reply_timeout))
+    }
+}
+
+impl<'a, A, M, Tr>
+    AskRequest<
+        LocalAskRequest<'a, A, BoundedMailbox<A>>,
+        BoundedMailbox<A>,
+        M,
+        WithRequestTimeout,
+        Tr,
+    >
+where
+    A: Actor<Mailbox = BoundedMailbox<A>> + Message<M>,
+    M: Send + 'static,
+    Tr: Into<MaybeRequestTimeout>,
+{
+    /// Sends the message, waiting at most for the mailbox timeout for it to be enqueued in the actor's mailbox,
+    /// and returns a [`ReplyFuture`] which resolves to the actor's reply.
+    ///
+    /// See [`send_returning_handle`](AskRequest::send_returning_handle) for more information.
+    pub async fn send_returning_handle(
+        self,
+    ) -> Result<ReplyFuture<M, A::Reply>, error::SendError<M, <A::Reply as Reply>::Error>> {
+        let req = self.check_accepted()?;
+        let reply_timeout = match req.reply_timeout.into() {
+            MaybeRequestTimeout::NoTimeout => <A as Message<M>>::default_timeout().into(),
+            reply_timeout => reply_timeout,
+        };
+        let signal = match reply_timeout {
+            MaybeRequestTimeout::Timeout(duration) => {
+                req.location.signal.with_reply_deadline(duration)
+            }
+            MaybeRequestTimeout::NoTimeout => req.location.signal,
+        };
+        req.location
+            .mailbox
+            .0
+            .send_timeout(signal, req.mailbox_timeout.0)
+            .await?;
+        Ok(ReplyFuture::new(req.location.rx, reply_timeout))
+    }
+}
+
+/// A future for the reply to a message which has been enqueued in an actor's mailbox,
+/// returned by [`AskRequest::send_returning_handle`].
+///
+/// Dropping the future doesn't cancel the message, which is still handled by the actor, but its reply is discarded,
+/// unless the actor aborts abandoned requests with [`Actor::abort_abandoned_requests`](crate::Actor::abort_abandoned_requests).
+#[allow(missing_debug_implementations)]
+#[must_use = "the reply is discarded unless the future is awaited"]
+pub struct ReplyFuture<M, R: Reply> {
+    rx: oneshot::Receiver<Result<BoxReply, error::BoxSendError>>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    phantom: PhantomData<fn() -> (M, R)>,
+}
+
+impl<M, R: Reply> ReplyFuture<M, R> {
+    fn new(
+        rx: oneshot::Receiver<Result<BoxReply, error::BoxSendError>>,
+        reply_timeout: MaybeRequestTimeout,
+    ) -> Self {
+        ReplyFuture {
+            rx,
+            timeout: match reply_timeout {
+                MaybeRequestTimeout::Timeout(duration) => Some(Box::pin(sleep(duration))),
+                MaybeRequestTimeout::NoTimeout => None,
+            },
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<M, R> Future for ReplyFuture<M, R>
+where
+    M: 'static,
+    R: Reply,
+{
+    type Output = Result<R::Ok, error::SendError<M, R::Error>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(res) = self.rx.poll_unpin(cx) {
+            return Poll::Ready(match res {
+                Ok(Ok(val)) => Ok(val.downcast().unwrap()),
+                Ok(Err(err)) => Err(err.downcast()),
+                Err(err) => Err(err.into()),
+            });
+        }
+        if let Some(timeout) = self.timeout.as_mut() {
+            ready!(timeout.as_mut().poll(cx));
+            return Poll::Ready(Err(error::SendError::Timeout(None)));
+        }
+        Poll::Pending
+    }
+}
+
+/// Attaches the message's default reply deadline, for requests without a reply timeout of their own.
+fn with_default_reply_deadline<A, M>(signal: Signal<A>) -> (Signal<A>, Option<Duration>)
+where
+    A: Actor + Message<M>,
+    M: Send + 'static,
+{
+    match <A as Message<M>>::default_timeout() {
+        Some(duration) => (signal.with_reply_deadline(duration), Some(duration)),
+        None => (signal, None),
+    }
+}
+
+/// Waits for a reply, failing with [`SendError::Timeout`] if `reply_timeout` elapses first.
+async fn recv_reply<M, E>(
+    rx: oneshot::Receiver<Result<BoxReply, error::BoxSendError>>,
+    reply_timeout: Option<Duration>,
+) -> Result<Result<BoxReply, error::BoxSendError>, SendError<M, E>> {
+    match reply_timeout {
+        Some(duration) => Ok(timeout(duration, rx).await??),
+        None => Ok(rx.await?),
+    }
+}
+
 #[cfg(feature = "remote")]
 impl<'a, A, M>
     AskRequest<
@@ -239,6 +469,16 @@ where
 }
 
 macro_rules! impl_message_trait {
+    (local, $($async:ident)? => $trait:ident :: $method:ident, [$mailbox:ident $(, $rest:ident)*], $mailbox_timeout:ident, $reply_timeout:ident, |$req:ident| $($body:tt)*) => {
+        impl_message_trait!(local, $($async)? => $trait::$method, $mailbox, $mailbox_timeout, $reply_timeout, |$req| $($body)*);
+        impl_message_trait!(local, $($async)? => $trait::$method, [$($rest),*], $mailbox_timeout, $reply_timeout, |$req| $($body)*);
+    };
+    (local, $($async:ident)? => $trait:ident :: $method:ident, [], $mailbox_timeout:ident, $reply_timeout:ident, |$req:ident| $($body:tt)*) => {};
+    (remote, $($async:ident)? => $trait:ident :: $method:ident, [$mailbox:ident $(, $rest:ident)*], $mailbox_timeout:ident, $reply_timeout:ident, |$req:ident| $($body:tt)*) => {
+        impl_message_trait!(remote, $($async)? => $trait::$method, $mailbox, $mailbox_timeout, $reply_timeout, |$req| $($body)*);
+        impl_message_trait!(remote, $($async)? => $trait::$method, [$($rest),*], $mailbox_timeout, $reply_timeout, |$req| $($body)*);
+    };
+    (remote, $($async:ident)? => $trait:ident :: $method:ident, [], $mailbox_timeout:ident, $reply_timeout:ident, |$req:ident| $($body:tt)*) => {};
     (local, $($async:ident)? => $trait:ident :: $method:ident, $mailbox_timeout:ident, $reply_timeout:ident, |$req:ident| $($body:tt)*) => {
         impl<'a, A, M> $trait
             for AskRequest<
@@ -259,7 +499,7 @@ macro_rules! impl_message_trait {
             $($async)? fn $method(self) -> Result<Self::Ok, Self::Error> {
                 self.warn_deadlock("An actor is sending an `ask` request to itself, which will likely lead to a deadlock. To avoid this, use a `tell` request instead.");
 
-                let $req = self;
+                let $req = self.check_accepted()?;
                 $($body)*
             }
         }
@@ -282,7 +522,7 @@ macro_rules! impl_message_trait {
 
             #[inline]
             $($async)? fn $method(self) -> Result<Self::Ok, Self::Error> {
-                let $req = self;
+                let $req = self.check_accepted()?;
                 $($body)*
             }
         }
@@ -362,9 +602,10 @@ impl_message_trait!(
     WithoutRequestTimeout,
     WithoutRequestTimeout,
     |req| {
-        req.location.mailbox.send(req.location.signal).await?;
-        match req.location.rx.await? {
-            Ok(val) => Ok(*val.downcast().unwrap()),
+        let (signal, reply_timeout) = with_default_reply_deadline::<A, M>(req.location.signal);
+        req.location.mailbox.send(signal).await?;
+        match recv_reply(req.location.rx, reply_timeout).await? {
+            Ok(val) => Ok(val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
         }
     }
@@ -376,9 +617,10 @@ impl_message_trait!(
     WithoutRequestTimeout,
     WithRequestTimeout,
     |req| {
-        req.location.mailbox.0.send(req.location.signal).await?;
+        let signal = req.location.signal.with_reply_deadline(req.reply_timeout.0);
+        req.location.mailbox.0.send(signal).await?;
         match timeout(req.reply_timeout.0, req.location.rx).await?? {
-            Ok(val) => Ok(*val.downcast().unwrap()),
+            Ok(val) => Ok(val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
         }
     }
@@ -390,13 +632,14 @@ impl_message_trait!(
     WithRequestTimeout,
     WithoutRequestTimeout,
     |req| {
+        let (signal, reply_timeout) = with_default_reply_deadline::<A, M>(req.location.signal);
         req.location
             .mailbox
             .0
-            .send_timeout(req.location.signal, req.mailbox_timeout.0)
+            .send_timeout(signal, req.mailbox_timeout.0)
             .await?;
-        match req.location.rx.await? {
-            Ok(val) => Ok(*val.downcast().unwrap()),
+        match recv_reply(req.location.rx, reply_timeout).await? {
+            Ok(val) => Ok(val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
         }
     }
@@ -408,13 +651,14 @@ impl_message_trait!(
     WithRequestTimeout,
     WithRequestTimeout,
     |req| {
+        let signal = req.location.signal.with_reply_deadline(req.reply_timeout.0);
         req.location
             .mailbox
             .0
-            .send_timeout(req.location.signal, req.mailbox_timeout.0)
+            .send_timeout(signal, req.mailbox_timeout.0)
             .await?;
         match timeout(req.reply_timeout.0, req.location.rx).await?? {
-            Ok(val) => Ok(*val.downcast().unwrap()),
+            Ok(val) => Ok(val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
         }
     }
@@ -423,13 +667,14 @@ impl_message_trait!(
 impl_message_trait!(
     local,
     async => MessageSend::send,
-    UnboundedMailbox,
+    [UnboundedMailbox, LifoMailbox, FairMailbox],
     WithoutRequestTimeout,
     WithRequestTimeout,
     |req| {
-        req.location.mailbox.0.send(req.location.signal)?;
+        let signal = req.location.signal.with_reply_deadline(req.reply_timeout.0);
+        req.location.mailbox.0.send(signal)?;
         match timeout(req.reply_timeout.0, req.location.rx).await?? {
-            Ok(val) => Ok(*val.downcast().unwrap()),
+            Ok(val) => Ok(val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
         }
     }
@@ -441,7 +686,7 @@ impl_message_trait!(
     async => MessageSend::send,
     WithoutRequestTimeout,
     WithoutRequestTimeout,
-    |req| (None, None)
+    |req| (None, <A as Message<M>>::default_timeout())
 );
 #[cfg(feature = "remote")]
 impl_message_trait!(
@@ -459,7 +704,7 @@ impl_message_trait!(
     BoundedMailbox,
     WithRequestTimeout,
     WithoutRequestTimeout,
-    |req| (Some(req.mailbox_timeout.0), None)
+    |req| (Some(req.mailbox_timeout.0), <A as Message<M>>::default_timeout())
 );
 #[cfg(feature = "remote")]
 impl_message_trait!(
@@ -475,7 +720,7 @@ impl_message_trait!(
 impl_message_trait!(
     remote,
     async => MessageSend::send,
-    UnboundedMailbox,
+    [UnboundedMailbox, LifoMailbox, FairMailbox],
     WithoutRequestTimeout,
     WithRequestTimeout,
     |req| (None, Some(req.reply_timeout.0))
@@ -547,7 +792,7 @@ impl_message_trait!(
 impl_message_trait!(
     local,
     async => MessageSend::send,
-    UnboundedMailbox,
+    [UnboundedMailbox, LifoMailbox, FairMailbox],
     MaybeRequestTimeout,
     MaybeRequestTimeout,
     |req| {
@@ -595,9 +840,10 @@ impl_message_trait!(
     WithoutRequestTimeout,
     WithoutRequestTimeout,
     |req| {
-        req.location.mailbox.try_send(req.location.signal)?;
-        match req.location.rx.await? {
-            Ok(val) => Ok(*val.downcast().unwrap()),
+        let (signal, reply_timeout) = with_default_reply_deadline::<A, M>(req.location.signal);
+        req.location.mailbox.try_send(signal)?;
+        match recv_reply(req.location.rx, reply_timeout).await? {
+            Ok(val) => Ok(val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
         }
     }
@@ -609,9 +855,10 @@ impl_message_trait!(
     WithoutRequestTimeout,
     WithRequestTimeout,
     |req| {
-        req.location.mailbox.0.try_send(req.location.signal)?;
+        let signal = req.location.signal.with_reply_deadline(req.reply_timeout.0);
+        req.location.mailbox.0.try_send(signal)?;
         match timeout(req.reply_timeout.0, req.location.rx).await?? {
-            Ok(val) => Ok(*val.downcast().unwrap()),
+            Ok(val) => Ok(val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
         }
     }
@@ -620,13 +867,14 @@ impl_message_trait!(
 impl_message_trait!(
     local,
     async => TryMessageSend::try_send,
-    UnboundedMailbox,
+    [UnboundedMailbox, LifoMailbox, FairMailbox],
     WithoutRequestTimeout,
     WithRequestTimeout,
     |req| {
-        req.location.mailbox.0.send(req.location.signal)?;
+        let signal = req.location.signal.with_reply_deadline(req.reply_timeout.0);
+        req.location.mailbox.0.send(signal)?;
         match timeout(req.reply_timeout.0, req.location.rx).await?? {
-            Ok(val) => Ok(*val.downcast().unwrap()),
+            Ok(val) => Ok(val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
         }
     }
@@ -638,7 +886,7 @@ impl_message_trait!(
     async => TryMessageSend::try_send,
     WithoutRequestTimeout,
     WithoutRequestTimeout,
-    |req| (None, None)
+    |req| (None, <A as Message<M>>::default_timeout())
 );
 #[cfg(feature = "remote")]
 impl_message_trait!(
@@ -654,7 +902,7 @@ impl_message_trait!(
 impl_message_trait!(
     remote,
     async => TryMessageSend::try_send,
-    UnboundedMailbox,
+    [UnboundedMailbox, LifoMailbox, FairMailbox],
     WithoutRequestTimeout,
     WithRequestTimeout,
     |req| (None, Some(req.reply_timeout.0))
@@ -705,7 +953,7 @@ impl_message_trait!(
 impl_message_trait!(
     local,
     async => TryMessageSend::try_send,
-    UnboundedMailbox,
+    [UnboundedMailbox, LifoMailbox, FairMailbox],
     MaybeRequestTimeout,
     MaybeRequestTimeout,
     |req| {
@@ -755,7 +1003,7 @@ impl_message_trait!(
     |req| {
         req.location.mailbox.blocking_send(req.location.signal)?;
         match req.location.rx.blocking_recv()? {
-            Ok(val) => Ok(*val.downcast().unwrap()),
+            Ok(val) => Ok(val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
         }
     }
@@ -772,7 +1020,7 @@ impl_message_trait!(
     |req| {
         req.location.mailbox.try_send(req.location.signal)?;
         match req.location.rx.blocking_recv()? {
-            Ok(val) => Ok(*val.downcast().unwrap()),
+            Ok(val) => Ok(val.downcast().unwrap()),
             Err(err) => Err(err.downcast()),
         }
     }
@@ -782,6 +1030,11 @@ impl_message_trait!(
 // === ForwardMessageSend === //
 ////////////////////////////////
 macro_rules! impl_forward_message {
+    (local, [$mailbox:ident $(, $rest:ident)*], $mailbox_timeout:ident, $reply_timeout:ident, |$req:ident, $tx:ident| $($body:tt)*) => {
+        impl_forward_message!(local, $mailbox, $mailbox_timeout, $reply_timeout, |$req, $tx| $($body)*);
+        impl_forward_message!(local, [$($rest),*], $mailbox_timeout, $reply_timeout, |$req, $tx| $($body)*);
+    };
+    (local, [], $mailbox_timeout:ident, $reply_timeout:ident, |$req:ident, $tx:ident| $($body:tt)*) => {};
     (local, $mailbox:ident, $mailbox_timeout:ident, $reply_timeout:ident, |$req:ident, $tx:ident| $($body:tt)*) => {
         impl<'a, A, M> ForwardMessageSend<A::Reply, M>
             for AskRequest<
@@ -856,7 +1109,7 @@ impl_forward_message!(
 
 impl_forward_message!(
     local,
-    UnboundedMailbox,
+    [UnboundedMailbox, LifoMailbox, FairMailbox],
     WithoutRequestTimeout,
     WithoutRequestTimeout,
     |req, tx| {
@@ -885,6 +1138,11 @@ impl_forward_message!(
 // === ForwardMessageSendSync === //
 ////////////////////////////////////
 macro_rules! impl_forward_message_sync {
+    (local, [$mailbox:ident $(, $rest:ident)*], $mailbox_timeout:ident, $reply_timeout:ident, |$req:ident, $tx:ident| $($body:tt)*) => {
+        impl_forward_message_sync!(local, $mailbox, $mailbox_timeout, $reply_timeout, |$req, $tx| $($body)*);
+        impl_forward_message_sync!(local, [$($rest),*], $mailbox_timeout, $reply_timeout, |$req, $tx| $($body)*);
+    };
+    (local, [], $mailbox_timeout:ident, $reply_timeout:ident, |$req:ident, $tx:ident| $($body:tt)*) => {};
     (local, $mailbox:ident, $mailbox_timeout:ident, $reply_timeout:ident, |$req:ident, $tx:ident| $($body:tt)*) => {
         impl<'a, A, M> ForwardMessageSendSync<A::Reply, M>
             for AskRequest<
@@ -911,7 +1169,7 @@ macro_rules! impl_forward_message_sync {
 
 impl_forward_message_sync!(
     local,
-    UnboundedMailbox,
+    [UnboundedMailbox, LifoMailbox, FairMailbox],
     WithoutRequestTimeout,
     WithoutRequestTimeout,
     |req, tx| {
@@ -989,15 +1247,21 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
 
     use crate::{
-        error::SendError,
+        actor::{
+            spawn_init, spawn_with_init, spawn_with_mailbox_parts, ActorID, ActorRef, InitActor,
+            PreparedActor, WeakActorRef,
+        },
+        error::{ActorStopReason, BoxError, SendError, SpawnError},
         mailbox::{
             bounded::{BoundedMailbox, BoundedMailboxReceiver},
             unbounded::UnboundedMailbox,
+            Mailbox, Signal,
         },
         message::{Context, Message},
+        reply::DelegatedReply,
         request::{BlockingMessageSend, MessageSend, TryBlockingMessageSend, TryMessageSend},
         spawn, Actor,
     };
@@ -1393,4 +1657,1286 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn ask_requests_to_init_actors() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor {
+            value: u32,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        impl InitActor for MyActor {
+            type Args = Option<u32>;
+
+            async fn init(args: Option<u32>, _actor_ref: ActorRef<Self>) -> Result<Self, BoxError> {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                let value = args.ok_or("missing value")?;
+                Ok(MyActor { value })
+            }
+        }
+
+        struct Get;
+
+        impl Message<Get> for MyActor {
+            type Reply = u32;
+
+            async fn handle(
+                &mut self,
+                _msg: Get,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.value
+            }
+        }
+
+        // Messages sent before the actor is constructed are queued
+        let actor_ref = spawn_init::<MyActor>(Some(7));
+        assert_eq!(actor_ref.ask(Get).send().await?, 7);
+
+        // Messages sent to an actor which failed to be constructed are rejected
+        let actor_ref = spawn_init::<MyActor>(None);
+        assert!(matches!(
+            actor_ref.ask(Get).send().await,
+            Err(SendError::ActorNotRunning(Get))
+        ));
+        actor_ref.wait_for_stop().await;
+        assert!(!actor_ref.is_alive());
+
+        // Errors from init are returned when waiting for the actor to be constructed
+        let actor_ref = spawn_with_init::<MyActor>(Some(3))
+            .await
+            .map_err(|err| err.to_string())?;
+        assert_eq!(actor_ref.ask(Get).send().await?, 3);
+        let err = spawn_with_init::<MyActor>(None).await.unwrap_err();
+        assert!(matches!(err, SpawnError::InitFailed(_)));
+        assert_eq!(err.to_string(), "panicked: missing value");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_update_stats() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Msg;
+
+        impl Message<Msg> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                _msg: Msg,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+            }
+        }
+
+        let actor_ref = spawn(MyActor);
+        let stats = actor_ref.stats();
+        assert_eq!(stats.messages_handled, 0);
+        assert!(stats.last_message_at.is_none());
+
+        actor_ref.ask(Msg).send().await?;
+        actor_ref.ask(Msg).send().await?;
+        let stats = actor_ref.stats();
+        assert_eq!(stats.messages_handled, 2);
+        assert_eq!(stats.panics, 0);
+        assert!(stats.last_message_at.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_record_queue_wait() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Sleep(Duration);
+
+        impl Message<Sleep> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                Sleep(duration): Sleep,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                tokio::time::sleep(duration).await;
+            }
+        }
+
+        let actor_ref = spawn(MyActor);
+        actor_ref.ask(Sleep(Duration::ZERO)).send().await?;
+        let first_wait = actor_ref.stats().total_queue_wait;
+
+        // The second message waits in the mailbox while the first is handled
+        let (a, b) = tokio::join!(
+            actor_ref.ask(Sleep(Duration::from_millis(50))).send(),
+            actor_ref.ask(Sleep(Duration::ZERO)).send(),
+        );
+        a?;
+        b?;
+
+        let stats = actor_ref.stats();
+        assert!(stats.queue_wait >= Duration::from_millis(40));
+        assert!(stats.total_queue_wait >= first_wait + stats.queue_wait);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_actor_died() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Sleep;
+
+        impl Message<Sleep> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                _msg: Sleep,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+        }
+
+        struct Panic;
+
+        impl Message<Panic> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                _msg: Panic,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                panic!("oh no");
+            }
+        }
+
+        let actor_ref = spawn(MyActor);
+        let reply = tokio::spawn({
+            let actor_ref = actor_ref.clone();
+            async move { actor_ref.ask(Sleep).send().await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        actor_ref.kill();
+        assert!(matches!(
+            reply.await?,
+            Err(SendError::ActorDied(ActorStopReason::Killed))
+        ));
+
+        let actor_ref = spawn(MyActor);
+        assert!(matches!(
+            actor_ref.ask(Panic).send().await,
+            Err(SendError::ActorDied(ActorStopReason::Panicked(_)))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ask_requests_observe_fully_applied_messages() -> Result<(), Box<dyn std::error::Error>>
+    {
+        struct MyActor {
+            a: u32,
+            b: u32,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Inc;
+
+        impl Message<Inc> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                _msg: Inc,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.a += 1;
+                tokio::time::sleep(Duration::from_micros(100)).await;
+                self.b += 1;
+            }
+        }
+
+        struct Read;
+
+        impl Message<Read> for MyActor {
+            type Reply = (u32, u32);
+
+            async fn handle(
+                &mut self,
+                _msg: Read,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                (self.a, self.b)
+            }
+        }
+
+        let actor_ref = spawn(MyActor { a: 0, b: 0 });
+        let writer = tokio::spawn({
+            let actor_ref = actor_ref.clone();
+            async move {
+                for _ in 0..50 {
+                    actor_ref.tell(Inc).send().await.unwrap();
+                }
+            }
+        });
+        for _ in 0..50 {
+            let (a, b) = actor_ref.ask(Read).send().await?;
+            assert_eq!(a, b);
+        }
+        writer.await?;
+        assert_eq!(actor_ref.ask(Read).send().await?, (50, 50));
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ask_requests_racing_shutdown() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        struct Echo(u32);
+
+        impl Message<Echo> for MyActor {
+            type Reply = u32;
+
+            async fn handle(
+                &mut self,
+                Echo(n): Echo,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                n
+            }
+        }
+
+        let actor_ref = spawn(MyActor);
+        let send_asks = |range: std::ops::Range<u32>| {
+            range
+                .map(|n| {
+                    let actor_ref = actor_ref.clone();
+                    tokio::spawn(async move { (n, actor_ref.ask(Echo(n)).send().await) })
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut requests = send_asks(0..100);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        actor_ref.stop_gracefully().await?;
+        requests.extend(send_asks(100..200));
+
+        for request in requests {
+            match request.await? {
+                (n, Ok(reply)) => assert_eq!(reply, n),
+                (n, Err(SendError::ActorNotRunning(msg))) => assert_eq!(msg, Echo(n)),
+                (n, Err(err)) => panic!("unexpected error for message {n}: {err:?}"),
+            }
+        }
+
+        actor_ref.wait_for_stop().await;
+        assert_eq!(
+            actor_ref.ask(Echo(0)).send().await,
+            Err(SendError::ActorNotRunning(Echo(0)))
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_declined() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Lookup(Option<u32>);
+
+        impl Message<Lookup> for MyActor {
+            type Reply = DelegatedReply<u32>;
+
+            async fn handle(
+                &mut self,
+                msg: Lookup,
+                mut ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                match msg.0 {
+                    Some(n) => ctx.reply(n),
+                    None => ctx.decline(),
+                }
+            }
+        }
+
+        struct DeclineLater;
+
+        impl Message<DeclineLater> for MyActor {
+            type Reply = DelegatedReply<u32>;
+
+            async fn handle(
+                &mut self,
+                _msg: DeclineLater,
+                mut ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                let (delegated_reply, reply_sender) = ctx.reply_sender();
+                if let Some(tx) = reply_sender {
+                    tokio::spawn(async move { tx.decline() });
+                }
+                delegated_reply
+            }
+        }
+
+        let actor_ref = spawn(MyActor);
+        assert!(matches!(actor_ref.ask(Lookup(Some(3))).send().await, Ok(3)));
+        assert!(matches!(
+            actor_ref.ask(Lookup(None)).send().await,
+            Err(SendError::NoReply)
+        ));
+        assert!(matches!(
+            actor_ref.ask(DeclineLater).send().await,
+            Err(SendError::NoReply)
+        ));
+        actor_ref.tell(Lookup(None)).send().await?;
+        assert!(matches!(actor_ref.ask(Lookup(Some(5))).send().await, Ok(5)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_reply_deadline() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Deadline;
+
+        impl Message<Deadline> for MyActor {
+            type Reply = Option<Instant>;
+
+            async fn handle(
+                &mut self,
+                _msg: Deadline,
+                ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                ctx.reply_deadline()
+            }
+        }
+
+        let actor_ref = spawn(MyActor);
+        assert_eq!(actor_ref.ask(Deadline).send().await?, None);
+
+        let sent_at = Instant::now();
+        let deadline = actor_ref
+            .ask(Deadline)
+            .reply_timeout(Duration::from_secs(10))
+            .send()
+            .await?
+            .expect("deadline should be set");
+        assert!(deadline >= sent_at + Duration::from_secs(10));
+        assert!(deadline <= Instant::now() + Duration::from_secs(10));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_stop_actor_from_handler() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Finish;
+
+        impl Message<Finish> for MyActor {
+            type Reply = u32;
+
+            async fn handle(
+                &mut self,
+                _msg: Finish,
+                mut ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                ctx.stop(ActorStopReason::Killed);
+                assert!(ctx.actor_extensions().is_empty());
+                10
+            }
+        }
+
+        let prepared_actor = PreparedActor::new();
+        let actor_ref = prepared_actor.actor_ref().clone();
+        let handle = prepared_actor.spawn(MyActor);
+
+        assert_eq!(actor_ref.ask(Finish).send().await.ok(), Some(10));
+        let (_, reason) = handle.await?;
+        assert_eq!(reason, ActorStopReason::Killed);
+        assert!(matches!(
+            actor_ref.ask(Finish).send().await,
+            Err(SendError::ActorNotRunning(Finish))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_returning_handle() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor {
+            count: u32,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = BoundedMailbox<Self>;
+        }
+
+        struct Inc;
+
+        impl Message<Inc> for MyActor {
+            type Reply = u32;
+
+            async fn handle(
+                &mut self,
+                _msg: Inc,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.count += 1;
+                self.count
+            }
+        }
+
+        let actor_ref = spawn(MyActor { count: 0 });
+
+        let first = actor_ref.ask(Inc).send_returning_handle().await?;
+        let second = actor_ref
+            .ask(Inc)
+            .mailbox_timeout(Duration::from_secs(1))
+            .reply_timeout(Duration::from_secs(1))
+            .send_returning_handle()
+            .await?;
+        // Dropping the handle still handles the message
+        drop(actor_ref.ask(Inc).send_returning_handle().await?);
+        assert_eq!(second.await?, 2);
+        assert_eq!(first.await?, 1);
+        assert_eq!(actor_ref.ask(Inc).await?, 4);
+
+        actor_ref.stop_gracefully().await?;
+        actor_ref.wait_for_stop().await;
+        assert!(matches!(
+            actor_ref.ask(Inc).send_returning_handle().await,
+            Err(SendError::ActorNotRunning(Inc))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_after_injected_signals() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Default)]
+        struct MyActor {
+            links_died: Vec<ActorID>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = BoundedMailbox<Self>;
+
+            async fn on_link_died(
+                &mut self,
+                _actor_ref: WeakActorRef<Self>,
+                id: ActorID,
+                _reason: ActorStopReason,
+            ) -> Result<Option<ActorStopReason>, BoxError> {
+                self.links_died.push(id);
+                Ok(None)
+            }
+        }
+
+        struct LinksDied;
+
+        impl Message<LinksDied> for MyActor {
+            type Reply = Vec<ActorID>;
+
+            async fn handle(
+                &mut self,
+                _msg: LinksDied,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.links_died.clone()
+            }
+        }
+
+        let (mailbox, mailbox_rx) = BoundedMailbox::new(8);
+        let actor_ref = spawn_with_mailbox_parts(MyActor::default(), mailbox.clone(), mailbox_rx);
+        let other_ref = spawn(MyActor::default());
+        mailbox
+            .send(Signal::LinkDied {
+                id: other_ref.id(),
+                reason: ActorStopReason::Killed,
+            })
+            .await?;
+        assert_eq!(actor_ref.ask(LinksDied).send().await?, vec![other_ref.id()]);
+
+        mailbox.send(Signal::Stop).await?;
+        actor_ref.wait_for_stop().await;
+        assert!(!actor_ref.is_alive());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_sender_id() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct SenderId;
+
+        impl Message<SenderId> for MyActor {
+            type Reply = Option<ActorID>;
+
+            async fn handle(
+                &mut self,
+                _msg: SenderId,
+                ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                ctx.sender_id()
+            }
+        }
+
+        struct AskSenderId(ActorRef<MyActor>);
+
+        impl Message<AskSenderId> for MyActor {
+            type Reply = Option<ActorID>;
+
+            async fn handle(
+                &mut self,
+                AskSenderId(actor_ref): AskSenderId,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                actor_ref.ask(SenderId).send().await.unwrap()
+            }
+        }
+
+        let a = spawn(MyActor);
+        let b = spawn(MyActor);
+        assert_eq!(b.ask(SenderId).send().await?, None);
+        assert_eq!(a.ask(AskSenderId(b.clone())).send().await?, Some(a.id()));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn ask_requests_log_level() -> Result<(), Box<dyn std::error::Error>> {
+        use std::sync::{Arc, Mutex};
+
+        use tracing_subscriber::layer::{Context as LayerContext, SubscriberExt};
+
+        #[derive(Clone, Default)]
+        struct SpanLevels(Arc<Mutex<Vec<tracing::Level>>>);
+
+        impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SpanLevels {
+            fn on_new_span(
+                &self,
+                attrs: &tracing::span::Attributes<'_>,
+                _id: &tracing::span::Id,
+                _ctx: LayerContext<'_, S>,
+            ) {
+                if attrs.metadata().name() == "handle_message" {
+                    self.0.lock().unwrap().push(*attrs.metadata().level());
+                }
+            }
+        }
+
+        struct Quiet;
+
+        impl Actor for Quiet {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            fn log_level() -> tracing::Level {
+                tracing::Level::TRACE
+            }
+        }
+
+        struct Loud;
+
+        impl Actor for Loud {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Ping;
+
+        impl Message<Ping> for Quiet {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Ping, _ctx: Context<'_, Self, Self::Reply>) {}
+        }
+
+        impl Message<Ping> for Loud {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Ping, _ctx: Context<'_, Self, Self::Reply>) {}
+        }
+
+        let levels = SpanLevels::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(levels.clone()));
+
+        let quiet_ref = crate::spawn(Quiet);
+        let loud_ref = crate::spawn(Loud);
+        // Handler spans are only created for messages sent from within a span
+        let _span = tracing::info_span!("sender").entered();
+        quiet_ref.ask(Ping).await?;
+        loud_ref.ask(Ping).await?;
+
+        assert_eq!(
+            *levels.0.lock().unwrap(),
+            [tracing::Level::TRACE, tracing::Level::DEBUG]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_delegated_across_actors() -> Result<(), Box<dyn std::error::Error>> {
+        struct Hop {
+            next: Option<ActorRef<Hop>>,
+        }
+
+        impl Actor for Hop {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Route(Vec<u32>);
+
+        impl Message<Route> for Hop {
+            type Reply = DelegatedReply<Vec<u32>>;
+
+            async fn handle(
+                &mut self,
+                Route(mut hops): Route,
+                mut ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                hops.push(hops.len() as u32);
+                match &self.next {
+                    Some(next) => ctx.delegate_to(next, Route(hops)).await,
+                    None => {
+                        let (delegated, reply_sender) = ctx.reply_sender();
+                        if let Some(tx) = reply_sender {
+                            tx.send(hops);
+                        }
+                        delegated
+                    }
+                }
+            }
+        }
+
+        let third = crate::spawn(Hop { next: None });
+        let second = crate::spawn(Hop {
+            next: Some(third.clone()),
+        });
+        let first = crate::spawn(Hop {
+            next: Some(second.clone()),
+        });
+
+        // The last actor in the chain replies to the original caller
+        assert_eq!(first.ask(Route(vec![])).await?, vec![0, 1, 2]);
+
+        // A broken chain replies with ActorStopped
+        third.stop_gracefully().await?;
+        third.wait_for_stop().await;
+        assert_eq!(first.ask(Route(vec![])).await, Err(SendError::ActorStopped));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_with_default_timeout() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = BoundedMailbox<Self>;
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Sleep(Duration);
+
+        impl Message<Sleep> for MyActor {
+            type Reply = bool;
+
+            async fn handle(
+                &mut self,
+                Sleep(duration): Sleep,
+                ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                let has_deadline = ctx.reply_deadline().is_some();
+                tokio::time::sleep(duration).await;
+                has_deadline
+            }
+
+            fn default_timeout() -> Option<Duration> {
+                Some(Duration::from_millis(50))
+            }
+        }
+
+        let actor_ref = crate::spawn(MyActor);
+
+        assert!(actor_ref.ask(Sleep(Duration::ZERO)).await?);
+        assert_eq!(
+            actor_ref.ask(Sleep(Duration::from_millis(200))).await,
+            Err(SendError::Timeout(None))
+        );
+        assert_eq!(
+            actor_ref
+                .ask(Sleep(Duration::from_millis(200)))
+                .send_returning_handle()
+                .await?
+                .await,
+            Err(SendError::Timeout(None))
+        );
+
+        // A reply timeout on the request takes precedence
+        assert!(
+            actor_ref
+                .ask(Sleep(Duration::from_millis(100)))
+                .reply_timeout(Duration::from_secs(5))
+                .await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_reply_now() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Work {
+            panic: bool,
+        }
+
+        impl Message<Work> for MyActor {
+            type Reply = Result<u32, &'static str>;
+
+            async fn handle(
+                &mut self,
+                Work { panic }: Work,
+                mut ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                ctx.reply_now(Ok(1));
+                ctx.reply_now(Ok(2));
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                if panic {
+                    panic!("trailing work failed");
+                }
+                Err("ignored")
+            }
+        }
+
+        let actor_ref = crate::spawn(MyActor);
+
+        // The reply arrives before the handler finishes
+        let start = Instant::now();
+        assert_eq!(actor_ref.ask(Work { panic: false }).await, Ok(1));
+        assert!(start.elapsed() < Duration::from_millis(200));
+
+        // The ignored error isn't treated as a failed tell
+        actor_ref.flush().await?;
+        assert!(actor_ref.is_alive());
+
+        // A panic after replying doesn't affect the reply
+        assert_eq!(actor_ref.ask(Work { panic: true }).await, Ok(1));
+        actor_ref.wait_for_stop().await;
+        assert!(!actor_ref.is_alive());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_in_scope() -> Result<(), Box<dyn std::error::Error>> {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        struct Downstream {
+            handled: Arc<AtomicUsize>,
+        }
+
+        impl Actor for Downstream {
+            type Mailbox = BoundedMailbox<Self>;
+
+            fn new_mailbox() -> (Self::Mailbox, BoundedMailboxReceiver<Self>) {
+                BoundedMailbox::new(1)
+            }
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Work(Duration);
+
+        impl Message<Work> for Downstream {
+            type Reply = Result<usize, String>;
+
+            async fn handle(
+                &mut self,
+                Work(duration): Work,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                tokio::time::sleep(duration).await;
+                Ok(self.handled.fetch_add(1, Ordering::SeqCst) + 1)
+            }
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Fail;
+
+        impl Message<Fail> for Downstream {
+            type Reply = Result<usize, String>;
+
+            async fn handle(
+                &mut self,
+                _: Fail,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                Err("failed".to_string())
+            }
+        }
+
+        struct Coordinator {
+            busy: ActorRef<Downstream>,
+            idle: ActorRef<Downstream>,
+        }
+
+        impl Actor for Coordinator {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct FanOut {
+            fail: bool,
+        }
+
+        impl Message<FanOut> for Coordinator {
+            type Reply = Result<usize, String>;
+
+            async fn handle(
+                &mut self,
+                FanOut { fail }: FanOut,
+                ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                let scope = ctx.scope();
+                let busy = scope.ask(&self.busy, Work(Duration::ZERO));
+                if fail {
+                    scope
+                        .ask(&self.idle, Fail)
+                        .await
+                        .map_err(|_| "fan out failed".to_string())?;
+                }
+                let idle = scope.ask(&self.idle, Work(Duration::ZERO));
+                let busy = busy.await.map_err(|_| "busy failed".to_string())?;
+                let idle = idle.await.map_err(|_| "idle failed".to_string())?;
+                Ok(busy + idle)
+            }
+        }
+
+        let handled = Arc::new(AtomicUsize::new(0));
+        let busy = crate::spawn(Downstream {
+            handled: handled.clone(),
+        });
+        let idle = crate::spawn(Downstream {
+            handled: Arc::new(AtomicUsize::new(0)),
+        });
+        let coordinator = crate::spawn(Coordinator {
+            busy: busy.clone(),
+            idle,
+        });
+
+        assert_eq!(coordinator.ask(FanOut { fail: false }).await, Ok(2));
+
+        // Keep the busy actor handling one message with another waiting, so the scoped request waits for capacity
+        busy.tell(Work(Duration::from_millis(100))).await?;
+        busy.wait_startup().await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        busy.tell(Work(Duration::ZERO)).await?;
+
+        // Failing early cancels the request still waiting to be sent
+        assert_eq!(
+            coordinator.ask(FanOut { fail: true }).await,
+            Err(SendError::HandlerError("fan out failed".to_string()))
+        );
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        busy.flush().await?;
+        assert_eq!(handled.load(Ordering::SeqCst), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_inherit_deadline() -> Result<(), Box<dyn std::error::Error>> {
+        use std::sync::{Arc, Mutex};
+
+        struct Database;
+
+        impl Actor for Database {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Query(Duration);
+
+        impl Message<Query> for Database {
+            type Reply = bool;
+
+            async fn handle(
+                &mut self,
+                Query(duration): Query,
+                ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                tokio::time::sleep(duration).await;
+                ctx.reply_deadline().is_some()
+            }
+        }
+
+        type Results = Arc<Mutex<Vec<Result<bool, SendError<Query>>>>>;
+
+        struct Api {
+            db: ActorRef<Database>,
+            results: Results,
+        }
+
+        impl Actor for Api {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Request(Duration);
+
+        impl Message<Request> for Api {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                Request(duration): Request,
+                ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                let res = ctx.ask(&self.db, Query(duration)).await;
+                self.results.lock().unwrap().push(res);
+            }
+        }
+
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let db = crate::spawn(Database);
+        let api = crate::spawn(Api {
+            db,
+            results: results.clone(),
+        });
+
+        // Without a deadline, the request waits for the reply
+        api.ask(Request(Duration::from_millis(20))).await?;
+        // The downstream request gives up when the caller does, and sees the inherited deadline
+        assert_eq!(
+            api.ask(Request(Duration::from_millis(200)))
+                .reply_timeout(Duration::from_millis(50))
+                .await,
+            Err(SendError::Timeout(None))
+        );
+        api.ask(Request(Duration::ZERO))
+            .reply_timeout(Duration::from_secs(1))
+            .await?;
+        api.flush().await?;
+
+        let results = results.lock().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], Ok(false));
+        assert_eq!(results[1], Err(SendError::Timeout(None)));
+        assert_eq!(results[2], Ok(true));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_abort_when_abandoned() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor {
+            finished: bool,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            fn abort_abandoned_requests() -> bool {
+                true
+            }
+        }
+
+        struct Slow;
+
+        impl Message<Slow> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Slow, _ctx: Context<'_, Self, Self::Reply>) {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                self.finished = true;
+            }
+        }
+
+        struct Finished;
+
+        impl Message<Finished> for MyActor {
+            type Reply = bool;
+
+            async fn handle(
+                &mut self,
+                _msg: Finished,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.finished
+            }
+        }
+
+        struct Delegated(u32);
+
+        impl Message<Delegated> for MyActor {
+            type Reply = DelegatedReply<u32>;
+
+            async fn handle(
+                &mut self,
+                Delegated(n): Delegated,
+                mut ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                let (delegated, reply_sender) = ctx.reply_sender();
+                if let Some(tx) = reply_sender {
+                    tokio::spawn(async move {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        tx.send(n);
+                    });
+                }
+                delegated
+            }
+        }
+
+        let actor_ref = spawn(MyActor { finished: false });
+
+        // Replies are still received when the caller waits
+        assert!(!actor_ref.ask(Finished).send().await?);
+        assert_eq!(actor_ref.ask(Delegated(3)).send().await?, 3);
+
+        // The slow handler is aborted once the caller stops waiting, rather than holding up the next message
+        let abandoned =
+            tokio::time::timeout(Duration::from_millis(50), actor_ref.ask(Slow).send()).await;
+        assert!(abandoned.is_err());
+        let finished =
+            tokio::time::timeout(Duration::from_secs(1), actor_ref.ask(Finished).send()).await??;
+        assert!(!finished);
+        assert!(actor_ref.is_alive());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_reply_abandoned() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor {
+            abandoned: bool,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Slow;
+
+        impl Message<Slow> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Slow, mut ctx: Context<'_, Self, Self::Reply>) {
+                assert!(!ctx.is_reply_abandoned());
+                tokio::select! {
+                    _ = ctx.reply_abandoned() => {
+                        self.abandoned = ctx.is_reply_abandoned();
+                    }
+                    _ = tokio::time::sleep(Duration::from_secs(10)) => {}
+                }
+            }
+        }
+
+        struct Abandoned;
+
+        impl Message<Abandoned> for MyActor {
+            type Reply = bool;
+
+            async fn handle(
+                &mut self,
+                _msg: Abandoned,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.abandoned
+            }
+        }
+
+        let actor_ref = spawn(MyActor { abandoned: false });
+        let abandoned =
+            tokio::time::timeout(Duration::from_millis(50), actor_ref.ask(Slow).send()).await;
+        assert!(abandoned.is_err());
+        let abandoned =
+            tokio::time::timeout(Duration::from_secs(1), actor_ref.ask(Abandoned).send()).await??;
+        assert!(abandoned);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_borrow_state_across_await() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor {
+            index: tokio::sync::RwLock<Vec<String>>,
+            prefix: String,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Search;
+
+        impl Message<Search> for MyActor {
+            type Reply = usize;
+
+            async fn handle(
+                &mut self,
+                _msg: Search,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                // Both borrows of the actor's state are held across the awaits below
+                let prefix: &str = &self.prefix;
+                let index = self.index.read().await;
+                tokio::task::yield_now().await;
+                index
+                    .iter()
+                    .filter(|entry| entry.starts_with(prefix))
+                    .count()
+            }
+        }
+
+        let actor_ref = spawn(MyActor {
+            index: tokio::sync::RwLock::new(vec![
+                "kameo".to_string(),
+                "kafka".to_string(),
+                "tokio".to_string(),
+            ]),
+            prefix: "ka".to_string(),
+        });
+        assert_eq!(actor_ref.ask(Search).await?, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_requests_fallback_reply_after_panic() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::{error::PanicError, reply::StrandedReply};
+
+        struct MyActor {
+            recover: bool,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            async fn on_panic(
+                &mut self,
+                _actor_ref: WeakActorRef<Self>,
+                err: PanicError,
+            ) -> Result<Option<ActorStopReason>, BoxError> {
+                if self.recover {
+                    Ok(None)
+                } else {
+                    Ok(Some(ActorStopReason::Panicked(err)))
+                }
+            }
+
+            async fn on_panic_reply(
+                &mut self,
+                _actor_ref: WeakActorRef<Self>,
+                _err: PanicError,
+                reply: StrandedReply<Self>,
+            ) -> Result<(), BoxError> {
+                assert!(reply.is::<Lookup>());
+                let reply = reply.send::<Other>(0).unwrap_err();
+                reply.send::<Lookup>(Err("degraded".to_string())).unwrap();
+                Ok(())
+            }
+        }
+
+        struct Lookup;
+
+        impl Message<Lookup> for MyActor {
+            type Reply = Result<u32, String>;
+
+            async fn handle(
+                &mut self,
+                _msg: Lookup,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                panic!("lookup failed")
+            }
+        }
+
+        struct Other;
+
+        impl Message<Other> for MyActor {
+            type Reply = u32;
+
+            async fn handle(
+                &mut self,
+                _msg: Other,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                1
+            }
+        }
+
+        // The actor continues, so the caller receives the fallback
+        let actor_ref = spawn(MyActor { recover: true });
+        assert!(matches!(
+            actor_ref.ask(Lookup).await,
+            Err(SendError::HandlerError(err)) if err == "degraded"
+        ));
+        assert_eq!(actor_ref.ask(Other).await?, 1);
+
+        // The actor stops, so the caller receives the reason
+        let actor_ref = spawn(MyActor { recover: false });
+        assert!(matches!(
+            actor_ref.ask(Lookup).await,
+            Err(SendError::ActorDied(ActorStopReason::Panicked(_)))
+        ));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,172 @@
+use std::{
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{Mutex, PoisonError},
+    task::{Context, Poll},
+};
+
+use futures::FutureExt;
+use tokio::{sync::oneshot, task::JoinSet};
+
+use crate::{
+    actor::ActorRef,
+    error::SendError,
+    message::Message,
+    request::{AskRequest, LocalAskRequest, MessageSend, WithoutRequestTimeout},
+    Reply,
+};
+
+/// A scope for sending requests to other actors concurrently from within a message handler.
+///
+/// A scope is created with [`Context::scope`](crate::message::Context::scope). Each request added with
+/// [`ask`](RequestScope::ask) is sent in the background straight away, and its reply is received through the
+/// returned [`ScopedReply`]. The replies borrow the scope, so they can't outlive it.
+///
+/// When the scope is dropped, such as when the handler returns early with `?` or panics, every request which
+/// hasn't completed is cancelled, so no requests are left running after the handler finishes. To wait for the
+/// remaining requests instead, use [`join`](RequestScope::join).
+///
+/// # Cancellation
+///
+/// Cancelling a request drops it wherever it is. A message still waiting for capacity in a bounded mailbox is
+/// never delivered. A message already in the other actor's mailbox is still handled, but its reply is discarded.
+///
+/// # Example
+///
+/// ```
+/// use kameo::actor::ActorRef;
+/// use kameo::message::{Context, Message};
+///
+/// # #[derive(kameo::Actor)]
+/// # struct Prices;
+/// #
+/// # #[derive(kameo::Actor)]
+/// # struct Stock;
+/// #
+/// # struct GetPrice;
+/// # impl Message<GetPrice> for Prices {
+/// #     type Reply = Result<u32, std::io::Error>;
+/// #     async fn handle(&mut self, _: GetPrice, _: Context<'_, Self, Self::Reply>) -> Self::Reply { Ok(10) }
+/// # }
+/// #
+/// # struct GetStock;
+/// # impl Message<GetStock> for Stock {
+/// #     type Reply = Result<u32, std::io::Error>;
+/// #     async fn handle(&mut self, _: GetStock, _: Context<'_, Self, Self::Reply>) -> Self::Reply { Ok(3) }
+/// # }
+/// #
+/// #[derive(kameo::Actor)]
+/// struct Checkout {
+///     prices: ActorRef<Prices>,
+///     stock: ActorRef<Stock>,
+/// }
+///
+/// struct Quote;
+///
+/// impl Message<Quote> for Checkout {
+///     type Reply = Result<u32, Box<dyn std::error::Error + Send + Sync>>;
+///
+///     async fn handle(&mut self, _: Quote, ctx: Context<'_, Self, Self::Reply>) -> Self::Reply {
+///         let scope = ctx.scope();
+///         let price = scope.ask(&self.prices, GetPrice);
+///         let stock = scope.ask(&self.stock, GetStock);
+///
+///         // Both requests are already in flight. If the price request fails, the stock request is
+///         // cancelled when the scope is dropped
+///         let price = price.await?;
+///         let stock = stock.await?;
+///         Ok(price * stock)
+///     }
+/// }
+/// ```
+pub struct RequestScope {
+    tasks: Mutex<JoinSet<()>>,
+}
+
+impl RequestScope {
+    pub(crate) fn new() -> Self {
+        RequestScope {
+            tasks: Mutex::new(JoinSet::new()),
+        }
+    }
+
+    /// Sends a message to an actor in the background, returning a [`ScopedReply`] which resolves to its reply.
+    ///
+    /// The request is cancelled if the scope is dropped before it completes.
+    pub fn ask<A, M>(&self, actor_ref: &ActorRef<A>, msg: M) -> ScopedReply<'_, M, A::Reply>
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        for<'a> AskRequest<
+            LocalAskRequest<'a, A, A::Mailbox>,
+            A::Mailbox,
+            M,
+            WithoutRequestTimeout,
+            WithoutRequestTimeout,
+        >: MessageSend<
+            Ok = <A::Reply as Reply>::Ok,
+            Error = SendError<M, <A::Reply as Reply>::Error>,
+        >,
+    {
+        let (tx, rx) = oneshot::channel();
+        let actor_ref = actor_ref.clone();
+        self.tasks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .spawn(async move {
+                let _ = tx.send(actor_ref.ask(msg).send().await);
+            });
+
+        ScopedReply {
+            rx,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Waits for every request in the scope to complete.
+    ///
+    /// Replies which weren't awaited are discarded.
+    pub async fn join(self) {
+        let mut tasks = self
+            .tasks
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        while tasks.join_next().await.is_some() {}
+    }
+}
+
+impl fmt::Debug for RequestScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+        f.debug_struct("RequestScope")
+            .field("tasks", &tasks.len())
+            .finish()
+    }
+}
+
+/// The reply to a request sent with [`RequestScope::ask`].
+#[must_use = "the reply is discarded unless the future is awaited"]
+pub struct ScopedReply<'s, M, R: Reply> {
+    #[allow(clippy::type_complexity)]
+    rx: oneshot::Receiver<Result<R::Ok, SendError<M, R::Error>>>,
+    phantom: PhantomData<&'s RequestScope>,
+}
+
+impl<M, R: Reply> Future for ScopedReply<'_, M, R> {
+    type Output = Result<R::Ok, SendError<M, R::Error>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // The request only stops without replying if its task was aborted, such as by the runtime shutting down
+        self.rx
+            .poll_unpin(cx)
+            .map(|res| res.unwrap_or(Err(SendError::ActorStopped)))
+    }
+}
+
+impl<M, R: Reply> fmt::Debug for ScopedReply<'_, M, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopedReply").finish_non_exhaustive()
+    }
+}
@@ -1,5 +1,10 @@
 use core::panic;
-use std::{future::IntoFuture, marker::PhantomData, time::Duration};
+use std::{
+    future::IntoFuture,
+    marker::PhantomData,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
 
 use futures::{future::BoxFuture, FutureExt};
 
@@ -8,8 +13,12 @@ use crate::remote;
 
 use crate::{
     actor, error,
-    mailbox::{bounded::BoundedMailbox, unbounded::UnboundedMailbox, Mailbox, Signal},
-    message::Message,
+    mailbox::{
+        bounded::BoundedMailbox, fair::FairMailbox, lifo::LifoMailbox, unbounded::UnboundedMailbox,
+        Mailbox, Signal,
+    },
+    message::{Extensions, Message, MessageMeta, Redelivery},
+    reply::{BoxReplySender, ReplyTransport},
     Actor, Reply,
 };
 
@@ -38,6 +47,8 @@ where
 {
     mailbox: &'a Mb,
     signal: Signal<A>,
+    closed: &'a AtomicBool,
+    too_large: bool,
 }
 
 /// A request to a remote actor.
@@ -66,6 +77,8 @@ where
         A: Message<M>,
         M: Send + 'static,
     {
+        let too_large = crate::message::is_too_large::<A, M>(&msg);
+
         TellRequest {
             location: LocalTellRequest {
                 mailbox: actor_ref.mailbox(),
@@ -74,7 +87,12 @@ where
                     actor_ref: actor_ref.clone(),
                     reply: None,
                     sent_within_actor: actor_ref.is_current(),
+                    extensions: Extensions::new(),
+                    meta: MessageMeta::for_send(),
+                    sent_at: Instant::now(),
                 },
+                closed: &actor_ref.info.closed,
+                too_large,
             },
             timeout: WithoutRequestTimeout,
             #[cfg(debug_assertions)]
@@ -88,6 +106,104 @@ impl<A, M, T> TellRequest<LocalTellRequest<'_, A, A::Mailbox>, A::Mailbox, M, T>
 where
     A: Actor,
 {
+    /// Attaches an extension to the message, accessible to the handler through [`Context::extensions`].
+    ///
+    /// [`Context::extensions`]: crate::message::Context::extensions
+    pub fn extension<V: Send + Sync + 'static>(mut self, val: V) -> Self {
+        if let Signal::Message { extensions, .. } = &mut self.location.signal {
+            extensions.insert(val);
+        }
+        self
+    }
+
+    /// Sends the reply to the message through a [`ReplyTransport`], rather than discarding it.
+    ///
+    /// The request still completes once the message is in the mailbox, without waiting for the reply. The reply,
+    /// or the error which prevented it, is sent through the transport once the message is handled, so handler errors
+    /// go to the transport rather than to [`Actor::on_async_error`]. If the request fails to send, the error is
+    /// returned here instead, and nothing is sent through the transport.
+    ///
+    /// See [`ReplyTransport`] for an example.
+    ///
+    /// [`Actor::on_async_error`]: crate::Actor::on_async_error
+    pub fn reply_to<R>(mut self, transport: R) -> Self
+    where
+        A: Message<M>,
+        M: Send + 'static,
+        R: ReplyTransport<
+            Result<<A::Reply as Reply>::Ok, error::SendError<M, <A::Reply as Reply>::Error>>,
+        >,
+    {
+        if let Signal::Message { reply, .. } = &mut self.location.signal {
+            *reply = Some(BoxReplySender::typed(transport));
+        }
+        self
+    }
+
+    /// Redelivers the message if its handler panics, up to `max_redeliveries` times.
+    ///
+    /// A copy of the message is kept while it is being handled. If the handler panics and the actor continues running,
+    /// because [`Actor::on_panic`] returned `Ok(None)`, the copy is handled again before any other message in the mailbox.
+    /// If the actor stops instead, the message is not redelivered.
+    ///
+    /// Once the message has been redelivered `max_redeliveries` times and the handler panics again, the message is
+    /// considered poisoned and is dropped, logging an error with `tracing`. This prevents a message which always panics
+    /// from being handled forever.
+    ///
+    /// Extensions attached to the original request are not included when the message is redelivered.
+    ///
+    /// [`Actor::on_panic`]: crate::Actor::on_panic
+    pub fn redeliver(mut self, max_redeliveries: u32) -> Self
+    where
+        A: Message<M>,
+        M: Clone + Send + 'static,
+    {
+        self.location.signal = match self.location.signal {
+            Signal::Message {
+                message,
+                actor_ref,
+                reply,
+                sent_within_actor,
+                extensions,
+                mut meta,
+                sent_at,
+            } => {
+                let message: Box<M> = message.as_any().downcast().unwrap();
+                meta.redelivery = Some(Box::new(Redelivery::new(&*message, max_redeliveries)));
+                Signal::Message {
+                    message,
+                    actor_ref,
+                    reply,
+                    sent_within_actor,
+                    extensions,
+                    meta,
+                    sent_at,
+                }
+            }
+            signal => signal,
+        };
+        self
+    }
+
+    /// Rejects the message with [`SendError::Closed`](error::SendError::Closed) if the actor has been closed, or
+    /// [`SendError::TooLarge`](error::SendError::TooLarge) if it's larger than the actor accepts.
+    fn check_accepted<E>(self) -> Result<Self, error::SendError<M, E>>
+    where
+        M: 'static,
+    {
+        if self.location.closed.load(Ordering::Acquire) {
+            return Err(error::SendError::Closed(
+                self.location.signal.downcast_message().unwrap(),
+            ));
+        }
+        if self.location.too_large {
+            return Err(error::SendError::TooLarge(
+                self.location.signal.downcast_message().unwrap(),
+            ));
+        }
+        Ok(self)
+    }
+
     #[cfg(all(debug_assertions, feature = "tracing"))]
     fn warn_deadlock(&self, msg: &'static str) {
         use tracing::warn;
@@ -197,6 +313,16 @@ where
 }
 
 macro_rules! impl_message_trait {
+    (local, $($async:ident)? => $trait:ident :: $method:ident, [$mailbox:ident $(, $rest:ident)*], $timeout:ident, |$req:ident| $($body:tt)*) => {
+        impl_message_trait!(local, $($async)? => $trait::$method, $mailbox, $timeout, |$req| $($body)*);
+        impl_message_trait!(local, $($async)? => $trait::$method, [$($rest),*], $timeout, |$req| $($body)*);
+    };
+    (local, $($async:ident)? => $trait:ident :: $method:ident, [], $timeout:ident, |$req:ident| $($body:tt)*) => {};
+    (remote, $($async:ident)? => $trait:ident :: $method:ident, [$mailbox:ident $(, $rest:ident)*], $timeout:ident, |$req:ident| $($body:tt)*) => {
+        impl_message_trait!(remote, $($async)? => $trait::$method, $mailbox, $timeout, |$req| $($body)*);
+        impl_message_trait!(remote, $($async)? => $trait::$method, [$($rest),*], $timeout, |$req| $($body)*);
+    };
+    (remote, $($async:ident)? => $trait:ident :: $method:ident, [], $timeout:ident, |$req:ident| $($body:tt)*) => {};
     (local, $($async:ident)? => $trait:ident :: $method:ident, $timeout:ident, |$req:ident| $($body:tt)*) => {
         impl<'a, A, M> $trait
             for TellRequest<
@@ -214,7 +340,7 @@ macro_rules! impl_message_trait {
 
             #[inline]
             $($async)? fn $method(self) -> Result<Self::Ok, Self::Error> {
-                let $req = self;
+                let $req = self.check_accepted()?;
                 $($body)*
             }
         }
@@ -236,7 +362,7 @@ macro_rules! impl_message_trait {
 
             #[inline]
             $($async)? fn $method(self) -> Result<Self::Ok, Self::Error> {
-                let $req = self;
+                let $req = self.check_accepted()?;
                 $($body)*
             }
         }
@@ -352,7 +478,7 @@ impl_message_trait!(
 impl_message_trait!(
     local,
     async => MessageSend::send,
-    UnboundedMailbox,
+    [UnboundedMailbox, LifoMailbox, FairMailbox],
     MaybeRequestTimeout,
     |req| {
         match req.timeout {
@@ -380,7 +506,7 @@ impl_message_trait!(
 impl_message_trait!(
     local,
     => MessageSendSync::send_sync,
-    UnboundedMailbox,
+    [UnboundedMailbox, LifoMailbox, FairMailbox],
     WithoutRequestTimeout,
     |req| {
         req.location.mailbox.0.send(req.location.signal)?;
@@ -418,7 +544,7 @@ impl_message_trait!(local, async => TryMessageSend::try_send, BoundedMailbox, Ma
     }
 });
 
-impl_message_trait!(local, async => TryMessageSend::try_send, UnboundedMailbox, MaybeRequestTimeout, |req| {
+impl_message_trait!(local, async => TryMessageSend::try_send, [UnboundedMailbox, LifoMailbox, FairMailbox], MaybeRequestTimeout, |req| {
     match req.timeout {
         MaybeRequestTimeout::NoTimeout => {
             TellRequest {
@@ -512,18 +638,21 @@ where
 mod tests {
     use std::time::Duration;
 
+    use futures::future;
+
     use crate::{
-        error::SendError,
+        actor::{PreparedActor, WeakActorRef},
+        error::{ActorStopReason, BoxError, PanicError, SendError},
         mailbox::{
             bounded::{BoundedMailbox, BoundedMailboxReceiver},
             unbounded::UnboundedMailbox,
         },
-        message::{Context, Message},
+        message::{Ambient, BoxDebug, Context, Message},
         request::{
             BlockingMessageSend, MessageSend, MessageSendSync, TryBlockingMessageSend,
             TryMessageSend, TryMessageSendSync,
         },
-        spawn, Actor,
+        spawn, Actor, Reply,
     };
 
     #[tokio::test]
@@ -763,6 +892,288 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn bounded_try_send_many() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = BoundedMailbox<Self>;
+
+            fn new_mailbox() -> (BoundedMailbox<Self>, BoundedMailboxReceiver<Self>) {
+                BoundedMailbox::new(3)
+            }
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        struct Msg(u32);
+
+        impl Message<Msg> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                _msg: Msg,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+        }
+
+        let actor_ref = spawn(MyActor);
+        actor_ref.wait_startup().await;
+        assert_eq!(actor_ref.try_send_many([Msg(0), Msg(1)]), Ok(()));
+        assert_eq!(
+            actor_ref.try_send_many([Msg(2), Msg(3), Msg(4)]),
+            Err(SendError::MailboxFull(vec![Msg(3), Msg(4)]))
+        );
+        actor_ref.kill();
+        actor_ref.wait_for_stop().await;
+        assert_eq!(
+            actor_ref.try_send_many([Msg(5), Msg(6)]),
+            Err(SendError::ActorNotRunning(vec![Msg(5), Msg(6)]))
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_request_extensions() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Tag(&'static str);
+
+        struct Record;
+
+        impl Message<Record> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                _msg: Record,
+                mut ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                let tag = ctx.extensions_mut().remove::<Tag>().map(|tag| tag.0);
+                ctx.spawn_task(|_| future::pending());
+                let actor_extensions = ctx.actor_extensions_mut();
+                if !actor_extensions.contains::<Vec<Option<&'static str>>>() {
+                    actor_extensions.insert(Vec::<Option<&'static str>>::new());
+                }
+                actor_extensions
+                    .get_mut::<Vec<Option<&'static str>>>()
+                    .unwrap()
+                    .push(tag);
+            }
+        }
+
+        struct Tags;
+
+        impl Message<Tags> for MyActor {
+            type Reply = Vec<Option<&'static str>>;
+
+            async fn handle(
+                &mut self,
+                _msg: Tags,
+                ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                // Data attached by the runtime isn't visible in either set of extensions
+                assert!(ctx.extensions().is_empty());
+                assert_eq!(ctx.ambient::<Tag>().map(|tag| tag.0), Some("c"));
+                assert!(ctx.reply_deadline().is_some());
+                assert_eq!(ctx.actor_extensions().len(), 1);
+                ctx.actor_extensions()
+                    .get::<Vec<Option<&'static str>>>()
+                    .cloned()
+                    .unwrap_or_default()
+            }
+        }
+
+        let actor_ref = spawn(MyActor);
+        actor_ref.tell(Record).extension(Tag("a")).send().await?;
+        actor_ref.tell(Record).send().await?;
+        actor_ref.tell(Record).extension(Tag("b")).send_sync()?;
+        let tags = Ambient::new()
+            .with(Tag("c"))
+            .scope(async {
+                actor_ref
+                    .ask(Tags)
+                    .reply_timeout(Duration::from_secs(1))
+                    .send()
+                    .await
+            })
+            .await?;
+        assert_eq!(tags, vec![Some("a"), None, Some("b")]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_to_recipients() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Msg;
+
+        impl Message<Msg> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                _msg: Msg,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+            }
+        }
+
+        let actor_ref = spawn(MyActor);
+        let recipient = actor_ref.recipient::<Msg>();
+        let weak_recipient = actor_ref.downgrade_to_recipient::<Msg>();
+        assert_eq!(recipient.id(), actor_ref.id());
+        assert_eq!(weak_recipient, recipient.downgrade());
+        recipient.tell(Msg).await?;
+        assert_eq!(weak_recipient.upgrade(), Some(recipient.clone()));
+
+        actor_ref.stop_gracefully().await?;
+        actor_ref.wait_for_stop().await;
+        drop(actor_ref);
+        drop(recipient);
+        assert_eq!(weak_recipient.upgrade(), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_request_errors_routed_to_on_async_error() -> Result<(), Box<dyn std::error::Error>>
+    {
+        struct MyActor {
+            errors: Vec<String>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            async fn on_async_error(
+                &mut self,
+                _actor_ref: WeakActorRef<Self>,
+                err: PanicError,
+            ) -> Result<Option<ActorStopReason>, BoxError> {
+                self.errors.push(err.to_string());
+                Ok(None)
+            }
+        }
+
+        struct Fail;
+
+        impl Message<Fail> for MyActor {
+            type Reply = Result<(), &'static str>;
+
+            async fn handle(
+                &mut self,
+                _msg: Fail,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                Err("failed")
+            }
+        }
+
+        struct Errors;
+
+        impl Message<Errors> for MyActor {
+            type Reply = usize;
+
+            async fn handle(
+                &mut self,
+                _msg: Errors,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.errors.len()
+            }
+        }
+
+        let actor_ref = spawn(MyActor { errors: vec![] });
+        actor_ref.tell(Fail).send().await?;
+        actor_ref.tell(Fail).send().await?;
+        assert_eq!(actor_ref.ask(Errors).send().await?, 2);
+        assert!(actor_ref.is_alive());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_redelivered_after_panic() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Default)]
+        struct MyActor {
+            attempts: u32,
+            handled: u32,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            async fn on_panic(
+                &mut self,
+                _actor_ref: WeakActorRef<Self>,
+                _err: PanicError,
+            ) -> Result<Option<ActorStopReason>, BoxError> {
+                Ok(None)
+            }
+        }
+
+        /// Panics until it has been attempted the given number of times.
+        #[derive(Clone)]
+        struct Flaky(u32);
+
+        impl Message<Flaky> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                Flaky(succeed_on): Flaky,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.attempts += 1;
+                if self.attempts < succeed_on {
+                    panic!("flaky");
+                }
+                self.handled += 1;
+            }
+        }
+
+        struct Counts;
+
+        impl Message<Counts> for MyActor {
+            type Reply = (u32, u32);
+
+            async fn handle(
+                &mut self,
+                _msg: Counts,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                let counts = (self.attempts, self.handled);
+                self.attempts = 0;
+                counts
+            }
+        }
+
+        let actor_ref = spawn(MyActor::default());
+
+        actor_ref.tell(Flaky(3)).redeliver(5).send().await?;
+        assert_eq!(actor_ref.ask(Counts).send().await?, (3, 1));
+
+        // The message is dropped after its redeliveries are exhausted
+        actor_ref.tell(Flaky(10)).redeliver(2).send().await?;
+        assert_eq!(actor_ref.ask(Counts).send().await?, (3, 1));
+        assert!(actor_ref.is_alive());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn bounded_tell_requests_mailbox_timeout() -> Result<(), Box<dyn std::error::Error>> {
         struct MyActor;
@@ -822,4 +1233,856 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn tell_requests_flush() -> Result<(), Box<dyn std::error::Error>> {
+        use std::sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        };
+
+        struct MyActor {
+            count: Arc<AtomicU32>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Inc;
+
+        impl Message<Inc> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Inc, _ctx: Context<'_, Self, Self::Reply>) {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                self.count.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let count = Arc::new(AtomicU32::new(0));
+        let actor_ref = spawn(MyActor {
+            count: count.clone(),
+        });
+        for _ in 0..3 {
+            actor_ref.tell(Inc).send().await?;
+        }
+        actor_ref.flush().await?;
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+
+        actor_ref.stop_gracefully().await?;
+        actor_ref.wait_for_stop().await;
+        assert!(matches!(
+            actor_ref.flush().await,
+            Err(SendError::ActorNotRunning(()))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_too_large() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::message::MessageSize;
+
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = BoundedMailbox<Self>;
+
+            fn max_message_bytes() -> Option<usize> {
+                Some(8)
+            }
+        }
+
+        struct Payload(Vec<u8>);
+
+        impl Message<Payload> for MyActor {
+            type Reply = ();
+
+            fn message_size(msg: &Payload) -> usize {
+                msg.0.message_size()
+            }
+
+            async fn handle(&mut self, _msg: Payload, _ctx: Context<'_, Self, Self::Reply>) {}
+        }
+
+        let actor_ref = spawn(MyActor);
+        actor_ref.tell(Payload(vec![0; 4])).send().await?;
+        actor_ref.tell(Payload(vec![0; 4])).try_send().await?;
+
+        let res = actor_ref.tell(Payload(vec![0; 16])).send().await;
+        assert!(matches!(res, Err(SendError::TooLarge(Payload(bytes))) if bytes.len() == 16));
+        let res = actor_ref.tell(Payload(vec![0; 16])).try_send().await;
+        assert!(matches!(res, Err(SendError::TooLarge(_))));
+        let res = actor_ref.try_send_many([Payload(vec![0; 4]), Payload(vec![0; 16])]);
+        assert!(matches!(res, Err(SendError::TooLarge(remaining)) if remaining.len() == 1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_spawning_tasks() -> Result<(), Box<dyn std::error::Error>> {
+        use std::sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        };
+
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct SpawnTask(Arc<AtomicBool>);
+
+        impl Message<SpawnTask> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, msg: SpawnTask, mut ctx: Context<'_, Self, Self::Reply>) {
+                struct SetOnDrop(Arc<AtomicBool>);
+
+                impl Drop for SetOnDrop {
+                    fn drop(&mut self) {
+                        self.0.store(true, Ordering::SeqCst);
+                    }
+                }
+
+                let guard = SetOnDrop(msg.0);
+                ctx.spawn_task(|_| async move {
+                    let _guard = guard;
+                    futures::future::pending::<()>().await;
+                });
+                assert!(ctx.actor_extensions().is_empty());
+            }
+        }
+
+        struct SpawnPanickingTask;
+
+        impl Message<SpawnPanickingTask> for MyActor {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                _msg: SpawnPanickingTask,
+                mut ctx: Context<'_, Self, Self::Reply>,
+            ) {
+                ctx.spawn_task(|_| async { panic!("detached") });
+                ctx.spawn_linked_task(|_| async {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    panic!("linked")
+                });
+            }
+        }
+
+        let aborted = Arc::new(AtomicBool::new(false));
+        let actor_ref = spawn(MyActor);
+        actor_ref.tell(SpawnTask(aborted.clone())).send().await?;
+        actor_ref.flush().await?;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!aborted.load(Ordering::SeqCst));
+        actor_ref.stop_gracefully().await?;
+        actor_ref.wait_for_stop().await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(aborted.load(Ordering::SeqCst));
+
+        let actor_ref = spawn(MyActor);
+        actor_ref.tell(SpawnPanickingTask).send().await?;
+        tokio::time::timeout(Duration::from_secs(1), actor_ref.wait_for_stop()).await?;
+        assert!(!actor_ref.is_alive());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_try_spawn_link() -> Result<(), Box<dyn std::error::Error>> {
+        struct Parent;
+
+        impl Actor for Parent {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Child(u32);
+
+        impl Actor for Child {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        let parent_ref = crate::spawn(Parent);
+        let child_ref = parent_ref.try_spawn_link(Child(1)).await?;
+        assert!(child_ref.is_alive());
+
+        parent_ref.kill();
+        parent_ref.wait_for_stop().await;
+
+        // The linked child is notified that its parent was killed
+        child_ref.wait_for_stop().await;
+        assert!(!child_ref.is_alive());
+
+        // A child is never spawned for a parent which already stopped
+        match parent_ref.try_spawn_link(Child(2)).await {
+            Err(SendError::ActorNotRunning(Child(n))) => assert_eq!(n, 2),
+            _ => panic!("expected the child to be returned"),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_after_close() -> Result<(), Box<dyn std::error::Error>> {
+        use std::sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        };
+
+        struct MyActor {
+            handled: Arc<AtomicU32>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = BoundedMailbox<Self>;
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Work;
+
+        impl Message<Work> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Work, _ctx: Context<'_, Self, Self::Reply>) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                self.handled.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let handled = Arc::new(AtomicU32::new(0));
+        let actor_ref = crate::spawn(MyActor {
+            handled: handled.clone(),
+        });
+        for _ in 0..3 {
+            actor_ref.tell(Work).await?;
+        }
+
+        actor_ref.close().await?;
+        assert!(actor_ref.is_closed());
+        assert_eq!(actor_ref.tell(Work).await, Err(SendError::Closed(Work)));
+        assert_eq!(actor_ref.ask(Work).await, Err(SendError::Closed(Work)));
+        assert_eq!(
+            actor_ref.try_send_many([Work, Work]),
+            Err(SendError::Closed(vec![Work, Work]))
+        );
+
+        // Messages enqueued before closing are still handled
+        actor_ref.wait_for_stop().await;
+        assert_eq!(handled.load(Ordering::SeqCst), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_to_aborted_actor() -> Result<(), Box<dyn std::error::Error>> {
+        use tokio::sync::oneshot;
+
+        struct MyActor {
+            stopped_tx: Option<oneshot::Sender<ActorStopReason>>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            async fn on_stop(
+                &mut self,
+                _actor_ref: WeakActorRef<Self>,
+                reason: ActorStopReason,
+            ) -> Result<(), BoxError> {
+                let _ = self.stopped_tx.take().unwrap().send(reason);
+                Ok(())
+            }
+        }
+
+        struct Msg;
+
+        impl Message<Msg> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Msg, _ctx: Context<'_, Self, Self::Reply>) {}
+        }
+
+        let (stopped_tx, stopped_rx) = oneshot::channel();
+        let prepared_actor = PreparedActor::new();
+        let actor_ref = prepared_actor.actor_ref().clone();
+        let handle = prepared_actor.spawn(MyActor {
+            stopped_tx: Some(stopped_tx),
+        });
+        actor_ref.tell(Msg).await?;
+
+        // Aborting the task kills the actor, rather than dropping it without calling `on_stop`
+        handle.abort();
+        assert!(matches!(handle.await, Err(err) if err.is_cancelled()));
+        assert!(matches!(stopped_rx.await?, ActorStopReason::Killed));
+        actor_ref.wait_for_stop().await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_record_history() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            fn record_history() -> usize {
+                3
+            }
+        }
+
+        struct Ping;
+        struct Crash;
+
+        impl Message<Ping> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, _: Ping, _ctx: Context<'_, Self, Self::Reply>) {}
+        }
+
+        impl Message<Crash> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, _: Crash, _ctx: Context<'_, Self, Self::Reply>) {
+                panic!("crash");
+            }
+        }
+
+        let prepared_actor = PreparedActor::new();
+        let actor_ref = prepared_actor.actor_ref().clone();
+        for _ in 0..5 {
+            actor_ref.tell(Ping).await?;
+        }
+        actor_ref.tell(Crash).await?;
+
+        let (_, reason) = prepared_actor.spawn(MyActor).await?;
+        let ActorStopReason::Panicked(err) = reason else {
+            panic!("expected the actor to panic");
+        };
+        // Only the last 3 messages are kept, ending with the message which panicked
+        let names: Vec<_> = err
+            .message_history()
+            .iter()
+            .map(|record| record.name)
+            .collect();
+        assert_eq!(names.len(), 3);
+        assert!(names[0].ends_with("Ping"));
+        assert!(names[1].ends_with("Ping"));
+        assert!(names[2].ends_with("Crash"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_validated() -> Result<(), Box<dyn std::error::Error>> {
+        struct MyActor {
+            handled: Vec<u32>,
+            errors: Vec<String>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            async fn on_async_error(
+                &mut self,
+                _actor_ref: WeakActorRef<Self>,
+                err: PanicError,
+            ) -> Result<Option<ActorStopReason>, BoxError> {
+                self.errors.push(err.to_string());
+                Ok(None)
+            }
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Even(u32);
+
+        impl Message<Even> for MyActor {
+            type Reply = Result<(), String>;
+
+            fn validate(&self, Even(n): &Even) -> Result<(), String> {
+                if n % 2 != 0 {
+                    return Err(format!("{n} is odd"));
+                }
+                Ok(())
+            }
+
+            async fn handle(
+                &mut self,
+                Even(n): Even,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.handled.push(n);
+                Ok(())
+            }
+        }
+
+        // Reply error types don't need to implement `Debug` to be rejected by validation
+        struct NotPositive;
+
+        struct Checked(Result<u32, NotPositive>);
+
+        impl Reply for Checked {
+            type Ok = u32;
+            type Error = NotPositive;
+            type Value = Self;
+
+            fn to_result(self) -> Result<u32, NotPositive> {
+                self.0
+            }
+
+            fn into_boxed_err(self) -> Option<BoxDebug> {
+                None
+            }
+
+            fn into_value(self) -> Self {
+                self
+            }
+        }
+
+        struct Positive(i32);
+
+        impl Message<Positive> for MyActor {
+            type Reply = Checked;
+
+            fn validate(&self, Positive(n): &Positive) -> Result<(), NotPositive> {
+                if *n <= 0 {
+                    return Err(NotPositive);
+                }
+                Ok(())
+            }
+
+            async fn handle(
+                &mut self,
+                Positive(n): Positive,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                Checked(Ok(n as u32))
+            }
+        }
+
+        struct Report;
+
+        impl Message<Report> for MyActor {
+            type Reply = (Vec<u32>, Vec<String>);
+
+            async fn handle(
+                &mut self,
+                _msg: Report,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                (self.handled.clone(), self.errors.clone())
+            }
+        }
+
+        let actor_ref = spawn(MyActor {
+            handled: vec![],
+            errors: vec![],
+        });
+
+        actor_ref.tell(Even(2)).send().await?;
+        actor_ref.tell(Even(3)).send().await?;
+        assert_eq!(
+            actor_ref.ask(Even(5)).send().await,
+            Err(SendError::HandlerError("5 is odd".to_string()))
+        );
+
+        assert!(actor_ref.tell(Positive(-1)).send().await.is_ok());
+
+        let (handled, errors) = actor_ref.ask(Report).send().await?;
+        assert_eq!(handled, vec![2]);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("3 is odd"));
+        assert!(errors[1].contains("NotPositive"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_yield_if_should_stop() -> Result<(), Box<dyn std::error::Error>> {
+        use std::sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        };
+
+        struct MyActor {
+            iterations: Arc<AtomicU32>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Spin;
+
+        impl Message<Spin> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Spin, ctx: Context<'_, Self, Self::Reply>) {
+                for _ in 0..1_000_000 {
+                    self.iterations.fetch_add(1, Ordering::SeqCst);
+                    if ctx.yield_if_should_stop().await {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let iterations = Arc::new(AtomicU32::new(0));
+        let actor_ref = spawn(MyActor {
+            iterations: iterations.clone(),
+        });
+        actor_ref.tell(Spin).send().await?;
+        actor_ref.stop_gracefully().await?;
+        actor_ref.wait_for_stop().await;
+
+        // The handler finished early rather than running every iteration
+        assert!(iterations.load(Ordering::SeqCst) < 1_000_000);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn tell_requests_log_unprocessed_messages() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{
+            fmt,
+            sync::{Arc, Mutex},
+        };
+
+        use tracing_subscriber::layer::{Context as LayerContext, SubscriberExt};
+
+        #[derive(Clone, Default)]
+        struct UnprocessedCounts(Arc<Mutex<Vec<u64>>>);
+
+        impl tracing::field::Visit for &UnprocessedCounts {
+            fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+                if field.name() == "count" {
+                    self.0.lock().unwrap().push(value);
+                }
+            }
+
+            fn record_debug(&mut self, _field: &tracing::field::Field, _value: &dyn fmt::Debug) {}
+        }
+
+        impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for UnprocessedCounts {
+            fn on_event(&self, event: &tracing::Event<'_>, _ctx: LayerContext<'_, S>) {
+                if *event.metadata().level() == tracing::Level::WARN {
+                    event.record(&mut &*self);
+                }
+            }
+        }
+
+        struct MyActor;
+
+        impl Actor for MyActor {
+            type Mailbox = UnboundedMailbox<Self>;
+
+            fn log_unprocessed_messages() -> bool {
+                true
+            }
+        }
+
+        struct Sleep;
+
+        impl Message<Sleep> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Sleep, _ctx: Context<'_, Self, Self::Reply>) {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+        }
+
+        let counts = UnprocessedCounts::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(counts.clone()));
+
+        let prepared_actor = PreparedActor::new();
+        let actor_ref = prepared_actor.actor_ref().clone();
+        let handle = prepared_actor.spawn(MyActor);
+        for _ in 0..4 {
+            actor_ref.tell(Sleep).send().await?;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        actor_ref.kill();
+        handle.await?;
+
+        // The first message was being handled when the actor was killed
+        assert_eq!(*counts.0.lock().unwrap(), [3]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_preserve_per_sender_order() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::actor::{Sequence, SequenceCheck, SequenceTracker, SequencedSender};
+
+        const PRODUCERS: u64 = 4;
+        const MESSAGES: u64 = 200;
+
+        #[derive(Default)]
+        struct MyActor {
+            received: Vec<(u64, u64)>,
+            tracker: SequenceTracker,
+            violations: Vec<SequenceCheck>,
+        }
+
+        impl Actor for MyActor {
+            type Mailbox = BoundedMailbox<Self>;
+        }
+
+        struct Msg {
+            producer: u64,
+            n: u64,
+        }
+
+        impl Message<Msg> for MyActor {
+            type Reply = ();
+
+            async fn handle(&mut self, msg: Msg, ctx: Context<'_, Self, Self::Reply>) {
+                self.received.push((msg.producer, msg.n));
+                if let Some(seq) = ctx.extensions().get::<Sequence>() {
+                    let check = self.tracker.check(*seq);
+                    if check != SequenceCheck::InOrder {
+                        self.violations.push(check);
+                    }
+                }
+            }
+        }
+
+        struct Received;
+
+        impl Message<Received> for MyActor {
+            type Reply = (Vec<(u64, u64)>, Vec<SequenceCheck>);
+
+            async fn handle(
+                &mut self,
+                _msg: Received,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                (self.received.clone(), self.violations.clone())
+            }
+        }
+
+        let actor_ref = spawn(MyActor::default());
+        let mut producers = Vec::new();
+        for producer in 0..PRODUCERS {
+            let actor_ref = actor_ref.clone();
+            producers.push(tokio::spawn(async move {
+                if producer % 2 == 0 {
+                    actor_ref
+                        .send_all_ordered((0..MESSAGES).map(|n| Msg { producer, n }))
+                        .await
+                        .map_err(|err| err.to_string())
+                } else {
+                    let mut sender = SequencedSender::new(actor_ref);
+                    for n in 0..MESSAGES {
+                        sender
+                            .tell(Msg { producer, n })
+                            .send()
+                            .await
+                            .map_err(|err| err.to_string())?;
+                        tokio::task::yield_now().await;
+                    }
+                    Ok(())
+                }
+            }));
+        }
+        for producer in producers {
+            producer.await??;
+        }
+
+        let (received, violations) = actor_ref.ask(Received).await?;
+        assert_eq!(received.len() as u64, PRODUCERS * MESSAGES);
+        for producer in 0..PRODUCERS {
+            let sent: Vec<_> = received
+                .iter()
+                .filter(|(p, _)| *p == producer)
+                .map(|(_, n)| *n)
+                .collect();
+            assert_eq!(sent, (0..MESSAGES).collect::<Vec<_>>());
+        }
+        assert_eq!(violations, []);
+
+        let mut tracker = SequenceTracker::new();
+        let seq = |seq| Sequence { sender: 0, seq };
+        assert_eq!(tracker.check(seq(0)), SequenceCheck::InOrder);
+        assert_eq!(tracker.check(seq(3)), SequenceCheck::Gap(1..3));
+        assert_eq!(
+            tracker.check(seq(2)),
+            SequenceCheck::OutOfOrder { expected: 4 }
+        );
+        assert_eq!(tracker.check(seq(4)), SequenceCheck::InOrder);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_reply_to_transport() -> Result<(), Box<dyn std::error::Error>> {
+        struct Doubler;
+
+        impl Actor for Doubler {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        impl Message<u32> for Doubler {
+            type Reply = Result<u32, String>;
+
+            async fn handle(
+                &mut self,
+                n: u32,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                if n == 0 {
+                    return Err("zero".to_string());
+                }
+                Ok(n * 2)
+            }
+        }
+
+        #[derive(Default)]
+        struct Collector {
+            replies: Vec<Result<u32, SendError<u32, String>>>,
+        }
+
+        impl Actor for Collector {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        impl Message<Result<u32, SendError<u32, String>>> for Collector {
+            type Reply = ();
+
+            async fn handle(
+                &mut self,
+                reply: Result<u32, SendError<u32, String>>,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) {
+                self.replies.push(reply);
+            }
+        }
+
+        struct Replies;
+
+        impl Message<Replies> for Collector {
+            type Reply = Vec<Result<u32, SendError<u32, String>>>;
+
+            async fn handle(
+                &mut self,
+                _msg: Replies,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.replies.clone()
+            }
+        }
+
+        let doubler = spawn(Doubler);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        for n in [1, 0, 3] {
+            doubler.tell(n).reply_to(tx.clone()).send().await?;
+        }
+        assert_eq!(rx.recv().await, Some(Ok(2)));
+        assert_eq!(
+            rx.recv().await,
+            Some(Err(SendError::HandlerError("zero".to_string())))
+        );
+        assert_eq!(rx.recv().await, Some(Ok(6)));
+        // The handler error went to the transport, rather than stopping the actor
+        assert!(doubler.is_alive());
+
+        let collector = spawn(Collector::default());
+        doubler.tell(4).reply_to(collector.clone()).send().await?;
+        doubler.tell(5).reply_to(collector.clone()).send().await?;
+        doubler.flush().await?;
+        collector.flush().await?;
+        assert_eq!(collector.ask(Replies).await?, vec![Ok(8), Ok(10)]);
+
+        // A message rejected when the actor stops is returned through the transport
+        let prepared_actor = PreparedActor::new();
+        let actor_ref = prepared_actor.actor_ref().clone();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        actor_ref.tell(7).reply_to(tx).send().await?;
+        actor_ref.kill();
+        prepared_actor.spawn(Doubler).await?;
+        assert_eq!(rx.recv().await, Some(Err(SendError::ActorNotRunning(7))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tell_requests_propagate_ambient_context() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct TenantId(u64);
+
+        struct Backend {
+            seen: Vec<Option<TenantId>>,
+        }
+
+        impl Actor for Backend {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Record;
+
+        impl Message<Record> for Backend {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Record, ctx: Context<'_, Self, Self::Reply>) {
+                self.seen.push(ctx.ambient::<TenantId>().copied());
+            }
+        }
+
+        struct Seen;
+
+        impl Message<Seen> for Backend {
+            type Reply = Vec<Option<TenantId>>;
+
+            async fn handle(
+                &mut self,
+                _msg: Seen,
+                _ctx: Context<'_, Self, Self::Reply>,
+            ) -> Self::Reply {
+                self.seen.clone()
+            }
+        }
+
+        struct Frontend {
+            backend: crate::actor::ActorRef<Backend>,
+        }
+
+        impl Actor for Frontend {
+            type Mailbox = UnboundedMailbox<Self>;
+        }
+
+        struct Forward;
+
+        impl Message<Forward> for Frontend {
+            type Reply = ();
+
+            async fn handle(&mut self, _msg: Forward, _ctx: Context<'_, Self, Self::Reply>) {
+                assert_eq!(Ambient::current().get::<TenantId>(), Some(&TenantId(7)));
+                self.backend.tell(Record).await.unwrap();
+            }
+        }
+
+        let backend = spawn(Backend { seen: vec![] });
+        let frontend = spawn(Frontend {
+            backend: backend.clone(),
+        });
+
+        // The context rides along from the tell, through the frontend, to the backend
+        Ambient::new()
+            .with(TenantId(7))
+            .scope(async { frontend.tell(Forward).await })
+            .await?;
+        frontend.flush().await?;
+        // Requests sent outside of a scope carry no context
+        backend.tell(Record).await?;
+        assert_eq!(backend.ask(Seen).await?, vec![Some(TenantId(7)), None]);
+
+        Ok(())
+    }
 }